@@ -0,0 +1,51 @@
+use board_plugin::resources::{BoardVariant, BombDensity, Symmetry, TileMap};
+use board_plugin::Coordinates;
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
+
+/// Benchmarked map sizes and bomb counts, from the expert board to huge boards
+const MAPS: [(u16, u16, u16); 3] = [(30, 16, 99), (100, 100, 2000), (300, 300, 18000)];
+
+/// Generates a seeded map
+fn generate(width: u16, height: u16, bomb_count: u16) -> TileMap {
+    let mut tile_map = TileMap::empty(width, height);
+    let (variant, density) = (BoardVariant::Classic, BombDensity::Uniform);
+    tile_map.set_bombs(bomb_count, 42, &variant, Symmetry::None, &density);
+    tile_map
+}
+
+fn generation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("generation");
+    for (width, height, bomb_count) in MAPS {
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", width, height)),
+            &(width, height, bomb_count),
+            |b, &(width, height, bomb_count)| b.iter(|| generate(width, height, bomb_count)),
+        );
+    }
+    group.finish();
+}
+
+fn bbbv(c: &mut Criterion) {
+    let mut group = c.benchmark_group("bbbv");
+    for (width, height, bomb_count) in MAPS {
+        let tile_map = generate(width, height, bomb_count);
+        group.bench_function(format!("{}x{}", width, height), |b| b.iter(|| tile_map.bbbv()));
+    }
+    group.finish();
+}
+
+fn flood_fill(c: &mut Criterion) {
+    let mut group = c.benchmark_group("flood_fill");
+    for (width, height, _) in MAPS {
+        // A single corner bomb, opening the whole map
+        let tile_map = TileMap::from_bomb_positions(width, height, &[Coordinates { x: 0, y: 0 }]);
+        let start = Coordinates { x: width - 1, y: height - 1 };
+        group.bench_function(format!("{}x{}", width, height), |b| {
+            b.iter(|| tile_map.opening_at(black_box(start)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, generation, bbbv, flood_fill);
+criterion_main!(benches);
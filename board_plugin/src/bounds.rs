@@ -1,16 +1,49 @@
+use crate::components::Coordinates;
 use bevy::prelude::Vec2;
 
-#[derive(Debug, Copy, Clone)]
+/// Axis aligned rectangle in world space, like the area covered by the board
+#[derive(Debug, Copy, Clone, PartialEq)]
 pub struct Bounds2 {
+    /// Bottom left corner
     pub position: Vec2,
     pub size: Vec2,
 }
 
 impl Bounds2 {
-    pub fn in_bounds(&self, coords: Vec2) -> bool {
-        coords.x >= self.position.x
-            && coords.y >= self.position.y
-            && coords.x <= self.position.x + self.size.x
-            && coords.y <= self.position.y + self.size.y
+    /// Is `point` inside the bounds, edges included
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.x <= self.position.x + self.size.x
+            && point.y <= self.position.y + self.size.y
+    }
+
+    /// Center of the bounds
+    pub fn center(&self) -> Vec2 {
+        self.position + self.size / 2.
+    }
+
+    /// Closest point to `point` inside the bounds
+    pub fn clamp(&self, point: Vec2) -> Vec2 {
+        point.clamp(self.position, self.position + self.size)
+    }
+
+    /// Coordinates of the tile at `world_pos`, for tiles of `tile_size` laid from the bottom left
+    /// corner. The top and right edges belong to no tile
+    pub fn tile_at(&self, world_pos: Vec2, tile_size: f32) -> Option<Coordinates> {
+        if !self.contains(world_pos) {
+            return None;
+        }
+        let tiles = (self.size / tile_size).round();
+        let coords = ((world_pos - self.position) / tile_size).floor();
+        if coords.x >= tiles.x || coords.y >= tiles.y {
+            return None;
+        }
+        Some(Coordinates { x: coords.x as u16, y: coords.y as u16 })
+    }
+
+    /// World position of the center of the tile at `coords`, for tiles of `tile_size`
+    pub fn tile_center(&self, coords: Coordinates, tile_size: f32) -> Vec2 {
+        self.position + (Vec2::new(coords.x as f32, coords.y as f32) + 0.5) * tile_size
     }
 }
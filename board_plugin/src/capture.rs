@@ -0,0 +1,290 @@
+//! Board capture of `ExportBoardImageEvent`: the board camera renders the board to an image for
+//! a frame, the image being copied back from the GPU once rendered. Only registered when
+//! rendering, headless apps rasterizing the board on the CPU instead
+use crate::components::Flag;
+use crate::resources::{BoardGeometry, BoardWindow};
+use crate::systems::export::{save_image, ExportTasks};
+use crate::CoverSprite;
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_asset::RenderAssets;
+use bevy::render::render_resource::{
+    BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+    ImageDataLayout, MapMode, TextureDimension, TextureFormat, TextureUsages,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::texture::BevyDefault;
+use bevy::render::{RenderApp, RenderStage};
+use bevy::tasks::IoTaskPool;
+use image::RgbaImage;
+use std::collections::VecDeque;
+use std::num::NonZeroU32;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
+/// Row alignment of texture to buffer copies, `wgpu::COPY_BYTES_PER_ROW_ALIGNMENT`
+const ROW_ALIGNMENT: u32 = 256;
+
+/// Pixels copied back from the GPU, by captured image
+type CapturedPixels = Arc<Mutex<Vec<(Handle<Image>, Vec<u8>)>>>;
+
+/// Registers the board capture, only when rendering
+pub(crate) fn build(app: &mut App) {
+    let captured = CapturedPixels::default();
+    let render_app = match app.get_sub_app_mut(RenderApp) {
+        Err(_) => return,
+        Ok(render_app) => render_app,
+    };
+    render_app
+        .insert_resource(captured.clone())
+        .add_system_to_stage(RenderStage::Extract, extract_capture)
+        // The render graph ran during the render stage
+        .add_system_to_stage(RenderStage::Cleanup, read_capture);
+    app.insert_resource(BoardCapture { captured, ..Default::default() }).add_system(capture_board);
+}
+
+/// Board exports waiting for their capture, one capture being rendered at a time
+#[derive(Default)]
+pub struct BoardCapture {
+    queue: VecDeque<CaptureRequest>,
+    stage: CaptureStage,
+    /// Rendered captures waiting for their pixels
+    reading: Vec<CaptureRequest>,
+    captured: CapturedPixels,
+}
+
+impl BoardCapture {
+    /// Queues the capture of the board, saved to `path` under the `header`
+    pub(crate) fn request(
+        &mut self,
+        path: PathBuf,
+        header: String,
+        background: [u8; 4],
+        solution: bool,
+    ) {
+        self.queue.push_back(CaptureRequest {
+            path,
+            header,
+            background,
+            solution,
+            image: Default::default(),
+            size: (0, 0),
+        });
+    }
+}
+
+struct CaptureRequest {
+    path: PathBuf,
+    header: String,
+    background: [u8; 4],
+    /// Are the covers hidden during the capture
+    solution: bool,
+    image: Handle<Image>,
+    size: (u32, u32),
+}
+
+enum CaptureStage {
+    Idle,
+    /// The target image was added this frame, the camera being retargeted on the next one so
+    /// its projection follows the image size
+    Preparing(CaptureRequest),
+    /// The board camera renders to the image this frame, its previous state being restored on
+    /// the next one
+    Rendering {
+        request: CaptureRequest,
+        camera: Entity,
+        saved: SavedCamera,
+        hidden: Vec<Entity>,
+    },
+}
+
+impl Default for CaptureStage {
+    fn default() -> Self {
+        Self::Idle
+    }
+}
+
+/// Board camera state before the capture, its projection following the restored target
+struct SavedCamera {
+    target: RenderTarget,
+    scale: f32,
+    transform: Transform,
+}
+
+/// Capture rendered this frame, in the render world
+struct ExtractedCapture {
+    image: Handle<Image>,
+    size: (u32, u32),
+}
+
+/// System moving the board capture along, one stage per frame, and saving the captured images
+fn capture_board(
+    mut capture: ResMut<BoardCapture>,
+    geometry: Option<Res<BoardGeometry>>,
+    board_window: Res<BoardWindow>,
+    pool: Res<IoTaskPool>,
+    mut images: ResMut<Assets<Image>>,
+    mut tasks: ResMut<ExportTasks>,
+    mut cameras: Query<(Entity, &mut Camera, &mut OrthographicProjection, &mut Transform)>,
+    mut covers: Query<(Entity, &mut Visibility), Or<(With<CoverSprite>, With<Flag>)>>,
+) {
+    let capture = &mut *capture;
+    match std::mem::take(&mut capture.stage) {
+        CaptureStage::Idle => (),
+        CaptureStage::Preparing(request) => {
+            let board_camera =
+                cameras.iter_mut().find(|(_, camera, ..)| board_window.is_board_camera(camera));
+            match (board_camera, &geometry) {
+                (Some((entity, mut camera, mut projection, mut transform)), Some(geometry)) => {
+                    let saved = SavedCamera {
+                        target: camera.target.clone(),
+                        scale: projection.scale,
+                        transform: *transform,
+                    };
+                    camera.target = RenderTarget::Image(request.image.clone());
+                    projection.scale = 1.;
+                    transform.translation =
+                        geometry.bounds.center().extend(transform.translation.z);
+                    transform.scale = Vec3::new(1., 1., transform.scale.z);
+                    let mut hidden = Vec::new();
+                    if request.solution {
+                        for (entity, mut visibility) in covers.iter_mut() {
+                            if visibility.is_visible {
+                                visibility.is_visible = false;
+                                hidden.push(entity);
+                            }
+                        }
+                    }
+                    capture.stage =
+                        CaptureStage::Rendering { request, camera: entity, saved, hidden };
+                }
+                _ => {
+                    warn!("No board camera to export the board");
+                    images.remove(&request.image);
+                }
+            }
+        }
+        CaptureStage::Rendering { request, camera, saved, hidden } => {
+            if let Ok((_, mut c, mut projection, mut transform)) = cameras.get_mut(camera) {
+                c.target = saved.target;
+                projection.scale = saved.scale;
+                *transform = saved.transform;
+            }
+            for entity in hidden {
+                if let Ok((_, mut visibility)) = covers.get_mut(entity) {
+                    visibility.is_visible = true;
+                }
+            }
+            capture.reading.push(request);
+        }
+    }
+    // The next capture starts once the board camera is restored
+    if matches!(capture.stage, CaptureStage::Idle) {
+        let request = geometry.as_ref().and_then(|_| capture.queue.pop_front());
+        if let (Some(mut request), Some(geometry)) = (request, &geometry) {
+            // Rotated views are captured whole
+            let (sin, cos) = geometry.view.rotation.to_radians().sin_cos();
+            let (sin, cos) = (sin.abs(), cos.abs());
+            let size = geometry.bounds.size;
+            let size = Vec2::new(size.x * cos + size.y * sin, size.x * sin + size.y * cos);
+            request.size = (size.x.ceil().max(1.) as u32, size.y.ceil().max(1.) as u32);
+            request.image = images.add(target_image(request.size));
+            capture.stage = CaptureStage::Preparing(request);
+        }
+    }
+    let captured: Vec<_> = capture.captured.lock().unwrap().drain(..).collect();
+    for (image, mut pixels) in captured {
+        let index = match capture.reading.iter().position(|r| r.image == image) {
+            None => continue,
+            Some(i) => i,
+        };
+        let request = capture.reading.swap_remove(index);
+        images.remove(&request.image);
+        let (width, height) = request.size;
+        if TextureFormat::bevy_default() == TextureFormat::Bgra8UnormSrgb {
+            pixels.chunks_exact_mut(4).for_each(|pixel| pixel.swap(0, 2));
+        }
+        let image = match RgbaImage::from_raw(width, height, pixels) {
+            None => {
+                error!("Invalid board capture of {}", request.path.display());
+                continue;
+            }
+            Some(i) => i,
+        };
+        tasks.0.push(save_image(&pool, request.path, image, request.header, request.background));
+    }
+}
+
+/// Image the board camera renders to, read back once rendered
+fn target_image((width, height): (u32, u32)) -> Image {
+    let size = Extent3d { width, height, depth_or_array_layers: 1 };
+    let mut image =
+        Image::new_fill(size, TextureDimension::D2, &[0, 0, 0, 0], TextureFormat::bevy_default());
+    image.texture_descriptor.usage = TextureUsages::TEXTURE_BINDING
+        | TextureUsages::COPY_DST
+        | TextureUsages::COPY_SRC
+        | TextureUsages::RENDER_ATTACHMENT;
+    image
+}
+
+/// Extract system passing the capture rendered this frame to the render world
+fn extract_capture(mut commands: Commands, capture: Res<BoardCapture>) {
+    if let CaptureStage::Rendering { request, .. } = &capture.stage {
+        commands
+            .insert_resource(ExtractedCapture { image: request.image.clone(), size: request.size });
+    }
+}
+
+/// Render system copying the captured image back from the GPU, rows being padded in the copy
+fn read_capture(
+    mut commands: Commands,
+    capture: Option<Res<ExtractedCapture>>,
+    gpu_images: Res<RenderAssets<Image>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    captured: Res<CapturedPixels>,
+) {
+    let capture = match capture {
+        None => return,
+        Some(c) => c,
+    };
+    commands.remove_resource::<ExtractedCapture>();
+    let gpu_image = match gpu_images.get(&capture.image) {
+        None => return,
+        Some(i) => i,
+    };
+    let (width, height) = capture.size;
+    let row = width * 4;
+    let padded_row = (row + ROW_ALIGNMENT - 1) / ROW_ALIGNMENT * ROW_ALIGNMENT;
+    let buffer = render_device.create_buffer(&BufferDescriptor {
+        label: Some("board_capture_buffer"),
+        size: (padded_row * height) as u64,
+        usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+        mapped_at_creation: false,
+    });
+    let mut encoder = render_device
+        .create_command_encoder(&CommandEncoderDescriptor { label: Some("board_capture") });
+    encoder.copy_texture_to_buffer(
+        gpu_image.texture.as_image_copy(),
+        ImageCopyBuffer {
+            buffer: &buffer,
+            layout: ImageDataLayout {
+                offset: 0,
+                bytes_per_row: NonZeroU32::new(padded_row),
+                rows_per_image: None,
+            },
+        },
+        Extent3d { width, height, depth_or_array_layers: 1 },
+    );
+    render_queue.submit([encoder.finish()]);
+    let slice = buffer.slice(..);
+    render_device.map_buffer(&slice, MapMode::Read);
+    let pixels: Vec<u8> = slice
+        .get_mapped_range()
+        .chunks(padded_row as usize)
+        .flat_map(|padded| &padded[..row as usize])
+        .copied()
+        .collect();
+    buffer.unmap();
+    captured.lock().unwrap().push((capture.image.clone(), pixels));
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Auto flag component, placed on flags placed by the `BoardOptions::auto_flag` assist
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct AutoFlag;
@@ -4,6 +4,6 @@ use bevy::prelude::Component;
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
 pub struct BombNeighbor {
-    /// Number of neighbor bombs
-    pub count: u8,
+    /// Number of neighbor bombs, negative around anti-mines
+    pub count: i8,
 }
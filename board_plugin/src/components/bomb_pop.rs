@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Pop animation component, scales a tile up and back down while its timer runs
+#[derive(Debug, Clone, Component)]
+pub struct BombPop(pub Timer);
@@ -0,0 +1,18 @@
+use crate::Coordinates;
+use bevy::prelude::*;
+use bevy::tasks::Task;
+
+/// Opening being computed on the async compute pool, input being deferred until it is uncovered
+#[derive(Component)]
+pub struct CascadeInProgress {
+    /// Empty tile the opening spreads from
+    pub origin: Coordinates,
+    /// Flood fill task, spawned by `compute_cascades`
+    pub task: Option<Task<Vec<Coordinates>>>,
+}
+
+impl CascadeInProgress {
+    pub fn new(origin: Coordinates) -> Self {
+        Self { origin, task: None }
+    }
+}
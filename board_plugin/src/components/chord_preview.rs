@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Chord preview component, placed on the tile covers a chord would uncover
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct ChordPreview;
@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Confetti particle of the win celebration, despawned on any input
+#[derive(Debug, Copy, Clone, Component)]
+pub struct Confetti;
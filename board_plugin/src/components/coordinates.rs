@@ -1,7 +1,18 @@
 use bevy::prelude::Component;
+use serde::{Deserialize, Serialize};
 use std::fmt::{self, Display, Formatter};
 use std::ops::{Add, Sub};
 
+/// Offsets of the orthogonal neighbors
+const ORTHOGONAL_OFFSETS: [(i16, i16); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+/// Offsets of the diagonal neighbors, bottom row first
+const DIAGONAL_OFFSETS: [(i16, i16); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+/// Offsets of the orthogonal and diagonal neighbors, bottom row first
+const SQUARE_OFFSETS: [(i16, i16); 8] =
+    [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+
+/// Tile board position, with a bottom left origin
+#[derive(Serialize, Deserialize)]
 #[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
 #[derive(Debug, Default, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
 pub struct Coordinates {
@@ -9,6 +20,90 @@ pub struct Coordinates {
     pub y: u16,
 }
 
+/// Which tiles are neighbors of a tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Adjacency {
+    /// The 4 tiles sharing a side
+    Orthogonal,
+    /// The 4 tiles sharing a corner only
+    Diagonal,
+    /// The 8 tiles sharing a side or a corner
+    Square,
+}
+
+impl Adjacency {
+    /// Neighbor offsets
+    pub const fn offsets(&self) -> &'static [(i16, i16)] {
+        match self {
+            Self::Orthogonal => &ORTHOGONAL_OFFSETS,
+            Self::Diagonal => &DIAGONAL_OFFSETS,
+            Self::Square => &SQUARE_OFFSETS,
+        }
+    }
+}
+
+impl Coordinates {
+    /// Coordinates of the `x` column and `y` row
+    pub const fn new(x: u16, y: u16) -> Self {
+        Self { x, y }
+    }
+
+    /// Coordinates moved by `(dx, dy)`, `None` if out of the `u16` range
+    pub fn offset(self, dx: i16, dy: i16) -> Option<Self> {
+        let x = u16::try_from(self.x as i32 + dx as i32).ok()?;
+        let y = u16::try_from(self.y as i32 + dy as i32).ok()?;
+        Some(Self { x, y })
+    }
+
+    /// Sum, `None` on overflow
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        Some(Self { x: self.x.checked_add(rhs.x)?, y: self.y.checked_add(rhs.y)? })
+    }
+
+    /// Difference, `None` if a component would be negative
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        Some(Self { x: self.x.checked_sub(rhs.x)?, y: self.y.checked_sub(rhs.y)? })
+    }
+
+    /// Sum, clamped to `u16::MAX`
+    pub fn saturating_add(self, rhs: Self) -> Self {
+        Self { x: self.x.saturating_add(rhs.x), y: self.y.saturating_add(rhs.y) }
+    }
+
+    /// Difference, clamped to zero
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self { x: self.x.saturating_sub(rhs.x), y: self.y.saturating_sub(rhs.y) }
+    }
+
+    /// Neighbors with non negative coordinates, bottom row first. Board bounds are not checked
+    pub fn neighbors(self, adjacency: Adjacency) -> impl Iterator<Item = Self> {
+        adjacency.offsets().iter().filter_map(move |(dx, dy)| self.offset(*dx, *dy))
+    }
+
+    /// Row-major index in a board of `width` columns
+    pub fn to_index(self, width: u16) -> usize {
+        self.y as usize * width as usize + self.x as usize
+    }
+
+    /// Coordinates of the row-major `index` in a board of `width` columns
+    pub fn from_index(index: usize, width: u16) -> Self {
+        let width = width as usize;
+        Self { x: (index % width) as u16, y: (index / width) as u16 }
+    }
+}
+
+impl From<(u16, u16)> for Coordinates {
+    fn from((x, y): (u16, u16)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl From<Coordinates> for (u16, u16) {
+    fn from(coords: Coordinates) -> Self {
+        (coords.x, coords.y)
+    }
+}
+
 // We want to be able to make coordinates sums..
 impl Add for Coordinates {
     type Output = Self;
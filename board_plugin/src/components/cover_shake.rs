@@ -0,0 +1,5 @@
+use bevy::prelude::*;
+
+/// Tile cover shake animation, played when a flag is rejected
+#[derive(Debug, Clone, Component)]
+pub struct CoverShake(pub Timer);
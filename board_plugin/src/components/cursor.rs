@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Cursor component, placed on the keyboard cursor sprite
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Cursor;
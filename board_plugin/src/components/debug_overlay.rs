@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+/// Debug overlay component, placed on the root of the bomb and region markers and tile counts
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct DebugOverlay;
+
+/// Debug overlay tile counts text
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct DebugCounts;
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Detonated bomb component, placed on bomb tiles uncovered while lives remained
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Detonated;
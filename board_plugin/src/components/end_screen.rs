@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// End screen component, placed on the overlay displayed over a won or lost board
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct EndScreen;
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Flag component, placed on flag sprites over tile covers
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Flag;
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+
+/// Flag sprite animation
+#[derive(Debug, Clone, Component)]
+pub enum FlagAnimation {
+    /// The flag falls from `height` and bounces on the tile
+    Drop { timer: Timer, height: f32 },
+    /// The flag fades out and is then despawned
+    FadeOut(Timer),
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+/// Fog overlay component, placed on the sprite darkening a tile cover out of sight of the
+/// uncovered tiles
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct FogOverlay;
@@ -0,0 +1,6 @@
+use crate::components::Coordinates;
+use bevy::prelude::Component;
+
+/// Ghost overlay component, placed on the sprites showing the tiles uncovered by a replay
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct GhostTile(pub Coordinates);
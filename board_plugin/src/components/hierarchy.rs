@@ -0,0 +1,26 @@
+use bevy::prelude::Component;
+
+/// Board root component, placed on the entity every board entity descends from
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct BoardRoot;
+
+/// Tile root component, placed on the tile entities along with their `Coordinates`
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct TileRoot;
+
+/// Cover sprite component, placed on the sprite hiding a covered tile
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct CoverSprite;
+
+/// Bomb sprite component, placed on the sprite drawing the bomb of a tile
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct BombSprite;
+
+/// Number text component, placed on the bomb counter of a tile, a text or an atlas sprite
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct NumberText;
@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+/// Mini-map component, placed on the sprite drawing the board tiles in a corner of the window
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct MiniMapView;
+
+/// Mini-map viewport component, placed on the sprite outlining the camera view on the mini-map
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct MiniMapViewport;
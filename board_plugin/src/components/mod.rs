@@ -1,9 +1,67 @@
+pub use auto_flag::AutoFlag;
 pub use bomb::Bomb;
 pub use bomb_neighbor::BombNeighbor;
-pub use coordinates::Coordinates;
+pub use bomb_pop::BombPop;
+pub use cascade_in_progress::CascadeInProgress;
+pub use chord_preview::ChordPreview;
+pub use confetti::Confetti;
+pub use coordinates::{Adjacency, Coordinates};
+pub use cover_shake::CoverShake;
+pub use cursor::Cursor;
+#[cfg(feature = "debug")]
+pub use debug_overlay::{DebugCounts, DebugOverlay};
+pub use detonated::Detonated;
+pub use end_screen::EndScreen;
+pub use flag::Flag;
+pub use flag_animation::FlagAnimation;
+pub use fog_overlay::FogOverlay;
+pub use ghost_tile::GhostTile;
+pub use hierarchy::{BoardRoot, BombSprite, CoverSprite, NumberText, TileRoot};
+pub use mini_map::{MiniMapView, MiniMapViewport};
+pub use night_shade::NightShade;
+pub use number_badge::NumberBadge;
+pub use particle::Particle;
+pub use pressed::Pressed;
+pub use progress_bar::{ProgressBarFill, ProgressBarView};
+pub use region_tint::RegionTint;
+pub use tile_modifier::TileModifier;
+pub use tooltip::Tooltip;
+pub use tutorial_highlight::TutorialHighlight;
+pub use tutorial_message::TutorialMessage;
+pub use tween::{Easing, Tween, TweenTarget};
 pub use uncover::Uncover;
+pub use win_flag::WinFlag;
 
+mod auto_flag;
 mod bomb;
 mod bomb_neighbor;
+mod bomb_pop;
+mod cascade_in_progress;
+mod chord_preview;
+mod confetti;
 mod coordinates;
+mod cover_shake;
+mod cursor;
+#[cfg(feature = "debug")]
+mod debug_overlay;
+mod detonated;
+mod end_screen;
+mod flag;
+mod flag_animation;
+mod fog_overlay;
+mod ghost_tile;
+mod hierarchy;
+mod mini_map;
+mod night_shade;
+mod number_badge;
+mod particle;
+mod pressed;
+mod progress_bar;
+mod region_tint;
+mod tile_modifier;
+mod tooltip;
+mod tutorial_highlight;
+mod tutorial_message;
+mod tween;
 mod uncover;
+mod win_flag;
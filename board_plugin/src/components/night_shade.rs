@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Night shade component, placed on the sprite darkening a tile in night mode
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct NightShade;
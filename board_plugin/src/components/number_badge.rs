@@ -0,0 +1,7 @@
+use bevy::prelude::Component;
+
+/// Number badge component, placed on the dot pattern drawn next to bomb counters. The badge is
+/// laid out for a unit tile and scaled to the tile size
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct NumberBadge;
@@ -0,0 +1,12 @@
+use bevy::prelude::*;
+
+/// Short lived sprite, moved and faded out until its lifetime ends
+#[derive(Debug, Clone, Component)]
+pub struct Particle {
+    /// Velocity in world units per second
+    pub velocity: Vec2,
+    /// Downward acceleration in world units per second squared
+    pub gravity: f32,
+    /// Remaining lifetime
+    pub lifetime: Timer,
+}
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Pressed component, placed on the tile cover under a held mouse button
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Pressed;
@@ -0,0 +1,11 @@
+use bevy::prelude::Component;
+
+/// Progress bar component, placed on the sprite of the bar background
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct ProgressBarView;
+
+/// Progress bar fill component, placed on the sprite filling the bar as tiles are uncovered
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct ProgressBarFill;
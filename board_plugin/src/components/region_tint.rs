@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Region tint component, placed on the sprite coloring a cover of a color regions board
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct RegionTint;
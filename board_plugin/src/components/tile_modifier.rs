@@ -0,0 +1,16 @@
+use bevy::prelude::Component;
+
+/// Modifier of a tile, see `TileModifiers`. Placed on the sprite drawn over the tile cover
+/// while the modifier applies
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Component)]
+pub enum TileModifier {
+    /// Takes two uncover attempts, the first one cracking the ice
+    Ice,
+    /// Can't be uncovered until every key is uncovered
+    Locked,
+    /// Unlocks the locked tiles once every key is uncovered
+    Key,
+    /// Uncovers its paired teleport along with it
+    Teleport,
+}
@@ -0,0 +1,7 @@
+use crate::components::Coordinates;
+use bevy::prelude::Component;
+
+/// Tooltip component, placed on the text describing the hovered numbered tile
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct Tooltip(pub Coordinates);
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Tutorial highlight component, placed on the tutorial highlight sprite
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct TutorialHighlight;
@@ -0,0 +1,6 @@
+use bevy::prelude::Component;
+
+/// Tutorial message component, placed on the tutorial instruction text
+#[cfg_attr(feature = "debug", derive(bevy_inspector_egui::Inspectable))]
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Component)]
+pub struct TutorialMessage;
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+
+/// Easing curves of a `Tween`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Easing {
+    Linear,
+    /// Decelerates towards the end
+    QuadOut,
+    /// Overshoots the end value slightly before settling
+    BackOut,
+    /// Bounces on the end value
+    BounceOut,
+}
+
+impl Easing {
+    /// Eased progress of a `t` progress from 0 to 1
+    pub fn apply(&self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::QuadOut => 1. - (1. - t) * (1. - t),
+            Self::BackOut => {
+                const C1: f32 = 1.70158;
+                const C3: f32 = C1 + 1.;
+                let t = t - 1.;
+                1. + C3 * t * t * t + C1 * t * t
+            }
+            Self::BounceOut => {
+                const N: f32 = 7.5625;
+                const D: f32 = 2.75;
+                if t < 1. / D {
+                    N * t * t
+                } else if t < 2. / D {
+                    let t = t - 1.5 / D;
+                    N * t * t + 0.75
+                } else if t < 2.5 / D {
+                    let t = t - 2.25 / D;
+                    N * t * t + 0.9375
+                } else {
+                    let t = t - 2.625 / D;
+                    N * t * t + 0.984375
+                }
+            }
+        }
+    }
+}
+
+/// Transform property animated by a `Tween`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum TweenTarget {
+    Translation {
+        from: Vec3,
+        to: Vec3,
+    },
+    Scale {
+        from: Vec3,
+        to: Vec3,
+    },
+    /// Translation, rotation and scale at once, like camera moves
+    Transform {
+        from: Transform,
+        to: Transform,
+    },
+}
+
+/// Generic transform animation of any entity, removed once its timer finishes
+#[derive(Debug, Clone, Component)]
+pub struct Tween {
+    pub timer: Timer,
+    pub target: TweenTarget,
+    pub easing: Easing,
+}
+
+impl Tween {
+    /// Tween lasting `duration` seconds
+    pub fn new(duration: f32, target: TweenTarget, easing: Easing) -> Self {
+        Self { timer: Timer::from_seconds(duration, false), target, easing }
+    }
+
+    /// Applies the current progress to `transform`
+    pub fn apply(&self, transform: &mut Transform) {
+        let t = self.easing.apply(self.timer.percent());
+        match self.target {
+            TweenTarget::Translation { from, to } => transform.translation = from.lerp(to, t),
+            TweenTarget::Scale { from, to } => transform.scale = from.lerp(to, t),
+            TweenTarget::Transform { from, to } => {
+                transform.translation = from.translation.lerp(to.translation, t);
+                transform.rotation = from.rotation.slerp(to.rotation, t);
+                transform.scale = from.scale.lerp(to.scale, t);
+            }
+        }
+    }
+}
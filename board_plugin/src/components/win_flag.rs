@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+use std::ops::Range;
+
+/// Cover flagged when the board is completed, its flags dropping once the timer finishes
+#[derive(Debug, Clone, Component)]
+pub struct WinFlag {
+    pub delay: Timer,
+    /// Indices of the flags to drop
+    pub flags: Range<u8>,
+}
@@ -0,0 +1,55 @@
+#import bevy_sprite::mesh2d_view_bind_group
+#import bevy_sprite::mesh2d_struct
+
+struct CoverMaterial {
+    color: vec4<f32>;
+    glow_color: vec4<f32>;
+    bevel: f32;
+    emboss: f32;
+    glow: f32;
+    dissolve: f32;
+};
+
+[[group(1), binding(0)]]
+var<uniform> material: CoverMaterial;
+
+struct FragmentInput {
+    [[builtin(front_facing)]] is_front: bool;
+    [[location(0)]] world_position: vec4<f32>;
+    [[location(1)]] world_normal: vec3<f32>;
+    [[location(2)]] uv: vec2<f32>;
+#ifdef VERTEX_TANGENTS
+    [[location(3)]] world_tangent: vec4<f32>;
+#endif
+};
+
+// Pseudo random value from 0 to 1, steady for a given position
+fn noise(uv: vec2<f32>) -> f32 {
+    return fract(sin(dot(floor(uv * 12.0), vec2<f32>(12.9898, 78.233))) * 43758.5453);
+}
+
+[[stage(fragment)]]
+fn fragment(in: FragmentInput) -> [[location(0)]] vec4<f32> {
+    var color = material.color.rgb;
+    // Distance to the closest edge, the top left edges catching the light
+    let edge = min(min(in.uv.x, 1.0 - in.uv.x), min(in.uv.y, 1.0 - in.uv.y));
+    if (edge < material.bevel) {
+        var shade = -material.emboss;
+        if (min(in.uv.x, in.uv.y) <= min(1.0 - in.uv.x, 1.0 - in.uv.y)) {
+            shade = material.emboss;
+        }
+        color = color * (1.0 + shade);
+    }
+    // The glow is brighter along the edges
+    let rim = 1.0 - clamp(edge * 4.0, 0.0, 1.0);
+    color = color + material.glow_color.rgb * material.glow * (0.3 + 0.7 * rim);
+    // Cells vanish one by one, their border burning before they do
+    let threshold = noise(in.uv);
+    if (threshold < material.dissolve) {
+        discard;
+    }
+    if (threshold < material.dissolve + 0.1 && material.dissolve > 0.0) {
+        color = material.glow_color.rgb;
+    }
+    return vec4<f32>(color, material.color.a);
+}
@@ -0,0 +1,297 @@
+//! Custom 2D material of the tile covers, drawing the bevel, hover glow and reveal dissolve
+//! effects of `BoardAssets::cover_style` in a single quad per tile. Covers keep their `Sprite`
+//! as the source of their color and size, so the systems painting covers are left unchanged,
+//! but lose their texture so only the material gets rendered
+use crate::events::TileUncoveredEvent;
+use crate::labels::BoardUncover;
+use crate::resources::{BoardAssets, BoardGeometry, CoverStyle, HoveredTile};
+use crate::{Board, Coordinates};
+use bevy::ecs::system::{lifetimeless::SRes, SystemParamItem};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::render::render_asset::{PrepareAssetError, RenderAsset};
+use bevy::render::render_resource::std140::{AsStd140, Std140};
+use bevy::render::render_resource::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingType, Buffer, BufferBindingType, BufferInitDescriptor, BufferSize,
+    BufferUsages, ShaderStages,
+};
+use bevy::render::renderer::RenderDevice;
+use bevy::sprite::{
+    Material2d, Material2dPipeline, Material2dPlugin, MaterialMesh2dBundle, Mesh2dHandle,
+};
+use bevy::utils::{HashMap, HashSet};
+
+const COVER_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 0x5f1e_c0de_7ac0_7e25);
+
+/// Registers the cover material, only when rendering
+pub(crate) fn build(app: &mut App) {
+    if !app.world.contains_resource::<Assets<Shader>>() {
+        return;
+    }
+    app.world
+        .resource_mut::<Assets<Shader>>()
+        .set_untracked(COVER_SHADER_HANDLE, Shader::from_wgsl(include_str!("cover.wgsl")));
+    app.add_plugin(Material2dPlugin::<CoverMaterial>::default())
+        .init_resource::<CoverMeshes>()
+        .add_system(attach_cover_materials)
+        .add_system(update_cover_materials)
+        .add_system(dissolve_covers.after(BoardUncover));
+}
+
+/// Tile cover material
+#[derive(Debug, Clone, TypeUuid)]
+#[uuid = "9b7e41d2-3c55-4d0b-a1f6-52c8e7d3b0a4"]
+pub struct CoverMaterial {
+    pub color: Color,
+    pub style: CoverStyle,
+    /// Hover glow, from 0 to 1
+    pub glow: f32,
+    /// Dissolved part of the cover, from 0 to 1
+    pub dissolve: f32,
+}
+
+impl CoverMaterial {
+    fn new(color: Color, style: CoverStyle) -> Self {
+        Self { color, style, glow: 0., dissolve: 0. }
+    }
+}
+
+#[derive(Clone, AsStd140)]
+struct CoverMaterialUniformData {
+    color: Vec4,
+    glow_color: Vec4,
+    bevel: f32,
+    emboss: f32,
+    glow: f32,
+    dissolve: f32,
+}
+
+pub struct GpuCoverMaterial {
+    _buffer: Buffer,
+    bind_group: BindGroup,
+}
+
+impl RenderAsset for CoverMaterial {
+    type ExtractedAsset = CoverMaterial;
+    type PreparedAsset = GpuCoverMaterial;
+    type Param = (SRes<RenderDevice>, SRes<Material2dPipeline<CoverMaterial>>);
+
+    fn extract_asset(&self) -> Self::ExtractedAsset {
+        self.clone()
+    }
+
+    fn prepare_asset(
+        material: Self::ExtractedAsset,
+        (render_device, pipeline): &mut SystemParamItem<Self::Param>,
+    ) -> Result<Self::PreparedAsset, PrepareAssetError<Self::ExtractedAsset>> {
+        let value = CoverMaterialUniformData {
+            color: Vec4::from(material.color.as_linear_rgba_f32()),
+            glow_color: Vec4::from(material.style.glow_color.as_linear_rgba_f32()),
+            bevel: material.style.bevel,
+            emboss: material.style.emboss,
+            glow: material.glow,
+            dissolve: material.dissolve,
+        };
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("cover_material_uniform_buffer"),
+            usage: BufferUsages::UNIFORM | BufferUsages::COPY_DST,
+            contents: value.as_std140().as_bytes(),
+        });
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            entries: &[BindGroupEntry { binding: 0, resource: buffer.as_entire_binding() }],
+            label: Some("cover_material_bind_group"),
+            layout: &pipeline.material2d_layout,
+        });
+        Ok(GpuCoverMaterial { _buffer: buffer, bind_group })
+    }
+}
+
+impl Material2d for CoverMaterial {
+    fn fragment_shader(_asset_server: &AssetServer) -> Option<Handle<Shader>> {
+        Some(COVER_SHADER_HANDLE.typed())
+    }
+
+    fn bind_group(render_asset: &<Self as RenderAsset>::PreparedAsset) -> &BindGroup {
+        &render_asset.bind_group
+    }
+
+    fn bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+        render_device.create_bind_group_layout(&BindGroupLayoutDescriptor {
+            entries: &[BindGroupLayoutEntry {
+                binding: 0,
+                visibility: ShaderStages::FRAGMENT,
+                ty: BindingType::Buffer {
+                    ty: BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: BufferSize::new(
+                        CoverMaterialUniformData::std140_size_static() as u64,
+                    ),
+                },
+                count: None,
+            }],
+            label: Some("cover_material_layout"),
+        })
+    }
+}
+
+/// Cover drawn with the cover material
+#[derive(Debug, Component)]
+struct MaterialCover {
+    coords: Coordinates,
+    size: f32,
+}
+
+/// Dissolving copy of an uncovered cover, despawned once dissolved
+#[derive(Debug, Component)]
+struct DissolvingCover(Timer);
+
+/// Square meshes of the cover sizes, shared by the covers. Must be used as a resource
+#[derive(Default)]
+struct CoverMeshes(HashMap<u32, Handle<Mesh>>);
+
+impl CoverMeshes {
+    fn get(&mut self, meshes: &mut Assets<Mesh>, size: f32) -> Handle<Mesh> {
+        self.0
+            .entry(size.to_bits())
+            .or_insert_with(|| meshes.add(Mesh::from(shape::Quad::new(Vec2::splat(size)))))
+            .clone()
+    }
+}
+
+/// Swaps the textures of the spawned covers for the cover material
+fn attach_cover_materials(
+    mut commands: Commands,
+    board: Option<Res<Board>>,
+    geometry: Option<Res<BoardGeometry>>,
+    board_assets: Res<BoardAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CoverMaterial>>,
+    mut cover_meshes: ResMut<CoverMeshes>,
+    sprites: Query<(Entity, &Parent, &Sprite), (Added<Sprite>, Without<MaterialCover>)>,
+    atlas_sprites: Query<
+        (Entity, &Parent, &TextureAtlasSprite),
+        (Added<TextureAtlasSprite>, Without<MaterialCover>),
+    >,
+    tiles: Query<&Coordinates>,
+) {
+    let (board, geometry) = match (board, geometry) {
+        (Some(b), Some(g)) => (b, g),
+        _ => return,
+    };
+    // Meshes of outdated sizes are dropped along with their last covers
+    if board.is_added() {
+        cover_meshes.0.clear();
+    }
+    let spawned = sprites
+        .iter()
+        .map(|(e, p, s)| (e, p, s.color, s.custom_size))
+        .chain(atlas_sprites.iter().map(|(e, p, s)| (e, p, s.color, s.custom_size)));
+    for (entity, parent, color, custom_size) in spawned {
+        let coords = match tiles.get(parent.0) {
+            Err(_) => continue,
+            Ok(c) => *c,
+        };
+        if board.covered_tiles.get(&coords) != Some(&entity) {
+            continue;
+        }
+        let size = custom_size.map_or(geometry.tile_size, |s| s.x);
+        let material = CoverMaterial::new(color, board_assets.cover_style);
+        commands
+            .entity(entity)
+            .remove::<Handle<Image>>()
+            .remove::<TextureAtlasSprite>()
+            .remove::<Handle<TextureAtlas>>()
+            .insert(Mesh2dHandle(cover_meshes.get(&mut meshes, size)))
+            .insert(materials.add(material))
+            // Atlas covers get a sprite as well, to be painted like the others
+            .insert(Sprite { color, custom_size, ..Default::default() })
+            .insert(MaterialCover { coords, size });
+    }
+}
+
+/// Mirrors the cover sprites in their material, and fades the hover glow
+fn update_cover_materials(
+    time: Res<Time>,
+    hovered: Res<HoveredTile>,
+    board_assets: Res<BoardAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<CoverMaterial>>,
+    mut cover_meshes: ResMut<CoverMeshes>,
+    mut covers: Query<(&mut MaterialCover, &Sprite, &Handle<CoverMaterial>, &mut Mesh2dHandle)>,
+) {
+    let style = board_assets.cover_style;
+    let step = match style.glow_fade > 0. {
+        true => time.delta_seconds() / style.glow_fade,
+        false => 1.,
+    };
+    for (mut cover, sprite, handle, mut mesh) in covers.iter_mut() {
+        let size = sprite.custom_size.map_or(cover.size, |s| s.x);
+        if size != cover.size {
+            cover.size = size;
+            mesh.0 = cover_meshes.get(&mut meshes, size);
+        }
+        let material = match materials.get(handle) {
+            None => continue,
+            Some(m) => m,
+        };
+        let target = if hovered.0 == Some(cover.coords) { 1. } else { 0. };
+        let glow = match target > material.glow {
+            true => (material.glow + step).min(target),
+            false => (material.glow - step).max(target),
+        };
+        // Unchanged materials are left untouched so they aren't prepared again
+        if glow != material.glow || sprite.color != material.color || style != material.style {
+            let material = materials.get_mut(handle).expect("checked above");
+            material.glow = glow;
+            material.color = sprite.color;
+            material.style = style;
+        }
+    }
+}
+
+/// Replaces the uncovered covers by dissolving copies
+fn dissolve_covers(
+    mut commands: Commands,
+    time: Res<Time>,
+    board_assets: Res<BoardAssets>,
+    mut materials: ResMut<Assets<CoverMaterial>>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    covers: Query<(&MaterialCover, &Parent, &Transform, &Mesh2dHandle, &Handle<CoverMaterial>)>,
+    mut dissolving: Query<(Entity, &mut DissolvingCover, &Handle<CoverMaterial>)>,
+) {
+    let duration = board_assets.cover_style.dissolve_duration;
+    let uncovered: HashSet<_> = tile_uncovered_evr.iter().map(|e| e.coords).collect();
+    if !uncovered.is_empty() && duration > 0. {
+        for (cover, parent, transform, mesh, handle) in covers.iter() {
+            if !uncovered.contains(&cover.coords) {
+                continue;
+            }
+            let material = match materials.get(handle) {
+                None => continue,
+                Some(m) => CoverMaterial { glow: 0., ..m.clone() },
+            };
+            let material = materials.add(material);
+            commands.entity(parent.0).with_children(|parent| {
+                parent
+                    .spawn_bundle(MaterialMesh2dBundle {
+                        mesh: mesh.clone(),
+                        material,
+                        transform: *transform,
+                        ..Default::default()
+                    })
+                    .insert(DissolvingCover(Timer::from_seconds(duration, false)))
+                    .insert(Name::new("Dissolving Cover"));
+            });
+        }
+    }
+    for (entity, mut dissolve, handle) in dissolving.iter_mut() {
+        dissolve.0.tick(time.delta());
+        if dissolve.0.finished() {
+            commands.entity(entity).despawn_recursive();
+        } else if let Some(material) = materials.get_mut(handle) {
+            material.dissolve = dissolve.0.percent();
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Default font and sprites compiled into the binary, used in place of the assets folder ones
+//! when these fail to load, or from the start with the `embedded-assets` feature. The files are
+//! copies of the tutorial assets kept inside the package, so it builds on its own
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+
+/// `fonts/pixeled.ttf`
+pub(crate) const FONT: &[u8] = include_bytes!("../embedded/fonts/pixeled.ttf");
+/// `sprites/flag.png`
+pub(crate) const FLAG: &[u8] = include_bytes!("../embedded/sprites/flag.png");
+/// `sprites/bomb.png`
+pub(crate) const BOMB: &[u8] = include_bytes!("../embedded/sprites/bomb.png");
+
+/// Embedded bomb counter font
+pub(crate) fn font() -> Font {
+    Font::try_from_bytes(FONT.to_vec()).expect("Invalid embedded font")
+}
+
+/// Decodes an embedded PNG sprite
+pub(crate) fn image(png: &[u8]) -> Image {
+    let rgba = image::load_from_memory_with_format(png, image::ImageFormat::Png)
+        .expect("Invalid embedded sprite")
+        .to_rgba8();
+    Image::new(
+        Extent3d { width: rgba.width(), height: rgba.height(), depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        rgba.into_raw(),
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
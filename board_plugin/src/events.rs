@@ -1,4 +1,260 @@
 use crate::components::Coordinates;
+use crate::resources::{
+    BoardOptionsError, LeaderboardKey, MineKind, NightMode, Player, PowerUp, ScoreEntry, Tile,
+    VisibleTile,
+};
+use bevy::prelude::{GamepadButtonType, KeyCode, MouseButton};
+use std::path::PathBuf;
 
+/// Origin of an input event, so analytics, replays and co-op play can tell actions apart
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum InputSource {
+    /// Mouse button press or release
+    Mouse(MouseButton),
+    /// Tap or long press
+    Touch,
+    /// Keyboard cursor key
+    Keyboard(KeyCode),
+    /// Gamepad button, for host apps with gamepad controls
+    Gamepad(GamepadButtonType),
+    /// Programmatic player, like a solver or the headless harness
+    Ai,
+    /// Recorded action played back
+    Replay,
+    /// Co-op peer action, mirrored over the network
+    Network,
+}
+
+/// Input event: a tile should be uncovered
+#[derive(Debug, Copy, Clone)]
+pub struct TileTriggerEvent(pub Coordinates, pub InputSource);
+
+/// Send this event to pause or resume the board
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PauseEvent {
+    Pause,
+    Resume,
+    Toggle,
+}
+
+/// Input event: a tile should be marked or unmarked
+#[derive(Debug, Copy, Clone)]
+pub struct TileMarkEvent(pub Coordinates, pub InputSource);
+
+/// Input event: the covered neighbors of a numbered tile should be uncovered,
+/// if its adjacent flags match its bomb count
+#[derive(Debug, Copy, Clone)]
+pub struct TileChordEvent(pub Coordinates, pub InputSource);
+
+/// Send this event to uncover a covered tile programmatically (AI players, tutorials..).
+/// Unlike input events, it isn't counted as a click, recorded in replays or shared with co-op
+/// peers. Marked tiles are left covered
+#[derive(Debug, Copy, Clone)]
+pub struct UncoverTileEvent(pub Coordinates);
+
+/// Send this event to flag a covered tile programmatically, tiles already flagged being left
+/// as is. Unlike input events, it isn't recorded in replays or shared with co-op peers
+#[derive(Debug, Copy, Clone)]
+pub struct FlagTileEvent(pub Coordinates);
+
+/// Sent by the `BoardOptions::auto_flag` assist to flag a proven mine with a tinted flag
+#[derive(Debug, Copy, Clone)]
+pub struct AutoFlagEvent(pub Coordinates);
+
+/// Send this event to use a power-up charge, see `BoardOptions::power_ups`
+#[derive(Debug, Copy, Clone)]
+pub struct UsePowerUpEvent(pub PowerUp);
+
+/// A power-up charge was granted
+#[derive(Debug, Copy, Clone)]
+pub struct PowerUpGrantedEvent(pub PowerUp);
+
+/// A power-up was used
+#[derive(Debug, Copy, Clone)]
+pub struct PowerUpUsedEvent {
+    pub power_up: PowerUp,
+    /// Target tile, if any
+    pub coords: Option<Coordinates>,
+}
+
+/// A covered tile is uncovered this frame, once the input events are checked
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct UncoverEvent(pub Coordinates);
+
+/// A board was generated and spawned
+#[derive(Debug, Copy, Clone)]
+pub struct BoardGeneratedEvent {
+    pub width: u16,
+    pub height: u16,
+    pub bomb_count: u16,
+    pub seed: u64,
+}
+
+/// No board could be generated from the options, the current board staying in place. See
+/// `BoardOptions::lenient` to clamp them instead
+#[derive(Debug, Clone)]
+pub struct BoardGenerationFailedEvent {
+    pub error: BoardOptionsError,
+}
+
+/// Every tile of a new board was spawned and it accepts input, see `BoardOptions::spawn_budget`
+#[derive(Debug, Copy, Clone)]
+pub struct BoardReadyEvent;
+
+/// A tile was uncovered
+#[derive(Debug, Copy, Clone)]
+pub struct TileUncoveredEvent {
+    pub coords: Coordinates,
+    pub tile: Tile,
+}
+
+/// A flag was placed on a covered tile
+#[derive(Debug, Copy, Clone)]
+pub struct TileFlaggedEvent(pub Coordinates);
+
+/// A flag was rejected from a safe tile, in the `BoardOptions::mistake_proof_flags` mode
+#[derive(Debug, Copy, Clone)]
+pub struct WrongFlagAttemptEvent(pub Coordinates);
+
+/// A flag was removed from a covered tile
+#[derive(Debug, Copy, Clone)]
+pub struct TileUnflaggedEvent(pub Coordinates);
+
+/// Visible tiles changed this frame, for external UIs mirroring the board. A new board sends all
+/// of its tiles
+#[derive(Debug, Clone)]
+pub struct BoardChangedEvent {
+    pub changes: Vec<(Coordinates, VisibleTile)>,
+}
+
+/// All safe tiles were uncovered
+#[derive(Debug, Copy, Clone)]
+pub struct BoardCompletedEvent {
+    /// Completion time in seconds, penalties included
+    pub time: f32,
+    /// Time penalties in seconds, see `BoardOptions::time_penalties`
+    pub penalty: f32,
+    /// 3BV of the completed board
+    pub bbbv: u32,
+    /// Overall difficulty score of the completed board
+    pub difficulty_score: f32,
+    /// Uncover clicks used to complete the board
+    pub clicks: u32,
+    /// Bonus points of the flagged decoys, see `MineKinds::decoy_bonus`
+    pub bonus: u32,
+}
+
+impl BoardCompletedEvent {
+    /// Ratio between the board 3BV and the clicks used, `1.0` being a perfect game
+    pub fn efficiency(&self) -> f32 {
+        if self.clicks == 0 {
+            return 0.;
+        }
+        self.bbbv as f32 / self.clicks as f32
+    }
+}
+
+/// A covered mine moved to another covered tile, see `BoardOptions::moving_mines`
+#[derive(Debug, Copy, Clone)]
+pub struct MineMovedEvent {
+    pub from: Coordinates,
+    pub to: Coordinates,
+}
+
+/// A bomb tile was uncovered
+#[derive(Debug, Copy, Clone)]
+pub struct BombExplodedEvent {
+    pub coords: Coordinates,
+    pub kind: MineKind,
+}
+
+/// A bomb tile was uncovered while lives remained, the game goes on
+#[derive(Debug, Copy, Clone)]
+pub struct LifeLostEvent {
+    pub coords: Coordinates,
+    /// Lives left after this one
+    pub remaining: u8,
+    pub kind: MineKind,
+}
+
+/// The countdown of a timed board expired, the game is lost
+#[derive(Debug, Copy, Clone)]
+pub struct TimeExpiredEvent;
+
+/// An endless mode level was completed, the next one is generated shortly after
+#[derive(Debug, Copy, Clone)]
+pub struct LevelCompletedEvent {
+    pub level: u32,
+}
+
+/// A versus race was won, see `VersusRace`
+#[derive(Debug, Copy, Clone)]
+pub struct RaceWonEvent {
+    pub player: Player,
+}
+
+/// Send this event to replace the board with a new one generated from the current options,
+/// endless runs restarting from their first level
+#[derive(Debug, Copy, Clone)]
+pub struct RestartBoardEvent;
+
+/// Send this event to replay a lost board with the same layout, see `LostBoard`
+#[derive(Debug, Copy, Clone)]
+pub struct RetryBoardEvent {
+    /// Are the flags placed before the fatal click restored
+    pub restore_flags: bool,
+}
+
+/// Send this event to export the board as seen by the player to a PNG file, with the time and
+/// seed written above it
+#[derive(Debug, Clone)]
+pub struct ExportBoardImageEvent {
+    pub path: PathBuf,
+    /// Exports every tile uncovered instead, with the `debug` feature only
+    pub solution: bool,
+}
+
+/// A board asset failed to load and was replaced by its embedded fallback, so the board still
+/// renders without the assets folder
+#[derive(Debug, Clone)]
+pub struct MissingAssetEvent {
+    /// Path of the asset, relative to the assets folder
+    pub path: String,
+}
+
+/// Send this event to switch the night mode of the theme, `None` turning it off. See
+/// `BoardAssets::night_mode`
+#[derive(Debug, Copy, Clone)]
+pub struct NightModeEvent(pub Option<NightMode>);
+
+/// Send this event to move the board camera over a tile, with `BoardOptions::camera_drag`
+/// enabled. Hints, tutorials and replays use it to show a tile out of view
+#[derive(Debug, Copy, Clone)]
+pub struct FocusTileEvent(pub Coordinates);
+
+/// Send this event to move and zoom the board camera out so the whole board shows, with
+/// `BoardOptions::camera_drag` enabled
+#[derive(Debug, Copy, Clone)]
+pub struct FitBoardEvent;
+
+/// Send this event to log the board as seen by the player, see `Board::render_ascii`
+#[derive(Debug, Copy, Clone)]
+pub struct PrintBoardEvent;
+
+/// Send this event to insert a score in the local leaderboards
+#[derive(Debug, Clone)]
+pub struct SubmitScoreEvent {
+    pub key: LeaderboardKey,
+    pub entry: ScoreEntry,
+}
+
+/// A tutorial step was performed
+#[derive(Debug, Copy, Clone)]
+pub struct TutorialStepCompletedEvent {
+    /// Index of the step in the script
+    pub index: usize,
+}
+
+/// The last tutorial step was performed, the board plays normally from now on
 #[derive(Debug, Copy, Clone)]
-pub struct TileTriggerEvent(pub Coordinates);
\ No newline at end of file
+pub struct TutorialCompletedEvent;
@@ -0,0 +1,24 @@
+use bevy::prelude::{RunCriteriaLabel, SystemLabel};
+
+/// Board camera controls, running before the input systems so camera drags cancel clicks
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, SystemLabel)]
+pub struct BoardCamera;
+
+/// Board input systems, turning mouse, keyboard and touch input into board events
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, SystemLabel)]
+pub struct BoardInput;
+
+/// Board uncovering, running after the input events are checked
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, SystemLabel)]
+pub struct BoardUncover;
+
+/// Run criteria of the running state, piped into `board_exists` by the board systems
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, RunCriteriaLabel)]
+pub enum BoardRunning {
+    Update,
+    InStackUpdate,
+}
+
+/// Win and loss checks, running after the tiles are uncovered
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, SystemLabel)]
+pub struct BoardStateCheck;
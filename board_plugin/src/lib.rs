@@ -1,25 +1,102 @@
 mod bounds;
+mod capture;
 mod components;
-mod events;
+#[cfg(feature = "cover_material")]
+mod cover_material;
+mod embedded;
+pub mod events;
+pub mod labels;
+#[cfg(feature = "net")]
+pub mod net;
+#[cfg(feature = "egui")]
+pub mod panel;
 pub mod resources;
 mod systems;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+pub use bounds::Bounds2;
+pub use components::{
+    Adjacency, BoardRoot, BombSprite, Coordinates, CoverSprite, Easing, NumberText, TileRoot,
+    Tween, TweenTarget,
+};
 
 use crate::{
-    bounds::Bounds2,
     components::*,
-    events::TileTriggerEvent,
-    resources::{tile::Tile, tile_map::TileMap, Board, BoardOptions, BoardPosition, TileSize},
+    events::{
+        AutoFlagEvent, BoardChangedEvent, BoardCompletedEvent, BoardGeneratedEvent,
+        BoardGenerationFailedEvent, BoardReadyEvent, BombExplodedEvent, ExportBoardImageEvent,
+        FitBoardEvent, FlagTileEvent, FocusTileEvent, LevelCompletedEvent, LifeLostEvent,
+        MineMovedEvent, MissingAssetEvent, NightModeEvent, PauseEvent, PowerUpGrantedEvent,
+        PowerUpUsedEvent, PrintBoardEvent, RaceWonEvent, RestartBoardEvent, RetryBoardEvent,
+        SubmitScoreEvent, TileChordEvent, TileFlaggedEvent, TileMarkEvent, TileTriggerEvent,
+        TileUncoveredEvent, TileUnflaggedEvent, TimeExpiredEvent, TutorialCompletedEvent,
+        TutorialStepCompletedEvent, UncoverEvent, UncoverTileEvent, UsePowerUpEvent,
+        WrongFlagAttemptEvent,
+    },
+    labels::{BoardCamera, BoardInput, BoardRunning, BoardStateCheck, BoardUncover},
+    resources::{
+        tile::Tile, tile_map::TileMap, Accessibility, Board, BoardAssets, BoardOptions,
+        BoardWindow, DragState, GameStats, GameTimer, HoveredTile, InputBindings, KeyboardCursor,
+        Leaderboards, LevelDefinition, LevelLoader, ReplayRecorder, Settings,
+    },
     systems::{
-        input::input_handling,
-        uncover::{trigger_event_handler, uncover_tiles},
+        accessibility::announce_events,
+        ascii::print_board,
+        assist::{auto_flag_mines, auto_open_satisfied},
+        atlas::build_tile_atlas,
+        board::{
+            advance_endless_level, cleanup_board, create_board, load_board, restart_board,
+            retry_board, spawn_pending_board,
+        },
+        camera::frame_camera,
+        cascade::compute_cascades,
+        celebration::celebrate,
+        chunks::stream_chunks,
+        cursor::{display_keyboard_cursor, keyboard_cursor_handling},
+        end_screen::{end_screen_input, layout_end_screen, spawn_end_screen},
+        explosion::{pop_bombs, reveal_bombs, spawn_explosion, update_particles},
+        export::{export_board_image, finish_exports, ExportTasks},
+        fallback::replace_missing_assets,
+        fog::update_fog,
+        hover::hover_tiles,
+        input::{
+            chord_handling, drag_camera, input_handling, pressed_tile_handling,
+            touch_input_handling,
+        },
+        leaderboard::submit_scores,
+        lives::cross_out_detonated,
+        mark::{animate_flags, drop_win_flags, mark_tiles, restore_flags, shake_covers},
+        mini_map::{navigate_mini_map, update_mini_map},
+        modifiers::update_modifier_overlays,
+        moving_mines::move_mines,
+        night::{night_mode_event_handler, update_night_shades},
+        pause::{end_game, pause_event_handler},
+        power_ups::{display_freeze, grant_power_ups, spawn_power_up_effects, use_power_ups},
+        progress::update_progress_bar,
+        regions::paint_color_regions,
+        remaining::show_remaining_numbers,
+        replay::{advance_ghost, layout_ghost, record_replay},
+        resize::resize_board,
+        run_criteria::board_exists,
+        settings::{apply_settings, save_settings},
+        shake::shake_camera,
+        snapshot::send_board_changes,
+        split_counts::show_split_counts,
+        stats::{count_clicks, record_game_end, start_game_stats},
+        timer::{apply_time_penalties, start_timer, tick_countdown, tick_timer},
+        tooltip::tile_tooltip,
+        tutorial::{advance_tutorial, display_tutorial},
+        tween::{animate_tweens, pop_in_numbers},
+        uncover::{chord_event_handler, trigger_event_handler, uncover_tiles},
+        versus::{rival_input_handling, update_race},
+        view::apply_board_view,
+        window::toggle_fullscreen,
     },
 };
-use bevy::{
-    ecs::schedule::StateData, ecs::system::EntityCommands, math::Vec3Swizzles, prelude::*,
-    utils::HashMap,
-};
+use bevy::{ecs::schedule::StateData, prelude::*};
 #[cfg(feature = "debug")]
-use bevy_inspector_egui::RegisterInspectable;
+use bevy_inspector_egui::{InspectorPlugin, RegisterInspectable};
 
 pub struct BoardPlugin<T> {
     pub running_state: T,
@@ -29,22 +106,230 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
     fn build(&self, app: &mut App) {
         // When the running states comes into the stack we load a board
         app.add_system_set(
-            SystemSet::on_enter(self.running_state.clone()).with_system(Self::create_board),
+            SystemSet::on_enter(self.running_state.clone())
+                .with_system(create_board)
+                .with_system(start_timer)
+                .with_system(start_game_stats),
+        )
+        // The running state criteria are piped into `board_exists`, so the board systems stay
+        // inert while no board exists
+        .add_system_set(SystemSet::new().with_run_criteria(
+            State::on_update(self.running_state.clone()).label(BoardRunning::Update),
+        ))
+        .add_system_set(
+            SystemSet::new().with_run_criteria(
+                State::on_in_stack_update(self.running_state.clone())
+                    .label(BoardRunning::InStackUpdate),
+            ),
         )
-        // We handle input and trigger events only if the state is active
+        // We handle input and trigger events only if the state is active.
+        // Input, trigger handling, uncovering and win or loss checks run in that order, so a
+        // click is resolved within its frame
         .add_system_set(
-            SystemSet::on_update(self.running_state.clone())
-                .with_system(input_handling)
-                .with_system(trigger_event_handler),
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(BoardRunning::Update, board_exists))
+                .with_system(drag_camera.label(BoardCamera).before(BoardInput))
+                .with_system(navigate_mini_map.after(BoardCamera).before(BoardInput))
+                .with_system(input_handling.label(BoardInput))
+                .with_system(pressed_tile_handling.label(BoardInput))
+                .with_system(chord_handling.label(BoardInput))
+                .with_system(keyboard_cursor_handling.label(BoardInput))
+                .with_system(rival_input_handling.label(BoardInput))
+                .with_system(display_keyboard_cursor)
+                .with_system(advance_tutorial.after(BoardUncover))
+                .with_system(display_tutorial)
+                .with_system(touch_input_handling.label(BoardInput))
+                .with_system(use_power_ups.label(BoardInput))
+                .with_system(grant_power_ups)
+                .with_system(hover_tiles)
+                .with_system(tile_tooltip.after(BoardUncover))
+                .with_system(auto_flag_mines.after(BoardUncover))
+                .with_system(auto_open_satisfied.after(BoardUncover))
+                .with_system(mark_tiles.after(BoardInput).before(BoardUncover))
+                .with_system(trigger_event_handler.after(BoardInput).before(BoardUncover))
+                .with_system(chord_event_handler.after(BoardInput).before(BoardUncover))
+                .with_system(tick_timer)
+                .with_system(apply_time_penalties.after(BoardInput).before(BoardUncover))
+                .with_system(tick_countdown.before(BoardStateCheck))
+                .with_system(count_clicks)
+                .with_system(record_replay)
+                .with_system(end_screen_input)
+                .with_system(record_game_end)
+                .with_system(move_mines.after(BoardUncover)),
         )
         // We handle uncovering even if the state is inactive
         .add_system_set(
-            SystemSet::on_in_stack_update(self.running_state.clone()).with_system(uncover_tiles),
+            SystemSet::new()
+                .with_run_criteria(RunCriteria::pipe(BoardRunning::InStackUpdate, board_exists))
+                .with_system(compute_cascades.before(BoardUncover))
+                .with_system(uncover_tiles.label(BoardUncover))
+                .with_system(pause_event_handler)
+                .with_system(end_game.label(BoardStateCheck).after(BoardUncover))
+                .with_system(update_race.label(BoardStateCheck).after(BoardUncover))
+                .with_system(animate_flags)
+                .with_system(drop_win_flags)
+                .with_system(show_remaining_numbers)
+                .with_system(shake_covers)
+                .with_system(cross_out_detonated)
+                .with_system(advance_ghost)
+                .with_system(layout_ghost)
+                .with_system(spawn_explosion)
+                .with_system(reveal_bombs)
+                .with_system(pop_bombs)
+                .with_system(celebrate)
+                .with_system(resize_board)
+                .with_system(apply_board_view)
+                .with_system(frame_camera)
+                .with_system(stream_chunks)
+                .with_system(load_board)
+                .with_system(advance_endless_level)
+                .with_system(announce_events.after(BoardStateCheck))
+                .with_system(spawn_power_up_effects)
+                .with_system(display_freeze)
+                .with_system(update_fog.after(BoardUncover))
+                .with_system(update_night_shades.after(BoardUncover))
+                .with_system(update_modifier_overlays.after(BoardUncover))
+                .with_system(paint_color_regions.after(BoardUncover))
+                .with_system(show_split_counts.after(BoardUncover))
+                .with_system(pop_in_numbers.after(BoardUncover))
+                .with_system(update_mini_map.after(BoardUncover))
+                .with_system(update_progress_bar.after(BoardUncover))
+                .with_system(spawn_end_screen.after(BoardStateCheck))
+                .with_system(send_board_changes.after(BoardStateCheck))
+                .with_system(layout_end_screen)
+                .with_system(restart_board)
+                .with_system(retry_board)
+                .with_system(restore_flags),
         )
+        // Boards generated in the background are spawned whether a board exists or not
         .add_system_set(
-            SystemSet::on_exit(self.running_state.clone()).with_system(Self::cleanup_board),
+            SystemSet::new()
+                .with_run_criteria(BoardRunning::InStackUpdate)
+                .with_system(spawn_pending_board),
         )
-        .add_event::<TileTriggerEvent>();
+        .add_system_set(SystemSet::on_exit(self.running_state.clone()).with_system(cleanup_board))
+        .init_resource::<GameTimer>()
+        .init_resource::<HoveredTile>()
+        .init_resource::<KeyboardCursor>()
+        .init_resource::<DragState>()
+        .init_resource::<ExportTasks>()
+        // Bindings may be customized by the host app beforehand, customized `Settings` ones
+        // replacing them
+        .init_resource::<InputBindings>()
+        .init_resource::<BoardWindow>()
+        // Assets may be customized by the host app beforehand
+        .init_resource::<BoardAssets>()
+        // Statistics may be loaded from disk by the host app beforehand
+        .init_resource::<GameStats>()
+        .init_resource::<Accessibility>()
+        .init_resource::<ReplayRecorder>()
+        // Campaign levels shipped in the assets folder
+        .add_asset::<LevelDefinition>()
+        .init_asset_loader::<LevelLoader>()
+        .add_event::<TileTriggerEvent>()
+        .add_event::<TileMarkEvent>()
+        .add_event::<TileChordEvent>()
+        .add_event::<UncoverEvent>()
+        .add_event::<UncoverTileEvent>()
+        .add_event::<FlagTileEvent>()
+        .add_event::<BoardChangedEvent>()
+        .add_event::<BoardCompletedEvent>()
+        .add_event::<BombExplodedEvent>()
+        .add_event::<BoardGeneratedEvent>()
+        .add_event::<BoardGenerationFailedEvent>()
+        .add_event::<BoardReadyEvent>()
+        .add_event::<TileUncoveredEvent>()
+        .add_event::<TileFlaggedEvent>()
+        .add_event::<TileUnflaggedEvent>()
+        .add_event::<WrongFlagAttemptEvent>()
+        .add_event::<LifeLostEvent>()
+        .add_event::<LevelCompletedEvent>()
+        .add_event::<RaceWonEvent>()
+        .add_event::<PauseEvent>()
+        .add_event::<TutorialStepCompletedEvent>()
+        .add_event::<TutorialCompletedEvent>()
+        .add_event::<UsePowerUpEvent>()
+        .add_event::<PowerUpGrantedEvent>()
+        .add_event::<PowerUpUsedEvent>()
+        .add_event::<RestartBoardEvent>()
+        .add_event::<RetryBoardEvent>()
+        .add_event::<AutoFlagEvent>()
+        // Effects outlive the board
+        .add_system(update_particles)
+        .add_system(animate_tweens)
+        .add_system(shake_camera)
+        // Scores may be submitted at any time, even out of the running state
+        .add_system(submit_scores)
+        // Printing is meant for bug reports, it works in any state
+        .add_system(print_board)
+        .add_system(export_board_image)
+        .add_system(finish_exports)
+        // The atlas is built as soon as the textures are loaded, whatever the state
+        .add_system(build_tile_atlas)
+        // Assets missing from the assets folder are replaced as soon as their loading fails
+        .add_system(replace_missing_assets)
+        .add_system(toggle_fullscreen)
+        // Settings apply to any state, and are saved once every other system ran
+        .add_system(apply_settings)
+        // The theme may be switched to night mode in any state
+        .add_system(night_mode_event_handler)
+        .add_system_to_stage(CoreStage::Last, save_settings)
+        .add_event::<PrintBoardEvent>()
+        .add_event::<ExportBoardImageEvent>()
+        .add_event::<SubmitScoreEvent>()
+        .add_event::<TimeExpiredEvent>()
+        .add_event::<NightModeEvent>()
+        .add_event::<MissingAssetEvent>()
+        .add_event::<MineMovedEvent>()
+        .add_event::<FocusTileEvent>()
+        .add_event::<FitBoardEvent>();
+        // Leaderboards may be loaded by the host app beforehand, from a custom path
+        if !app.world.contains_resource::<Leaderboards>() {
+            let leaderboards = match Leaderboards::default_path().map(Leaderboards::load) {
+                None => Leaderboards::default(),
+                Some(Ok(l)) => l,
+                Some(Err(e)) => {
+                    error!("Failed to load leaderboards: {}", e);
+                    Leaderboards::default()
+                }
+            };
+            app.insert_resource(leaderboards);
+        }
+        // Settings may be loaded by the host app beforehand, from a custom path
+        if !app.world.contains_resource::<Settings>() {
+            let settings = match Settings::default_path().map(Settings::load) {
+                None => Settings::default(),
+                Some(Ok(s)) => s,
+                Some(Err(e)) => {
+                    error!("Failed to load settings: {}", e);
+                    Settings::default()
+                }
+            };
+            app.insert_resource(settings);
+        }
+        #[cfg(target_arch = "wasm32")]
+        app.add_startup_system(systems::web::disable_context_menu);
+        // Co-op actions are mirrored even out of the running state
+        #[cfg(feature = "net")]
+        app.init_resource::<net::NetSession>()
+            .add_event::<net::NetRequestEvent>()
+            .add_event::<net::NetStatusEvent>()
+            .add_system(net::net_requests)
+            .add_system(net::net_receive)
+            .add_system(net::net_send);
+        // The side panel is drawn in any state, once a board exists
+        #[cfg(feature = "egui")]
+        {
+            if !app.world.contains_resource::<bevy_egui::EguiContext>() {
+                app.add_plugin(bevy_egui::EguiPlugin);
+            }
+            app.add_system(panel::board_panel);
+        }
+        // Covers are drawn with the custom material, unless headless
+        #[cfg(feature = "cover_material")]
+        cover_material::build(app);
+        // Exported boards are rendered by the board camera, unless headless
+        capture::build(app);
         info!("Loaded Board Plugin");
 
         // registering custom components to be able to edit it in inspector
@@ -54,315 +339,44 @@ impl<T: StateData> Plugin for BoardPlugin<T> {
             app.register_inspectable::<BombNeighbor>();
             app.register_inspectable::<Bomb>();
             app.register_inspectable::<Uncover>();
+            app.register_inspectable::<Flag>();
+            app.register_inspectable::<Pressed>();
+            app.register_inspectable::<ChordPreview>();
+            app.register_inspectable::<Cursor>();
+            app.register_inspectable::<Detonated>();
+            app.register_inspectable::<TutorialHighlight>();
+            app.register_inspectable::<TutorialMessage>();
+            app.register_inspectable::<NumberBadge>();
+            app.register_inspectable::<FogOverlay>();
+            app.register_inspectable::<NightShade>();
+            app.register_inspectable::<RegionTint>();
+            app.register_inspectable::<TileModifier>();
+            app.register_inspectable::<MiniMapView>();
+            app.register_inspectable::<MiniMapViewport>();
+            app.register_inspectable::<ProgressBarView>();
+            app.register_inspectable::<ProgressBarFill>();
+            app.register_inspectable::<EndScreen>();
+            app.register_inspectable::<Tooltip>();
+            app.register_inspectable::<AutoFlag>();
+            app.register_inspectable::<BoardRoot>();
+            app.register_inspectable::<TileRoot>();
+            app.register_inspectable::<CoverSprite>();
+            app.register_inspectable::<BombSprite>();
+            app.register_inspectable::<NumberText>();
+            // Tuning the options live regenerates the board
+            app.add_plugin(InspectorPlugin::<BoardOptions>::new()).add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(RunCriteria::pipe(BoardRunning::Update, board_exists))
+                    .with_system(systems::hot_reload::hot_reload_options),
+            );
+            app.register_inspectable::<DebugOverlay>();
+            app.register_inspectable::<DebugCounts>();
+            // The overlay stays toggleable once the game is over
+            app.add_system_set(
+                SystemSet::new()
+                    .with_run_criteria(RunCriteria::pipe(BoardRunning::InStackUpdate, board_exists))
+                    .with_system(systems::debug_overlay::debug_overlay),
+            );
         }
     }
 }
-
-impl<T> BoardPlugin<T> {
-    /// System to generate the complete board
-    pub fn create_board(
-        mut commands: Commands,
-        board_options: Option<Res<BoardOptions>>,
-        window: Res<WindowDescriptor>,
-        asset_server: Res<AssetServer>,
-    ) {
-        let font = asset_server.load("fonts/pixeled.ttf");
-        let bomb_image = asset_server.load("sprites/bomb.png");
-        let options = match board_options {
-            None => BoardOptions::default(), // If no options is set we use the default one
-            Some(o) => o.clone(),
-        };
-
-        let tile_map = Self::build_map(&options);
-        let tile_size = Self::build_tile_size(window, &options, &tile_map);
-        let board_size =
-            Vec2::new(tile_map.width() as f32 * tile_size, tile_map.height() as f32 * tile_size);
-        let board_position = Self::build_board_position(&options, board_size);
-        let mut covered_tiles =
-            HashMap::with_capacity((tile_map.width() * tile_map.height()).into());
-        let mut safe_start = None;
-
-        let board_entity = commands
-            .spawn()
-            .insert(Name::new("Board"))
-            .insert(Transform::from_translation(board_position))
-            .insert(GlobalTransform::default())
-            .with_children(|parent| {
-                Self::spawn_background(board_size, parent);
-                Self::spawn_tiles(
-                    parent,
-                    &tile_map,
-                    tile_size,
-                    options.tile_padding,
-                    Color::GRAY,
-                    bomb_image,
-                    font,
-                    Color::DARK_GRAY,
-                    &mut covered_tiles,
-                    &mut safe_start,
-                );
-            })
-            .id();
-        commands.insert_resource(Board {
-            tile_map,
-            tile_size,
-            covered_tiles,
-            bounds: Bounds2 { position: board_position.xy(), size: board_size },
-            entity: board_entity,
-        });
-        if options.safe_start {
-            if let Some(entity) = safe_start {
-                commands.entity(entity).insert(Uncover);
-            }
-        }
-    }
-
-    fn cleanup_board(board: Res<Board>, mut commands: Commands) {
-        commands.entity(board.entity).despawn_recursive();
-        commands.remove_resource::<Board>();
-    }
-
-    fn build_map(options: &BoardOptions) -> TileMap {
-        let mut tile_map = TileMap::empty(options.map_size.0, options.map_size.1);
-        tile_map.set_bombs(options.bomb_count);
-        #[cfg(feature = "debug")]
-        info!("{}", tile_map.console_output());
-        tile_map
-    }
-
-    fn build_tile_size(
-        window: Res<WindowDescriptor>,
-        options: &BoardOptions,
-        tile_map: &TileMap,
-    ) -> f32 {
-        match options.tile_size {
-            TileSize::Fixed(v) => v,
-            TileSize::Adaptive { min, max } => Self::adaptative_tile_size(
-                window,
-                (min, max),
-                (tile_map.width(), tile_map.height()),
-            ),
-        }
-    }
-
-    /// Board anchor position (bottom left)
-    fn build_board_position(options: &BoardOptions, board_size: Vec2) -> Vec3 {
-        match options.position {
-            BoardPosition::Centered { offset } => {
-                Vec3::new(-(board_size.x / 2.), -(board_size.y / 2.), 0.) + offset
-            }
-            BoardPosition::Custom(p) => p,
-        }
-    }
-
-    fn spawn_background(board_size: Vec2, parent: &mut ChildBuilder) {
-        // We spawn the board background sprite at the center of the board,
-        // since the sprite pivot is centered
-        parent
-            .spawn_bundle(SpriteBundle {
-                sprite: Sprite {
-                    color: Color::WHITE,
-                    custom_size: Some(board_size),
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(board_size.x / 2., board_size.y / 2., 0.),
-                ..Default::default()
-            })
-            .insert(Name::new("Background"));
-    }
-
-    // TODO: Refactor this to builder
-    fn spawn_tiles(
-        parent: &mut ChildBuilder,
-        tile_map: &TileMap,
-        size: f32,
-        padding: f32,
-        color: Color,
-        bomb_image: Handle<Image>,
-        font: Handle<Font>,
-        covered_tile_color: Color,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
-    ) {
-        // Tiles
-        for (y, line) in tile_map.iter().enumerate() {
-            for (x, tile) in line.iter().enumerate() {
-                let coordinates = Coordinates { x: x as u16, y: y as u16 };
-                let mut tile_entity = parent.spawn(); // Ex: cmd
-                                                      // Refactor to struct VisualTile
-                Self::insert_tile(
-                    &mut tile_entity,
-                    padding,
-                    size,
-                    y,
-                    x,
-                    coordinates,
-                    color,
-                    covered_tile_color,
-                    covered_tiles,
-                    safe_start_entity,
-                    tile,
-                );
-
-                match tile {
-                    Tile::Bomb => {
-                        Self::insert_bomb(&mut tile_entity, &bomb_image, padding, size);
-                    }
-                    Tile::BombNeighbor(count) => {
-                        Self::insert_bomb_neighbor(&mut tile_entity, &font, *count, size, padding);
-                    }
-                    Tile::Empty => (),
-                }
-            }
-        }
-    }
-
-    //noinspection RsTypeCheck
-    fn insert_bomb(
-        tile_entity: &mut EntityCommands,
-        bomb_image: &Handle<Image>,
-        padding: f32,
-        size: f32,
-    ) {
-        // If the tile is a bomb we add the matching component and a sprite child
-        tile_entity.insert(Bomb);
-        tile_entity.with_children(|parent| {
-            parent.spawn_bundle(SpriteBundle {
-                sprite: Sprite {
-                    custom_size: Some(Vec2::splat(size - padding)),
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(0., 0., 1.),
-                texture: bomb_image.clone(),
-                ..Default::default()
-            });
-        });
-    }
-
-    //noinspection RsTypeCheck
-    fn insert_bomb_neighbor(
-        tile_entity: &mut EntityCommands,
-        font: &Handle<Font>,
-        count: u8,
-        size: f32,
-        padding: f32,
-    ) {
-        // If the tile is a bomb neighbour we add the matching component and a text child
-        tile_entity.insert(BombNeighbor { count });
-        tile_entity.with_children(|parent| {
-            parent.spawn_bundle(Self::bomb_count_text_bundle(count, font.clone(), size - padding));
-        });
-    }
-
-    fn insert_tile(
-        tile_entity: &mut EntityCommands,
-        padding: f32,
-        size: f32,
-        y: usize,
-        x: usize,
-        coordinates: Coordinates,
-        color: Color,
-        covered_tile_color: Color,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        safe_start_entity: &mut Option<Entity>,
-        tile: &Tile,
-    ) {
-        tile_entity
-            .insert_bundle(SpriteBundle {
-                sprite: Sprite {
-                    color,
-                    custom_size: Some(Vec2::splat(size - padding)),
-                    ..Default::default()
-                },
-                transform: Transform::from_xyz(
-                    (x as f32 * size) + (size / 2.),
-                    (y as f32 * size) + (size / 2.),
-                    1.,
-                ),
-                ..Default::default()
-            })
-            .insert(Name::new(format!("Tile ({}, {})", x, y)))
-            .insert(coordinates);
-        Self::insert_cover(
-            tile_entity,
-            covered_tiles,
-            covered_tile_color,
-            padding,
-            size,
-            coordinates,
-            safe_start_entity,
-            tile,
-        );
-    }
-
-    fn insert_cover(
-        tile_entity: &mut EntityCommands,
-        covered_tiles: &mut HashMap<Coordinates, Entity>,
-        covered_tile_color: Color,
-        padding: f32,
-        size: f32,
-        coordinates: Coordinates,
-        safe_start_entity: &mut Option<Entity>,
-        tile: &Tile,
-    ) {
-        tile_entity.with_children(|parent| {
-            let entity = parent
-                .spawn_bundle(SpriteBundle {
-                    sprite: Sprite {
-                        custom_size: Some(Vec2::splat(size - padding)),
-                        color: covered_tile_color,
-                        ..Default::default()
-                    },
-                    transform: Transform::from_xyz(0., 0., 2.),
-                    ..Default::default()
-                })
-                .insert(Name::new("Tile Cover"))
-                .id();
-            covered_tiles.insert(coordinates, entity);
-            if safe_start_entity.is_none() && *tile == Tile::Empty {
-                *safe_start_entity = Some(entity);
-            }
-        });
-    }
-
-    /// Generates the bomb counter text 2D Bundle for a given value
-    fn bomb_count_text_bundle(count: u8, font: Handle<Font>, size: f32) -> Text2dBundle {
-        // We retrieve the text and the correct color
-        let (text, color) = (
-            count.to_string(),
-            match count {
-                1 => Color::WHITE,
-                2 => Color::GREEN,
-                3 => Color::YELLOW,
-                4 => Color::ORANGE,
-                _ => Color::PURPLE,
-            },
-        );
-        // We generate a text bundle
-        Text2dBundle {
-            text: Text {
-                sections: vec![TextSection {
-                    value: text,
-                    style: TextStyle { color, font, font_size: size },
-                }],
-                alignment: TextAlignment {
-                    vertical: VerticalAlign::Center,
-                    horizontal: HorizontalAlign::Center,
-                },
-            },
-            transform: Transform::from_xyz(0., 0., 1.),
-            ..Default::default()
-        }
-    }
-
-    /// Computes a tile size that matches the window according to the tile map size
-    fn adaptative_tile_size(
-        window: Res<WindowDescriptor>,
-        (min, max): (f32, f32),      // Tile size constraints
-        (width, height): (u16, u16), // Tile map dimensions
-    ) -> f32 {
-        let max_width = window.width / width as f32;
-        let max_heigth = window.height / height as f32;
-        max_width.min(max_heigth).clamp(min, max)
-    }
-}
@@ -0,0 +1,389 @@
+//! Co-op multiplayer: two clients play the same seeded board, their uncover and mark actions
+//! being mirrored over a TCP connection. Browsers can't open raw sockets, so the module is
+//! native only
+pub use protocol::NetMessage;
+
+use crate::events::{
+    BoardGeneratedEvent, InputSource, TileChordEvent, TileMarkEvent, TileTriggerEvent,
+};
+use crate::resources::BoardOptions;
+use crate::Board;
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task};
+use futures_lite::future;
+use std::io::{self, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::time::Duration;
+
+mod protocol;
+
+/// Time waited for a host to accept the connection
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Send this event to start or stop a co-op session
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NetRequestEvent {
+    /// Waits for a peer on the given address
+    Host(SocketAddr),
+    /// Connects to a hosting peer
+    Join(SocketAddr),
+    Disconnect,
+}
+
+/// Co-op session status changes
+#[derive(Debug, Clone, PartialEq)]
+pub enum NetStatusEvent {
+    /// Waiting for a peer
+    Hosting(SocketAddr),
+    /// Connected to the given peer
+    Connected(SocketAddr),
+    Disconnected,
+    Error(String),
+    /// The host board parameters were applied to the `BoardOptions` resource. The host app
+    /// should restart its board state to play the same board
+    BoardReceived {
+        seed: u64,
+    },
+}
+
+/// Co-op session role
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum NetRole {
+    Host,
+    Client,
+}
+
+#[derive(Debug)]
+enum NetState {
+    Idle,
+    Listening(TcpListener),
+    /// Connecting to the host on the IO task pool, not to block the frames
+    Connecting(Task<io::Result<TcpStream>>, SocketAddr),
+    /// Messages are read into `buffer` until complete, and written from `outgoing` as the
+    /// non-blocking stream accepts them
+    Connected {
+        stream: TcpStream,
+        buffer: Vec<u8>,
+        outgoing: Vec<u8>,
+    },
+}
+
+/// Co-op session. Must be used as a resource
+#[derive(Debug)]
+pub struct NetSession {
+    state: NetState,
+    role: Option<NetRole>,
+}
+
+impl Default for NetSession {
+    fn default() -> Self {
+        Self { state: NetState::Idle, role: None }
+    }
+}
+
+impl NetSession {
+    /// Is a peer connected
+    pub fn is_connected(&self) -> bool {
+        matches!(self.state, NetState::Connected { .. })
+    }
+
+    // Getter for `role`
+    pub fn role(&self) -> Option<NetRole> {
+        self.role
+    }
+
+    /// Queues the message and writes what the stream accepts
+    fn send(&mut self, message: &NetMessage) -> io::Result<()> {
+        let outgoing = match &mut self.state {
+            NetState::Connected { outgoing, .. } => outgoing,
+            _ => return Ok(()),
+        };
+        let line = message.encode().map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        outgoing.extend_from_slice(&line);
+        self.flush()
+    }
+
+    /// Writes the queued bytes until the stream would block, the rest waiting for the next
+    /// frames
+    fn flush(&mut self) -> io::Result<()> {
+        let (stream, outgoing) = match &mut self.state {
+            NetState::Connected { stream, outgoing, .. } => (stream, outgoing),
+            _ => return Ok(()),
+        };
+        while !outgoing.is_empty() {
+            match stream.write(outgoing) {
+                Ok(0) => return Err(io::ErrorKind::WriteZero.into()),
+                Ok(n) => {
+                    outgoing.drain(..n);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => (),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the available complete messages
+    fn receive(&mut self) -> io::Result<Vec<NetMessage>> {
+        let (stream, buffer) = match &mut self.state {
+            NetState::Connected { stream, buffer, .. } => (stream, buffer),
+            _ => return Ok(Vec::new()),
+        };
+        let mut chunk = [0; 1024];
+        loop {
+            match stream.read(&mut chunk) {
+                Ok(0) => return Err(io::ErrorKind::ConnectionAborted.into()),
+                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break,
+                Err(e) => return Err(e),
+            }
+        }
+        let mut messages = Vec::new();
+        while let Some(end) = buffer.iter().position(|b| *b == b'\n') {
+            let line: Vec<u8> = buffer.drain(..=end).collect();
+            match NetMessage::decode(&String::from_utf8_lossy(&line)) {
+                Ok(message) => messages.push(message),
+                Err(e) => warn!("Ignoring invalid co-op message: {}", e),
+            }
+        }
+        Ok(messages)
+    }
+
+    fn connect(&mut self, stream: TcpStream, role: NetRole) -> io::Result<()> {
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        self.state = NetState::Connected { stream, buffer: Vec::new(), outgoing: Vec::new() };
+        self.role = Some(role);
+        Ok(())
+    }
+
+    fn disconnect(&mut self) {
+        self.state = NetState::Idle;
+        self.role = None;
+    }
+}
+
+/// Hosts, joins or leaves co-op sessions
+pub fn net_requests(
+    mut commands: Commands,
+    mut session: ResMut<NetSession>,
+    board: Option<Res<Board>>,
+    board_options: Option<ResMut<BoardOptions>>,
+    io_pool: Res<IoTaskPool>,
+    mut net_request_evr: EventReader<NetRequestEvent>,
+    mut net_status_ewr: EventWriter<NetStatusEvent>,
+) {
+    for request in net_request_evr.iter() {
+        session.disconnect();
+        let result = match *request {
+            NetRequestEvent::Host(addr) => TcpListener::bind(addr).and_then(|listener| {
+                listener.set_nonblocking(true)?;
+                let addr = listener.local_addr()?;
+                info!("Hosting co-op session on {}", addr);
+                session.state = NetState::Listening(listener);
+                net_status_ewr.send(NetStatusEvent::Hosting(addr));
+                Ok(())
+            }),
+            NetRequestEvent::Join(addr) => {
+                let task = io_pool
+                    .spawn(async move { TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) });
+                session.state = NetState::Connecting(task, addr);
+                Ok(())
+            }
+            NetRequestEvent::Disconnect => {
+                net_status_ewr.send(NetStatusEvent::Disconnected);
+                Ok(())
+            }
+        };
+        if let Err(e) = result {
+            error!("Co-op request {:?} failed: {}", request, e);
+            net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+        }
+    }
+    // The client waits for the host to answer
+    if let NetState::Connecting(task, addr) = &mut session.state {
+        let addr = *addr;
+        let connected = match future::block_on(future::poll_once(task)) {
+            None => return,
+            Some(result) => result.and_then(|stream| session.connect(stream, NetRole::Client)),
+        };
+        match connected {
+            Ok(()) => {
+                info!("Joined co-op session on {}", addr);
+                net_status_ewr.send(NetStatusEvent::Connected(addr));
+            }
+            Err(e) => {
+                error!("Failed to join co-op session on {}: {}", addr, e);
+                session.disconnect();
+                net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+            }
+        }
+        return;
+    }
+    // The host waits for its peer, and sends it the current board
+    let accepted = match &session.state {
+        NetState::Listening(listener) => listener.accept(),
+        _ => return,
+    };
+    match accepted {
+        Ok((stream, addr)) => {
+            if let Err(e) = session.connect(stream, NetRole::Host) {
+                session.disconnect();
+                net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+                return;
+            }
+            info!("Co-op peer {} connected", addr);
+            net_status_ewr.send(NetStatusEvent::Connected(addr));
+            // Before the first board, the host picks the seed it will be generated with
+            let (options, seed) = match (board, board_options) {
+                (Some(board), options) => {
+                    (options.map(|o| o.clone()).unwrap_or_default(), board.tile_map.seed())
+                }
+                (None, Some(mut options)) => {
+                    let seed = *options.seed.get_or_insert_with(rand::random);
+                    (options.clone(), seed)
+                }
+                (None, None) => {
+                    let options = BoardOptions { seed: Some(rand::random()), ..Default::default() };
+                    commands.insert_resource(options.clone());
+                    let seed = options.seed.unwrap_or_default();
+                    (options, seed)
+                }
+            };
+            send_board(&mut session, &options, seed, &mut net_status_ewr);
+        }
+        Err(e) if e.kind() == io::ErrorKind::WouldBlock => (),
+        Err(e) => {
+            session.disconnect();
+            net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+        }
+    }
+}
+
+/// Sends the host board to the peer as the code of its `options` with the `seed` it was
+/// generated with. Boards without code can't be played together, which is reported as an error
+fn send_board(
+    session: &mut NetSession,
+    options: &BoardOptions,
+    seed: u64,
+    net_status_ewr: &mut EventWriter<NetStatusEvent>,
+) {
+    let options = BoardOptions { seed: Some(seed), ..options.clone() };
+    let code = match options.to_code() {
+        Ok(code) => code,
+        Err(e) => {
+            error!("The co-op board can't be sent: {}", e);
+            net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+            return;
+        }
+    };
+    if let Err(e) = session.send(&NetMessage::Board { code }) {
+        error!("Failed to send the co-op board: {}", e);
+        session.disconnect();
+        net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+    }
+}
+
+/// Turns the peer actions into local events
+pub fn net_receive(
+    mut commands: Commands,
+    mut session: ResMut<NetSession>,
+    board_options: Option<Res<BoardOptions>>,
+    mut net_status_ewr: EventWriter<NetStatusEvent>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    let messages = match session.receive() {
+        Ok(m) => m,
+        Err(e) => {
+            info!("Co-op peer disconnected: {}", e);
+            session.disconnect();
+            net_status_ewr.send(NetStatusEvent::Disconnected);
+            return;
+        }
+    };
+    for message in messages {
+        match message {
+            NetMessage::Board { code } => {
+                let shared = match BoardOptions::from_code(&code) {
+                    Ok(o) => o,
+                    Err(e) => {
+                        warn!("Ignoring invalid co-op board: {}", e);
+                        net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+                        continue;
+                    }
+                };
+                // The local display options are kept
+                let mut options = board_options.as_deref().cloned().unwrap_or_default();
+                let seed = shared.seed.unwrap_or_default();
+                options.seed = shared.seed;
+                options.map_size = shared.map_size;
+                options.layout = shared.layout;
+                options.mask = None;
+                options.safe_start = shared.safe_start;
+                options.lives = shared.lives;
+                options.variant = shared.variant;
+                options.symmetry = shared.symmetry;
+                options.density = shared.density;
+                options.min_opening = shared.min_opening;
+                options.mine_kinds = shared.mine_kinds;
+                commands.insert_resource(options);
+                net_status_ewr.send(NetStatusEvent::BoardReceived { seed });
+            }
+            NetMessage::Uncover(coords) => {
+                tile_trigger_ewr.send(TileTriggerEvent(coords, InputSource::Network))
+            }
+            NetMessage::Mark(coords) => {
+                tile_mark_ewr.send(TileMarkEvent(coords, InputSource::Network))
+            }
+            NetMessage::Chord(coords) => {
+                tile_chord_ewr.send(TileChordEvent(coords, InputSource::Network))
+            }
+        }
+    }
+}
+
+/// Sends the local actions and boards to the peer
+pub fn net_send(
+    mut session: ResMut<NetSession>,
+    board_options: Option<Res<BoardOptions>>,
+    mut board_generated_evr: EventReader<BoardGeneratedEvent>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut net_status_ewr: EventWriter<NetStatusEvent>,
+) {
+    let generated = board_generated_evr.iter().last().map(|e| e.seed);
+    let hosting = session.is_connected() && session.role() == Some(NetRole::Host);
+    if let (Some(seed), true) = (generated, hosting) {
+        let options = board_options.map(|o| o.clone()).unwrap_or_default();
+        send_board(&mut session, &options, seed, &mut net_status_ewr);
+    }
+    let mut messages = Vec::new();
+    // Peer actions are not sent back
+    let local = |source: &InputSource| *source != InputSource::Network;
+    messages
+        .extend(tile_trigger_evr.iter().filter(|e| local(&e.1)).map(|e| NetMessage::Uncover(e.0)));
+    messages.extend(tile_mark_evr.iter().filter(|e| local(&e.1)).map(|e| NetMessage::Mark(e.0)));
+    messages.extend(tile_chord_evr.iter().filter(|e| local(&e.1)).map(|e| NetMessage::Chord(e.0)));
+    if !session.is_connected() {
+        return;
+    }
+    // Bytes the stream didn't accept on the previous frames go first
+    if let Err(e) = session.flush() {
+        error!("Failed to send co-op messages: {}", e);
+        session.disconnect();
+        net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+        return;
+    }
+    for message in messages {
+        if let Err(e) = session.send(&message) {
+            error!("Failed to send co-op message: {}", e);
+            session.disconnect();
+            net_status_ewr.send(NetStatusEvent::Error(e.to_string()));
+            return;
+        }
+    }
+}
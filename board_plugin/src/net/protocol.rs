@@ -0,0 +1,61 @@
+use crate::components::Coordinates;
+use serde::{Deserialize, Serialize};
+
+/// Message exchanged between co-op peers, sent as a single RON line
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum NetMessage {
+    /// The host board options as a board code, see `BoardOptions::to_code`. Sent on connection
+    /// and on every new board
+    Board { code: String },
+    /// A tile was uncovered
+    Uncover(Coordinates),
+    /// A tile was marked or unmarked
+    Mark(Coordinates),
+    /// A numbered tile was chorded
+    Chord(Coordinates),
+}
+
+impl NetMessage {
+    /// Serializes the message as a newline terminated line
+    pub fn encode(&self) -> Result<Vec<u8>, ron::Error> {
+        let mut line = ron::to_string(self)?.into_bytes();
+        line.push(b'\n');
+        Ok(line)
+    }
+
+    /// Deserializes a message from a single line
+    pub fn decode(line: &str) -> Result<Self, ron::Error> {
+        ron::from_str(line.trim())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn messages_round_trip() {
+        let coords = Coordinates { x: 3, y: 7 };
+        let messages = [
+            NetMessage::Board { code: "1A2B-3C4D".to_string() },
+            NetMessage::Uncover(coords),
+            NetMessage::Mark(coords),
+            NetMessage::Chord(coords),
+        ];
+        for message in messages {
+            let line = message.encode().unwrap();
+            assert_eq!(line.last(), Some(&b'\n'));
+            // A message is a single line
+            assert_eq!(line.iter().filter(|b| **b == b'\n').count(), 1);
+            let decoded = NetMessage::decode(&String::from_utf8(line).unwrap()).unwrap();
+            assert_eq!(decoded, message);
+        }
+    }
+
+    #[test]
+    fn invalid_lines_are_rejected() {
+        assert!(NetMessage::decode("Explode((x: 1, y: 2))").is_err());
+        assert!(NetMessage::decode("Uncover((x: 1))").is_err());
+        assert!(NetMessage::decode("").is_err());
+    }
+}
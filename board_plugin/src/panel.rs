@@ -0,0 +1,98 @@
+//! Ready-made egui side panel showing the game timer, the mine counter and solver hints, with a
+//! difficulty selector restarting the board
+use crate::events::RestartBoardEvent;
+use crate::resources::{BoardLayout, BoardOptions, BoardState, Countdown, Difficulty, GameTimer};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use bevy_egui::{egui, EguiContext};
+
+/// Panel width in logical pixels
+const PANEL_WIDTH: f32 = 180.;
+/// Maximum number of listed hints
+const MAX_HINTS: usize = 5;
+
+/// Difficulties offered by the selector
+const DIFFICULTIES: [Difficulty; 3] =
+    [Difficulty::Beginner, Difficulty::Intermediate, Difficulty::Expert];
+
+/// Draws the side panel, while a board exists
+pub fn board_panel(
+    mut egui_context: ResMut<EguiContext>,
+    mut commands: Commands,
+    board: Option<Res<Board>>,
+    board_state: Option<Res<BoardState>>,
+    mut board_options: Option<ResMut<BoardOptions>>,
+    timer: Res<GameTimer>,
+    countdown: Option<Res<Countdown>>,
+    mut selected: Local<Option<Difficulty>>,
+    mut hints: Local<Vec<Coordinates>>,
+    mut restart_board_ewr: EventWriter<RestartBoardEvent>,
+) {
+    let (board, board_state) = match (board, board_state) {
+        (Some(b), Some(s)) => (b, s),
+        _ => return,
+    };
+    let difficulty = selected.get_or_insert_with(|| match &board_options {
+        None => BoardOptions::default().difficulty(),
+        Some(o) => o.difficulty(),
+    });
+    // Solving the whole board is only worth it when it changed
+    if board.is_changed() {
+        *hints = proven_mines(&board);
+    }
+    egui::SidePanel::left("board_panel").exact_width(PANEL_WIDTH).show(
+        egui_context.ctx_mut(),
+        |ui| {
+            ui.heading(format!("{:?}", *board_state));
+            ui.label(format!("Time: {:.1}s", timer.elapsed()));
+            if let Some(countdown) = countdown {
+                ui.label(format!("Remaining: {:.1}s", countdown.remaining()));
+            }
+            ui.label(format!("Mines: {}", board.remaining_mines()));
+            ui.separator();
+            egui::ComboBox::from_label("Difficulty")
+                .selected_text(format!("{:?}", difficulty))
+                .show_ui(ui, |ui| {
+                    for d in DIFFICULTIES {
+                        ui.selectable_value(difficulty, d, format!("{:?}", d));
+                    }
+                });
+            if ui.button("Restart").clicked() {
+                let (map_size, layout) =
+                    (difficulty.map_size(), BoardLayout::Random { count: difficulty.bomb_count() });
+                // Options are changed in place, as the restart may run later in this frame
+                match board_options.as_mut() {
+                    Some(options) => {
+                        options.map_size = map_size;
+                        options.layout = layout;
+                    }
+                    None => commands.insert_resource(BoardOptions {
+                        map_size,
+                        layout,
+                        ..Default::default()
+                    }),
+                }
+                restart_board_ewr.send(RestartBoardEvent);
+            }
+            ui.separator();
+            ui.label("Hints");
+            if hints.is_empty() {
+                ui.label("No proven mine");
+            }
+            for coords in hints.iter().take(MAX_HINTS) {
+                ui.label(format!("Mine at {}", coords));
+            }
+        },
+    );
+}
+
+/// Unflagged mines proven by the uncovered numbers, sorted by coordinates
+fn proven_mines(board: &Board) -> Vec<Coordinates> {
+    let numbers = (0..board.tile_map.height())
+        .flat_map(|y| (0..board.tile_map.width()).map(move |x| Coordinates { x, y }));
+    let mines: HashSet<_> = numbers.flat_map(|c| board.proven_mines(c)).collect();
+    let mut mines: Vec<_> = mines.into_iter().collect();
+    mines.sort();
+    mines
+}
@@ -0,0 +1,86 @@
+use crate::components::Coordinates;
+use crate::resources::Tile;
+use bevy::log::info;
+use std::fmt::{self, Display, Formatter};
+
+/// Game state announcement, meant to be vocalized by assistive technologies
+#[derive(Debug, Clone, PartialEq)]
+pub enum Announcement {
+    /// A single tile was uncovered
+    TileUncovered { coords: Coordinates, tile: Tile },
+    /// Several tiles were uncovered at once, by an opening or a chord
+    TilesUncovered { count: usize },
+    /// A flag was placed or removed
+    FlagChanged { coords: Coordinates, flagged: bool, remaining_mines: i32 },
+    /// A bomb was uncovered while lives remained
+    LifeLost { coords: Coordinates, remaining: u8 },
+    /// A bomb was uncovered, the game is lost
+    BombExploded { coords: Coordinates },
+    /// The countdown expired, the game is lost
+    TimeExpired,
+    /// All safe tiles were uncovered
+    BoardCompleted { time: f32 },
+}
+
+impl Display for Announcement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::TileUncovered { coords, tile } => match tile {
+                Tile::BombNeighbor(count) => write!(f, "{} uncovered, {} around", coords, count),
+                _ => write!(f, "{} uncovered, empty", coords),
+            },
+            Self::TilesUncovered { count } => write!(f, "{} tiles uncovered", count),
+            Self::FlagChanged { coords, flagged, remaining_mines } => write!(
+                f,
+                "{} {}, {} mines remaining",
+                coords,
+                if *flagged { "flagged" } else { "unflagged" },
+                remaining_mines
+            ),
+            Self::LifeLost { coords, remaining } => {
+                write!(f, "Bomb on {}, {} lives remaining", coords, remaining)
+            }
+            Self::BombExploded { coords } => write!(f, "Bomb on {}, game over", coords),
+            Self::TimeExpired => write!(f, "Time is up, game over"),
+            Self::BoardCompleted { time } => write!(f, "Board cleared in {:.1} seconds", time),
+        }
+    }
+}
+
+/// Receives the accessibility announcements, to forward them to a screen reader for example
+pub trait AccessibilityBackend: Send + Sync + 'static {
+    fn announce(&mut self, announcement: &Announcement);
+}
+
+/// Default backend, logging the announcements
+#[derive(Debug, Copy, Clone, Default)]
+pub struct LogBackend;
+
+impl AccessibilityBackend for LogBackend {
+    fn announce(&mut self, announcement: &Announcement) {
+        info!("Announcement: {}", announcement);
+    }
+}
+
+/// Accessibility announcements output. Must be used as a resource, insert it with a custom
+/// backend before adding the plugin to replace the log output
+pub struct Accessibility {
+    backend: Box<dyn AccessibilityBackend>,
+}
+
+impl Accessibility {
+    pub fn new(backend: impl AccessibilityBackend) -> Self {
+        Self { backend: Box::new(backend) }
+    }
+
+    /// Sends an announcement to the backend
+    pub fn announce(&mut self, announcement: Announcement) {
+        self.backend.announce(&announcement);
+    }
+}
+
+impl Default for Accessibility {
+    fn default() -> Self {
+        Self::new(LogBackend)
+    }
+}
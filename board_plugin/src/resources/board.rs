@@ -1,52 +1,402 @@
-use crate::bounds::Bounds2;
-use crate::{Coordinates, TileMap};
+use crate::resources::{
+    BoardModifiers, BoardSnapshot, BoardVariant, Cascade, ColorRegions, MineKind, TileChunks,
+    VisibleTile,
+};
+use crate::{Coordinates, Tile, TileMap};
 use bevy::prelude::*;
 use bevy::utils::HashMap;
+use std::collections::VecDeque;
+use std::ops::Range;
+use std::sync::Arc;
 
+/// Board state, the geometry being split into `BoardGeometry`. The covered and marked tiles stay
+/// here rather than in their own resources: uncovering a tile also clears its marks, cascades
+/// and chords read both, so every uncover or mark still changes the board
 #[derive(Debug)]
 pub struct Board {
-    pub tile_map: TileMap,
-    pub bounds: Bounds2,
-    pub tile_size: f32,
+    /// Shared with the async cascade tasks
+    pub tile_map: Arc<TileMap>,
     pub covered_tiles: HashMap<Coordinates, Entity>,
+    /// Number of flags on each marked tile
+    pub marked_tiles: HashMap<Coordinates, u8>,
+    /// Game rules variant
+    pub variant: BoardVariant,
+    /// Bombs uncovered while lives remained
+    pub detonated_tiles: Vec<Coordinates>,
     pub entity: Entity,
+    /// Board background sprite
+    pub background: Entity,
+    /// Board cover displayed while paused
+    pub pause_cover: Option<Entity>,
+    /// Spawned chunks and covered tiles of the unspawned ones, if the board is chunked
+    pub chunks: Option<TileChunks>,
+    /// Sight radius around uncovered tiles in fog-of-war mode, see `BoardOptions::fog_radius`
+    pub fog_radius: Option<u16>,
+    /// Uncover cascade spread, see `BoardOptions::cascade`
+    pub cascade: Cascade,
+    /// Are openings computed on the async compute pool, see `BoardOptions::async_cascade`
+    pub async_cascade: bool,
+    /// Openings computed on the async compute pool, by origin, waiting to be uncovered
+    pub openings: Vec<(Coordinates, Vec<Coordinates>)>,
+    /// Ice, locked, key and teleport tiles, see `BoardOptions::tile_modifiers`
+    pub modifiers: BoardModifiers,
+    /// Special mines by coordinates, see `BoardOptions::mine_kinds`
+    pub mine_kinds: HashMap<Coordinates, MineKind>,
+    /// Tile regions of the `ColorRegions` variant
+    pub regions: Option<ColorRegions>,
 }
 
 impl Board {
-    /// Translates a mouse position to board coordinates
-    pub fn mouse_position(&self, window: &Window, position: Vec2) -> Option<Coordinates> {
-        // Window to world space
-        let window_size = Vec2::new(window.width(), window.height());
-        let position = position - window_size / 2.;
-
-        // Bounds check
-        if !self.bounds.in_bounds(position) {
-            return None;
-        }
-        // World space to board space
-        let coordinates = position - self.bounds.position;
-        Some(Coordinates {
-            x: (coordinates.x / self.tile_size) as u16,
-            y: (coordinates.y / self.tile_size) as u16,
-        })
-    }
-
-    /// Retrieves a covered tile entity
+    /// Retrieves a covered tile entity, marked and fogged tiles can't be uncovered
     pub fn tile_to_uncover(&self, coords: &Coordinates) -> Option<&Entity> {
-        self.covered_tiles.get(coords)
+        if self.marked_tiles.contains_key(coords) || !self.in_sight(coords) {
+            None
+        } else {
+            self.covered_tiles.get(coords)
+        }
     }
 
     /// We try to uncover a tile, returning the entity
     pub fn try_uncover_tile(&mut self, coords: &Coordinates) -> Option<Entity> {
+        self.marked_tiles.remove(coords);
         self.covered_tiles.remove(coords)
     }
 
-    /// We retrieve the adjacent covered tile entities of `coord`
-    pub fn adjacent_covered_tiles(&self, coord: Coordinates) -> Vec<Entity> {
+    /// Uncovers the tile at `coords` and, if it is empty, its whole opening in a single breadth
+    /// first pass, numbered border included unless the cascade is `EmptyOnly`. Returns the
+    /// uncovered coordinates with their cover entity, the tiles of unspawned chunks having none
+    pub fn uncover_from(&mut self, coords: Coordinates) -> Vec<(Coordinates, Option<Entity>)> {
+        let mut uncovered = Vec::new();
+        let mut depth = 0;
+        let mut queue = VecDeque::from([(coords, 0)]);
+        while let Some((coords, d)) = queue.pop_front() {
+            let cover = match self.try_uncover_tile(&coords) {
+                Some(entity) => Some(entity),
+                None if self.try_uncover_unspawned(&coords) => None,
+                // Already uncovered, or queued twice
+                None => continue,
+            };
+            uncovered.push((coords, cover));
+            depth = d;
+            if self.tile_map[coords.y as usize][coords.x as usize] == Tile::Empty {
+                let neighbors = self.tile_map.neighbors(coords).filter(|c| self.cascades_to(c));
+                queue.extend(neighbors.map(|c| (c, d + 1)));
+            }
+        }
+        trace!(tiles = uncovered.len(), cascade_depth = depth, "Uncovered opening from {}", coords);
+        uncovered
+    }
+
+    /// Uncovers the still covered tiles of an opening computed ahead, returning them with their
+    /// cover entity like `uncover_from`. Ice and locked tiles are left covered
+    pub fn uncover_opening(&mut self, tiles: &[Coordinates]) -> Vec<(Coordinates, Option<Entity>)> {
+        tiles
+            .iter()
+            .filter_map(|coords| {
+                if self.modifiers.stops_cascade(coords) {
+                    return None;
+                }
+                match self.try_uncover_tile(coords) {
+                    Some(entity) => Some((*coords, Some(entity))),
+                    None if self.try_uncover_unspawned(coords) => Some((*coords, None)),
+                    None => None,
+                }
+            })
+            .collect()
+    }
+
+    /// Does the cascade from an empty neighbor uncover the tile at `coords`. Ice and locked
+    /// tiles need their own uncover attempt
+    pub fn cascades_to(&self, coords: &Coordinates) -> bool {
+        self.is_covered(coords)
+            && !self.modifiers.stops_cascade(coords)
+            && (self.cascade == Cascade::Classic
+                || self.tile_map[coords.y as usize][coords.x as usize] == Tile::Empty)
+    }
+
+    /// Uncovers a tile of an unspawned chunk, returning whether it was covered
+    pub fn try_uncover_unspawned(&mut self, coords: &Coordinates) -> bool {
+        let uncovered = self.chunks.as_mut().map_or(false, |c| c.covered.remove(coords));
+        if uncovered {
+            self.marked_tiles.remove(coords);
+        }
+        uncovered
+    }
+
+    /// Is the tile at `coords` covered, its chunk being spawned or not
+    pub fn is_covered(&self, coords: &Coordinates) -> bool {
+        self.covered_tiles.contains_key(coords)
+            || self.chunks.as_ref().map_or(false, |c| c.covered.contains(coords))
+    }
+
+    /// Is the tile at `coords` within the fog-of-war sight radius of an uncovered tile. Every
+    /// tile is in sight without fog, or until a first tile is uncovered
+    pub fn in_sight(&self, coords: &Coordinates) -> bool {
+        let radius = match self.fog_radius {
+            None => return true,
+            Some(r) => r,
+        };
+        if self.uncovered_tiles() == 0 && self.detonated_tiles.is_empty() {
+            return true;
+        }
+        let (x_min, y_min) = (coords.x.saturating_sub(radius), coords.y.saturating_sub(radius));
+        let x_max = coords.x.saturating_add(radius).min(self.tile_map.width() - 1);
+        let y_max = coords.y.saturating_add(radius).min(self.tile_map.height() - 1);
+        (y_min..=y_max).any(|y| {
+            (x_min..=x_max).any(|x| {
+                let c = Coordinates { x, y };
+                self.tile_map[y as usize][x as usize] != Tile::Hole && !self.is_covered(&c)
+            })
+        })
+    }
+
+    /// Number of covered tiles, including the ones of unspawned chunks
+    pub fn covered_count(&self) -> usize {
+        self.covered_tiles.len() + self.chunks.as_ref().map_or(0, |c| c.covered.len())
+    }
+
+    /// We try to add a flag to a covered tile, or to remove all of its flags once it holds
+    /// the variant maximum, returning the cover entity and whether a flag was added
+    pub fn try_toggle_mark(&mut self, coords: &Coordinates) -> Option<(Entity, bool)> {
+        if !self.in_sight(coords) {
+            return None;
+        }
+        let entity = *self.covered_tiles.get(coords)?;
+        let flags = self.marked_tiles.entry(*coords).or_default();
+        let mark = if *flags >= self.variant.max_flags() {
+            self.marked_tiles.remove(coords);
+            false
+        } else {
+            *flags += 1;
+            true
+        };
+        Some((entity, mark))
+    }
+
+    /// Flags every covered tile up to the flags its bomb requires, all of them being mines once
+    /// the board is completed. Returns the spawned covers, ordered by position, and the indices
+    /// of their new flags
+    pub fn flag_remaining(&mut self) -> Vec<(Coordinates, Entity, Range<u8>)> {
+        let chunk_covered = self.chunks.as_ref().map(|c| c.covered.iter().copied());
+        let mut covered: Vec<Coordinates> =
+            self.covered_tiles.keys().copied().chain(chunk_covered.into_iter().flatten()).collect();
+        covered.sort_by_key(|c| (c.y, c.x));
+        let mut flagged = Vec::new();
+        for coords in covered {
+            let required = match self.tile_map[coords.y as usize][coords.x as usize] {
+                Tile::ArmoredBomb(armor) => armor,
+                _ => 1,
+            };
+            let flags = self.flags_at(&coords);
+            let target = required.min(self.variant.max_flags()).max(flags);
+            self.marked_tiles.insert(coords, target);
+            if let Some(entity) = self.covered_tiles.get(&coords).filter(|_| target > flags) {
+                flagged.push((coords, *entity, flags..target));
+            }
+        }
+        flagged
+    }
+
+    /// Covered and unflagged neighbors of the numbered tile at `coords` proven to be mines, its
+    /// count matching its covered and detonated neighbors. Color region counts are matched
+    /// region by region. Player flags are not trusted, and only classic and color region boards
+    /// are deduced
+    pub fn proven_mines(&self, coords: Coordinates) -> Vec<Coordinates> {
+        let count = match self.uncovered_bomb_count(coords) {
+            None => return Vec::new(),
+            Some(c) => c as usize,
+        };
+        match (&self.variant, &self.regions) {
+            (BoardVariant::Classic, _) => self.proven_among(self.tile_map.neighbors(coords), count),
+            (BoardVariant::ColorRegions { .. }, Some(regions)) => {
+                let counts = regions.bomb_counts(&self.tile_map, coords);
+                (0..regions.count())
+                    .flat_map(|region| {
+                        let tiles = self
+                            .tile_map
+                            .neighbors(coords)
+                            .filter(move |c| regions.region_at(*c) == region);
+                        self.proven_among(tiles, counts[region as usize] as usize)
+                    })
+                    .collect()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    /// Covered and unflagged `tiles` proven to be mines, `count` mines being among them
+    fn proven_among(
+        &self,
+        tiles: impl Iterator<Item = Coordinates>,
+        count: usize,
+    ) -> Vec<Coordinates> {
+        let (covered, detonated): (Vec<_>, Vec<_>) = tiles
+            .filter(|c| self.is_covered(c) || self.detonated_tiles.contains(c))
+            .partition(|c| self.is_covered(c));
+        if covered.len() + detonated.len() != count {
+            return Vec::new();
+        }
+        covered.into_iter().filter(|c| self.flags_at(c) == 0).collect()
+    }
+
+    /// Kind of the mine at `coords`, standard for other tiles
+    pub fn mine_kind(&self, coords: &Coordinates) -> MineKind {
+        self.mine_kinds.get(coords).copied().unwrap_or_default()
+    }
+
+    /// Number of flagged decoys
+    pub fn flagged_decoys(&self) -> u32 {
+        let decoys = self.mine_kinds.iter().filter(|(_, kind)| **kind == MineKind::Decoy);
+        decoys.filter(|(c, _)| self.marked_tiles.contains_key(*c)).count() as u32
+    }
+
+    /// Number of flags on `coords`
+    pub fn flags_at(&self, coords: &Coordinates) -> u8 {
+        self.marked_tiles.get(coords).copied().unwrap_or_default()
+    }
+
+    /// Number of bombs minus the placed flags and detonated bombs, negative if there are more
+    /// flags than bombs
+    pub fn remaining_mines(&self) -> i32 {
+        let flags: i32 = self.marked_tiles.values().map(|f| *f as i32).sum();
+        self.tile_map.bomb_count() as i32 - flags - self.detonated_tiles.len() as i32
+    }
+
+    /// Is the board completed, all remaining covered tiles being bombs
+    pub fn is_completed(&self) -> bool {
+        self.tile_map.bomb_count() as usize == self.covered_count() + self.detonated_tiles.len()
+    }
+
+    /// 3BV of the board, the minimum number of clicks required to clear it
+    pub fn bbbv(&self) -> u32 {
+        self.tile_map.bbbv()
+    }
+
+    /// Overall difficulty score of the board
+    pub fn difficulty_score(&self) -> f32 {
+        self.tile_map.difficulty_score()
+    }
+
+    /// Number of uncovered safe tiles
+    pub fn uncovered_tiles(&self) -> usize {
+        self.tile_map.tile_count() - self.covered_count() - self.detonated_tiles.len()
+    }
+
+    /// Share of the safe tiles uncovered, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        let safe = self.tile_map.tile_count().saturating_sub(self.tile_map.bomb_count() as usize);
+        if safe == 0 {
+            return 1.;
+        }
+        (self.uncovered_tiles() as f32 / safe as f32).min(1.)
+    }
+
+    /// Retrieves the bomb count of an uncovered bomb neighbor tile
+    pub fn uncovered_bomb_count(&self, coords: Coordinates) -> Option<i8> {
+        if coords.x >= self.tile_map.width()
+            || coords.y >= self.tile_map.height()
+            || self.is_covered(&coords)
+        {
+            return None;
+        }
+        match self.tile_map[coords.y as usize][coords.x as usize] {
+            Tile::BombNeighbor(count) => Some(count),
+            _ => None,
+        }
+    }
+
+    /// Number of flags around `coord`, detonated bombs counting as flagged
+    pub fn adjacent_marked_count(&self, coord: Coordinates) -> usize {
+        self.tile_map
+            .safe_square_at(coord)
+            .map(|c| match self.detonated_tiles.contains(&c) {
+                true => self.tile_map[c.y as usize][c.x as usize].bomb_weight().max(0) as usize,
+                false => self.flags_at(&c) as usize,
+            })
+            .sum()
+    }
+
+    /// Covered and unflagged neighbors uncovered by chording the uncovered number at `coords`,
+    /// none if its adjacent flags don't match its count
+    pub fn chord_targets(&self, coords: Coordinates) -> Vec<Coordinates> {
+        let count = match self.uncovered_bomb_count(coords) {
+            None => return Vec::new(),
+            Some(c) => c,
+        };
+        if self.adjacent_marked_count(coords) as isize != count as isize {
+            debug!("Chord on {} ignored, flags don't match", coords);
+            return Vec::new();
+        }
+        self.tile_map
+            .neighbors(coords)
+            .filter(|c| self.is_covered(c) && self.flags_at(c) == 0)
+            .collect()
+    }
+
+    /// We retrieve the adjacent covered and unmarked tile entities of `coord`
+    pub fn adjacent_unmarked_tiles(&self, coord: Coordinates) -> Vec<Entity> {
         self.tile_map
             .safe_square_at(coord)
+            .filter(|c| !self.marked_tiles.contains_key(c))
             .filter_map(|c| self.covered_tiles.get(&c))
             .copied()
             .collect()
     }
+
+    /// Tile at `coords` as seen by the player
+    pub fn visible_tile(&self, coords: Coordinates) -> VisibleTile {
+        match self.tile_map[coords.y as usize][coords.x as usize] {
+            Tile::Hole => VisibleTile::Hole,
+            _ if self.is_covered(&coords) => VisibleTile::Covered { flags: self.flags_at(&coords) },
+            _ if self.detonated_tiles.contains(&coords) => VisibleTile::Detonated,
+            Tile::Empty => VisibleTile::Empty,
+            Tile::BombNeighbor(count) => VisibleTile::Number(count),
+            _ => VisibleTile::Exploded,
+        }
+    }
+
+    /// Cloneable copy of the visible board state
+    pub fn snapshot(&self) -> BoardSnapshot {
+        let (width, height) = (self.tile_map.width(), self.tile_map.height());
+        BoardSnapshot {
+            width,
+            height,
+            tiles: (0..height)
+                .flat_map(|y| (0..width).map(move |x| Coordinates { x, y }))
+                .map(|coords| self.visible_tile(coords))
+                .collect(),
+        }
+    }
+
+    /// Renders the board as seen by the player, top row first:
+    /// - `#` is a covered tile and `F` a marked one, or its flag count if above one
+    /// - `.` is an empty tile and digits are bomb counts, counts outside of `0..=9` being shown
+    ///   as `+` or `-`
+    /// - `*` is an exploded bomb and `X` a detonated one
+    pub fn render_ascii(&self) -> String {
+        let (width, height) = (self.tile_map.width(), self.tile_map.height());
+        let border = format!("+{}+\n", "-".repeat(width as usize));
+        let mut buffer = border.clone();
+        for y in (0..height).rev() {
+            buffer.push('|');
+            for x in 0..width {
+                buffer.push(match self.visible_tile(Coordinates { x, y }) {
+                    VisibleTile::Hole => ' ',
+                    VisibleTile::Covered { flags: 0 } => '#',
+                    VisibleTile::Covered { flags: 1 } => 'F',
+                    VisibleTile::Covered { flags } => {
+                        char::from_digit(flags.min(9) as u32, 10).unwrap_or('F')
+                    }
+                    VisibleTile::Detonated => 'X',
+                    VisibleTile::Empty => '.',
+                    VisibleTile::Number(count) if count < 0 => '-',
+                    VisibleTile::Number(count) => char::from_digit(count as u32, 10).unwrap_or('+'),
+                    VisibleTile::Exploded => '*',
+                });
+            }
+            buffer.push_str("|\n");
+        }
+        buffer.push_str(&border);
+        buffer
+    }
 }
\ No newline at end of file
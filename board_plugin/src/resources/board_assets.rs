@@ -0,0 +1,187 @@
+#[cfg(feature = "embedded-assets")]
+use crate::embedded;
+use crate::resources::{CoverStyle, NightMode, NumberPalette, TileAtlas};
+use bevy::prelude::*;
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+
+/// Material of a `Sprite` with a texture and color
+#[derive(Debug, Clone)]
+pub struct SpriteMaterial {
+    pub color: Color,
+    pub texture: Handle<Image>,
+}
+
+impl SpriteMaterial {
+    /// Plain color material
+    pub fn color(color: Color) -> Self {
+        Self { color, ..Default::default() }
+    }
+
+    /// White tinted texture material
+    pub fn texture(texture: Handle<Image>) -> Self {
+        Self { texture, ..Default::default() }
+    }
+}
+
+impl Default for SpriteMaterial {
+    fn default() -> Self {
+        Self { color: Color::WHITE, texture: DEFAULT_IMAGE_HANDLE.typed() }
+    }
+}
+
+/// Assets for the board. Must be used as a resource.
+///
+/// Use the loader for partial setup
+#[derive(Debug, Clone)]
+pub struct BoardAssets {
+    /// Label
+    pub label: String,
+    pub board_material: SpriteMaterial,
+    pub tile_material: SpriteMaterial,
+    pub covered_tile_material: SpriteMaterial,
+    /// Covered tile under the cursor
+    pub hovered_tile_material: SpriteMaterial,
+    /// Covered tile under a held mouse button
+    pub pressed_tile_material: SpriteMaterial,
+    /// Cover effects, with the `cover_material` feature
+    pub cover_style: CoverStyle,
+    pub bomb_counter_font: Handle<Font>,
+    /// Bomb counter colors, from 1 upwards
+    pub bomb_counter_colors: Vec<Color>,
+    /// Bomb counter colors of the anti-mine variant, from 0 downwards
+    pub negative_bomb_counter_colors: Vec<Color>,
+    /// Draws a dice-like dot pattern next to bomb counters, so they don't rely on colors only
+    pub number_badges: bool,
+    pub flag_material: SpriteMaterial,
+    /// Flags placed by the auto flag assist
+    pub auto_flag_material: SpriteMaterial,
+    pub bomb_material: SpriteMaterial,
+    /// Armored bombs of the `Armored` variant
+    pub armored_bomb_material: SpriteMaterial,
+    /// Anti-mines of the `AntiMine` variant
+    pub anti_mine_material: SpriteMaterial,
+    /// Mega mines, see `BoardOptions::mine_kinds`
+    pub mega_mine_material: SpriteMaterial,
+    /// Decoy mines, see `BoardOptions::mine_kinds`
+    pub decoy_mine_material: SpriteMaterial,
+    /// Region colors of the `ColorRegions` variant, repeating when there are more regions
+    pub region_colors: Vec<Color>,
+    /// Keyboard cursor, drawn over the tiles
+    pub cursor_material: SpriteMaterial,
+    /// Tutorial step highlight, drawn over the tiles
+    pub tutorial_material: SpriteMaterial,
+    /// Darkens the board but around uncovered tiles. Can be toggled at runtime with
+    /// `NightModeEvent`
+    pub night_mode: Option<NightMode>,
+    /// Texture atlas the tiles are drawn from once built, see `BoardOptions::texture_atlas`
+    pub atlas: Option<TileAtlas>,
+}
+
+impl BoardAssets {
+    /// Default colors for the bomb neighbor counters
+    pub fn default_colors() -> Vec<Color> {
+        NumberPalette::Classic.colors()
+    }
+
+    /// Default colors for the zero and negative bomb neighbor counters
+    pub fn default_negative_colors() -> Vec<Color> {
+        NumberPalette::Classic.negative_colors()
+    }
+
+    /// Default colors for the `ColorRegions` variant regions
+    pub fn default_region_colors() -> Vec<Color> {
+        vec![
+            Color::rgb(0.9, 0.45, 0.4),
+            Color::rgb(0.4, 0.6, 0.95),
+            Color::rgb(0.45, 0.8, 0.45),
+            Color::rgb(0.95, 0.8, 0.35),
+        ]
+    }
+
+    /// Uses the `palette` bomb counter colors, for the boards spawned afterwards
+    pub fn set_palette(&mut self, palette: NumberPalette) {
+        self.bomb_counter_colors = palette.colors();
+        self.negative_bomb_counter_colors = palette.negative_colors();
+    }
+
+    /// Safely retrieves the color matching a bomb counter
+    pub fn bomb_counter_color(&self, counter: i8) -> Color {
+        let (colors, index) = if counter > 0 {
+            (&self.bomb_counter_colors, counter as usize - 1)
+        } else {
+            (&self.negative_bomb_counter_colors, counter.unsigned_abs() as usize)
+        };
+        match colors.get(index) {
+            Some(c) => *c,
+            None => match colors.last() {
+                None => Color::WHITE,
+                Some(c) => *c,
+            },
+        }
+    }
+
+    /// Default font, flag and bomb textures, loaded from the assets folder
+    #[cfg(not(feature = "embedded-assets"))]
+    fn default_handles(world: &mut World) -> (Handle<Font>, Handle<Image>, Handle<Image>) {
+        let asset_server = world.get_resource::<AssetServer>().expect("Missing AssetServer");
+        (
+            asset_server.load("fonts/pixeled.ttf"),
+            asset_server.load("sprites/flag.png"),
+            asset_server.load("sprites/bomb.png"),
+        )
+    }
+
+    /// Default font, flag and bomb textures, compiled into the binary
+    #[cfg(feature = "embedded-assets")]
+    fn default_handles(world: &mut World) -> (Handle<Font>, Handle<Image>, Handle<Image>) {
+        let font = world.resource_mut::<Assets<Font>>().add(embedded::font());
+        let mut images = world.resource_mut::<Assets<Image>>();
+        (
+            font,
+            images.add(embedded::image(embedded::FLAG)),
+            images.add(embedded::image(embedded::BOMB)),
+        )
+    }
+}
+
+// The default theme uses the tutorial assets folder, or its embedded copy with the
+// `embedded-assets` feature
+impl FromWorld for BoardAssets {
+    fn from_world(world: &mut World) -> Self {
+        let (font, flag, bomb) = Self::default_handles(world);
+        Self {
+            label: "Default".to_string(),
+            board_material: SpriteMaterial::color(Color::WHITE),
+            tile_material: SpriteMaterial::color(Color::GRAY),
+            covered_tile_material: SpriteMaterial::color(Color::DARK_GRAY),
+            hovered_tile_material: SpriteMaterial::color(Color::rgb(0.4, 0.4, 0.4)),
+            pressed_tile_material: SpriteMaterial::color(Color::GRAY),
+            cover_style: CoverStyle::default(),
+            bomb_counter_font: font,
+            bomb_counter_colors: Self::default_colors(),
+            negative_bomb_counter_colors: Self::default_negative_colors(),
+            number_badges: false,
+            flag_material: SpriteMaterial::texture(flag.clone()),
+            auto_flag_material: SpriteMaterial { color: Color::rgb(0.6, 0.8, 1.), texture: flag },
+            bomb_material: SpriteMaterial::texture(bomb.clone()),
+            armored_bomb_material: SpriteMaterial {
+                color: Color::rgb(0.5, 0.6, 1.),
+                texture: bomb.clone(),
+            },
+            anti_mine_material: SpriteMaterial {
+                color: Color::rgb(0.4, 1., 0.6),
+                texture: bomb.clone(),
+            },
+            mega_mine_material: SpriteMaterial {
+                color: Color::rgb(1., 0.4, 0.3),
+                texture: bomb.clone(),
+            },
+            decoy_mine_material: SpriteMaterial { color: Color::rgb(1., 0.9, 0.4), texture: bomb },
+            region_colors: Self::default_region_colors(),
+            cursor_material: SpriteMaterial::color(Color::rgba(1., 1., 0., 0.3)),
+            tutorial_material: SpriteMaterial::color(Color::rgba(0.2, 0.6, 1., 0.4)),
+            night_mode: None,
+            atlas: None,
+        }
+    }
+}
@@ -0,0 +1,410 @@
+use crate::resources::{BoardLayout, BoardOptions, BoardVariant, BombDensity, MineKinds, Symmetry};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Crockford base32 alphabet, avoiding the ambiguous I, L, O and U characters
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+/// Code format version, the first encoded byte
+const VERSION: u8 = 2;
+/// Flags of the generation options written after the variant, only when not the default
+const SYMMETRY: u8 = 1;
+const DENSITY: u8 = 2;
+const MIN_OPENING: u8 = 4;
+const MINE_KINDS: u8 = 8;
+const EXTRAS: u8 = SYMMETRY | DENSITY | MIN_OPENING | MINE_KINDS;
+
+/// Board code encoding and parsing errors
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BoardCodeError {
+    /// The board can't be rebuilt from a code
+    NotShareable(&'static str),
+    /// The code contains a non base32 character
+    InvalidCharacter(char),
+    /// The code is too short or too long
+    InvalidLength,
+    /// The code was made by an incompatible version
+    UnsupportedVersion(u8),
+    /// The code contains an unknown variant
+    UnknownVariant(u8),
+    /// The code contains an unknown value of a generation option
+    InvalidOption(&'static str),
+    /// The code is corrupted, probably mistyped
+    InvalidChecksum,
+}
+
+impl Display for BoardCodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotShareable(reason) => write!(f, "board can't be shared: {}", reason),
+            Self::InvalidCharacter(c) => write!(f, "invalid character '{}' in board code", c),
+            Self::InvalidLength => write!(f, "board code has an invalid length"),
+            Self::UnsupportedVersion(v) => write!(f, "unsupported board code version {}", v),
+            Self::UnknownVariant(v) => write!(f, "unknown board variant {}", v),
+            Self::InvalidOption(option) => write!(f, "invalid {} in board code", option),
+            Self::InvalidChecksum => write!(f, "board code is corrupted, check for typos"),
+        }
+    }
+}
+
+impl Error for BoardCodeError {}
+
+impl BoardOptions {
+    /// Encodes the map size, bomb count, seed, rules and generation options into a short code,
+    /// so players can share exact boards. Only seeded, rectangular and randomly laid out boards
+    /// can be shared
+    pub fn to_code(&self) -> Result<String, BoardCodeError> {
+        let seed = self.seed.ok_or(BoardCodeError::NotShareable("it has no seed"))?;
+        let count = match self.layout {
+            BoardLayout::Random { count } => count,
+            BoardLayout::Explicit(_) => {
+                return Err(BoardCodeError::NotShareable("its bombs are placed by hand"))
+            }
+        };
+        if self.mask.is_some() {
+            return Err(BoardCodeError::NotShareable("it is not rectangular"));
+        }
+        let mut bytes = vec![VERSION, self.safe_start as u8, self.lives];
+        bytes.extend_from_slice(&self.map_size.0.to_be_bytes());
+        bytes.extend_from_slice(&self.map_size.1.to_be_bytes());
+        bytes.extend_from_slice(&count.to_be_bytes());
+        bytes.extend_from_slice(&seed.to_be_bytes());
+        match self.variant {
+            BoardVariant::Classic => bytes.push(0),
+            BoardVariant::Armored { armored_count, max_armor } => {
+                bytes.push(1);
+                bytes.extend_from_slice(&armored_count.to_be_bytes());
+                bytes.push(max_armor);
+            }
+            BoardVariant::AntiMine { anti_mine_count } => {
+                bytes.push(2);
+                bytes.extend_from_slice(&anti_mine_count.to_be_bytes());
+            }
+            BoardVariant::ColorRegions { regions } => {
+                bytes.push(3);
+                bytes.push(regions);
+            }
+        }
+        let (mut extras, mut extra_bytes) = (0, Vec::new());
+        if self.symmetry != Symmetry::None {
+            extras |= SYMMETRY;
+            extra_bytes.push(self.symmetry as u8);
+        }
+        let gradient = match self.density {
+            BombDensity::Uniform => None,
+            BombDensity::CenterGradient { strength } => Some((0, strength)),
+            BombDensity::EdgeGradient { strength } => Some((1, strength)),
+            BombDensity::Grid(_) => {
+                return Err(BoardCodeError::NotShareable("its bomb density is a custom grid"))
+            }
+        };
+        if let Some((kind, strength)) = gradient {
+            extras |= DENSITY;
+            extra_bytes.push(kind);
+            extra_bytes.extend_from_slice(&strength.to_bits().to_be_bytes());
+        }
+        if self.min_opening != 0 {
+            extras |= MIN_OPENING;
+            extra_bytes.extend_from_slice(&self.min_opening.to_be_bytes());
+        }
+        let (kinds, default_kinds) = (self.mine_kinds, MineKinds::default());
+        if (kinds.mega, kinds.decoy, kinds.decoy_bonus)
+            != (default_kinds.mega, default_kinds.decoy, default_kinds.decoy_bonus)
+        {
+            extras |= MINE_KINDS;
+            extra_bytes.extend_from_slice(&kinds.mega.to_bits().to_be_bytes());
+            extra_bytes.extend_from_slice(&kinds.decoy.to_bits().to_be_bytes());
+            extra_bytes.extend_from_slice(&kinds.decoy_bonus.to_be_bytes());
+        }
+        bytes.push(extras);
+        bytes.extend(extra_bytes);
+        bytes.push(checksum(&bytes));
+        Ok(encode_base32(&bytes))
+    }
+
+    /// Parses a board code made by `to_code`, the other options keeping their default value.
+    /// Codes are case insensitive and dashes are ignored
+    pub fn from_code(code: &str) -> Result<Self, BoardCodeError> {
+        let bytes = decode_base32(code)?;
+        let (checksum_byte, bytes) = bytes.split_last().ok_or(BoardCodeError::InvalidLength)?;
+        if bytes.first().is_some() && bytes[0] != VERSION {
+            return Err(BoardCodeError::UnsupportedVersion(bytes[0]));
+        }
+        if checksum(bytes) != *checksum_byte {
+            return Err(BoardCodeError::InvalidChecksum);
+        }
+        let mut reader = ByteReader(bytes);
+        let _version = reader.u8()?;
+        let safe_start = reader.u8()? != 0;
+        let lives = reader.u8()?;
+        let map_size = (reader.u16()?, reader.u16()?);
+        let count = reader.u16()?;
+        let seed = reader.u64()?;
+        let variant = match reader.u8()? {
+            0 => BoardVariant::Classic,
+            1 => BoardVariant::Armored { armored_count: reader.u16()?, max_armor: reader.u8()? },
+            2 => BoardVariant::AntiMine { anti_mine_count: reader.u16()? },
+            3 => BoardVariant::ColorRegions { regions: reader.u8()? },
+            v => return Err(BoardCodeError::UnknownVariant(v)),
+        };
+        let extras = reader.u8()?;
+        if extras & !EXTRAS != 0 {
+            return Err(BoardCodeError::InvalidOption("option flags"));
+        }
+        let symmetry = match (extras & SYMMETRY != 0).then(|| reader.u8()).transpose()? {
+            None | Some(0) => Symmetry::None,
+            Some(1) => Symmetry::Horizontal,
+            Some(2) => Symmetry::Vertical,
+            Some(3) => Symmetry::Rotational,
+            Some(_) => return Err(BoardCodeError::InvalidOption("symmetry")),
+        };
+        let density = match (extras & DENSITY != 0).then(|| reader.u8()).transpose()? {
+            None => BombDensity::Uniform,
+            Some(0) => BombDensity::CenterGradient { strength: reader.f32()? },
+            Some(1) => BombDensity::EdgeGradient { strength: reader.f32()? },
+            Some(_) => return Err(BoardCodeError::InvalidOption("density")),
+        };
+        let min_opening =
+            (extras & MIN_OPENING != 0).then(|| reader.u16()).transpose()?.unwrap_or_default();
+        let mine_kinds = if extras & MINE_KINDS != 0 {
+            MineKinds { mega: reader.f32()?, decoy: reader.f32()?, decoy_bonus: reader.u32()? }
+        } else {
+            MineKinds::default()
+        };
+        if !reader.0.is_empty() {
+            return Err(BoardCodeError::InvalidLength);
+        }
+        Ok(Self {
+            map_size,
+            layout: BoardLayout::Random { count },
+            safe_start,
+            seed: Some(seed),
+            variant,
+            lives,
+            symmetry,
+            density,
+            min_opening,
+            mine_kinds,
+            ..Default::default()
+        })
+    }
+}
+
+/// Reads big endian values, failing at the end of the bytes
+struct ByteReader<'a>(&'a [u8]);
+
+impl ByteReader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], BoardCodeError> {
+        if self.0.len() < N {
+            return Err(BoardCodeError::InvalidLength);
+        }
+        let (value, rest) = self.0.split_at(N);
+        self.0 = rest;
+        Ok(value.try_into().expect("Slice has the array length"))
+    }
+
+    fn u8(&mut self) -> Result<u8, BoardCodeError> {
+        Ok(self.take::<1>()?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, BoardCodeError> {
+        Ok(u16::from_be_bytes(self.take()?))
+    }
+
+    fn u32(&mut self) -> Result<u32, BoardCodeError> {
+        Ok(u32::from_be_bytes(self.take()?))
+    }
+
+    fn f32(&mut self) -> Result<f32, BoardCodeError> {
+        Ok(f32::from_bits(self.u32()?))
+    }
+
+    fn u64(&mut self) -> Result<u64, BoardCodeError> {
+        Ok(u64::from_be_bytes(self.take()?))
+    }
+}
+
+/// Position dependent checksum, catching swapped characters
+fn checksum(bytes: &[u8]) -> u8 {
+    bytes
+        .iter()
+        .enumerate()
+        .fold(0u8, |sum, (i, b)| sum.wrapping_mul(31).wrapping_add(*b ^ i as u8))
+}
+
+fn encode_base32(bytes: &[u8]) -> String {
+    let mut code = String::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for byte in bytes {
+        buffer = (buffer << 8) | *byte as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            code.push(ALPHABET[((buffer >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        code.push(ALPHABET[((buffer << (5 - bits)) & 31) as usize] as char);
+    }
+    code
+}
+
+fn decode_base32(code: &str) -> Result<Vec<u8>, BoardCodeError> {
+    let mut bytes = Vec::new();
+    let (mut buffer, mut bits) = (0u32, 0);
+    for c in code.chars().filter(|c| *c != '-') {
+        // Ambiguous characters are read as the digit they look like
+        let value = match c.to_ascii_uppercase() {
+            'O' => 0,
+            'I' | 'L' => 1,
+            upper => ALPHABET
+                .iter()
+                .position(|a| *a as char == upper)
+                .ok_or(BoardCodeError::InvalidCharacter(c))?,
+        };
+        buffer = (buffer << 5) | value as u32;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            bytes.push((buffer >> bits) as u8);
+        }
+    }
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::Coordinates;
+    use crate::resources::BoardMask;
+
+    fn options() -> BoardOptions {
+        BoardOptions {
+            map_size: (30, 16),
+            layout: BoardLayout::Random { count: 99 },
+            seed: Some(0xDEAD_BEEF),
+            safe_start: true,
+            lives: 3,
+            variant: BoardVariant::Armored { armored_count: 5, max_armor: 4 },
+            ..Default::default()
+        }
+    }
+
+    /// Code of raw `bytes`, with a valid checksum
+    fn code_of(bytes: &[u8]) -> String {
+        let mut bytes = bytes.to_vec();
+        bytes.push(checksum(&bytes));
+        encode_base32(&bytes)
+    }
+
+    #[test]
+    fn code_round_trips() {
+        let code = options().to_code().unwrap();
+        let parsed = BoardOptions::from_code(&code).unwrap();
+        assert_eq!(parsed.map_size, (30, 16));
+        assert_eq!(parsed.layout, BoardLayout::Random { count: 99 });
+        assert_eq!(parsed.seed, Some(0xDEAD_BEEF));
+        assert!(parsed.safe_start);
+        assert_eq!(parsed.lives, 3);
+        assert_eq!(parsed.variant, options().variant);
+        assert_eq!(parsed.to_code(), Ok(code.clone()));
+        // Codes are case insensitive and dashes are ignored
+        let typed = format!("{}-{}", &code[..4], code[4..].to_lowercase());
+        assert_eq!(BoardOptions::from_code(&typed).unwrap().to_code(), Ok(code));
+    }
+
+    #[test]
+    fn corrupted_codes_are_rejected() {
+        let mut bytes = decode_base32(&options().to_code().unwrap()).unwrap();
+        *bytes.last_mut().unwrap() ^= 1;
+        let code = encode_base32(&bytes);
+        assert_eq!(BoardOptions::from_code(&code).unwrap_err(), BoardCodeError::InvalidChecksum);
+        assert_eq!(
+            BoardOptions::from_code("AB!").unwrap_err(),
+            BoardCodeError::InvalidCharacter('!')
+        );
+    }
+
+    #[test]
+    fn other_versions_are_rejected() {
+        let code = code_of(&[VERSION + 1, 0, 1]);
+        assert_eq!(
+            BoardOptions::from_code(&code).unwrap_err(),
+            BoardCodeError::UnsupportedVersion(VERSION + 1)
+        );
+    }
+
+    #[test]
+    fn truncated_codes_are_rejected() {
+        assert_eq!(BoardOptions::from_code("").unwrap_err(), BoardCodeError::InvalidLength);
+        // The checksum matches, the map size is missing
+        let code = code_of(&[VERSION, 0, 1]);
+        assert_eq!(BoardOptions::from_code(&code).unwrap_err(), BoardCodeError::InvalidLength);
+    }
+
+    #[test]
+    fn generation_options_round_trip() {
+        let symmetric = BoardOptions { symmetry: Symmetry::Rotational, ..options() };
+        let parsed = BoardOptions::from_code(&symmetric.to_code().unwrap()).unwrap();
+        assert_eq!(parsed.symmetry, Symmetry::Rotational);
+        let dense = BoardOptions {
+            density: BombDensity::EdgeGradient { strength: 2.5 },
+            symmetry: Symmetry::Vertical,
+            ..options()
+        };
+        let parsed = BoardOptions::from_code(&dense.to_code().unwrap()).unwrap();
+        assert_eq!(parsed.density, BombDensity::EdgeGradient { strength: 2.5 });
+        assert_eq!(parsed.symmetry, Symmetry::Vertical);
+        let opening = BoardOptions { min_opening: 300, ..options() };
+        let parsed = BoardOptions::from_code(&opening.to_code().unwrap()).unwrap();
+        assert_eq!(parsed.min_opening, 300);
+        let mine_kinds = MineKinds { mega: 0.25, decoy: 0.1, decoy_bonus: 50 };
+        let kinds = BoardOptions { mine_kinds, ..options() };
+        let parsed = BoardOptions::from_code(&kinds.to_code().unwrap()).unwrap();
+        assert_eq!(parsed.mine_kinds.mega, 0.25);
+        assert_eq!(parsed.mine_kinds.decoy, 0.1);
+        assert_eq!(parsed.mine_kinds.decoy_bonus, 50);
+        // Default generation options only take their flags byte
+        let code = options().to_code().unwrap();
+        assert!(code.len() < symmetric.to_code().unwrap().len());
+        assert_eq!(BoardOptions::from_code(&code).unwrap().symmetry, Symmetry::None);
+    }
+
+    #[test]
+    fn unknown_generation_options_are_rejected() {
+        let mut bytes = decode_base32(&options().to_code().unwrap()).unwrap();
+        bytes.pop();
+        let flags = bytes.len() - 1;
+        bytes[flags] = 0x80;
+        assert_eq!(
+            BoardOptions::from_code(&code_of(&bytes)).unwrap_err(),
+            BoardCodeError::InvalidOption("option flags")
+        );
+        bytes[flags] = SYMMETRY;
+        bytes.push(9);
+        assert_eq!(
+            BoardOptions::from_code(&code_of(&bytes)).unwrap_err(),
+            BoardCodeError::InvalidOption("symmetry")
+        );
+    }
+
+    #[test]
+    fn unshareable_boards_have_no_code() {
+        let unseeded = BoardOptions { seed: None, ..options() };
+        assert_eq!(unseeded.to_code(), Err(BoardCodeError::NotShareable("it has no seed")));
+        let explicit = BoardOptions {
+            layout: BoardLayout::Explicit(vec![Coordinates { x: 0, y: 0 }]),
+            ..options()
+        };
+        assert_eq!(
+            explicit.to_code(),
+            Err(BoardCodeError::NotShareable("its bombs are placed by hand"))
+        );
+        let masked = BoardOptions { mask: Some(BoardMask(vec!["##".to_string()])), ..options() };
+        assert_eq!(masked.to_code(), Err(BoardCodeError::NotShareable("it is not rectangular")));
+        let weighted = BoardOptions { density: BombDensity::Grid(vec![vec![2.]]), ..options() };
+        assert_eq!(
+            weighted.to_code(),
+            Err(BoardCodeError::NotShareable("its bomb density is a custom grid"))
+        );
+    }
+}
@@ -0,0 +1,128 @@
+use crate::resources::tile::Tile;
+use crate::resources::tile_map::PARALLEL_TILE_COUNT;
+use crate::resources::{BoardLayout, BoardOptions, Cascade, TileMap};
+use bevy::log::{info_span, trace, warn};
+use rayon::prelude::*;
+
+/// Maximum number of layouts generated to find a safe start opening of `BoardOptions::min_opening`
+const MAX_OPENING_ATTEMPTS: u64 = 100;
+
+impl BoardOptions {
+    /// Generates the tile map of the options, with a random seed if none is set
+    pub(crate) fn build_map(&self) -> TileMap {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let _span = info_span!("build_map", seed).entered();
+        let mut tile_map = self.generate_map(seed);
+        if self.safe_start
+            && matches!(self.layout, BoardLayout::Random { .. })
+            && !self.has_min_opening(&tile_map)
+        {
+            match self.find_opening(seed, MAX_OPENING_ATTEMPTS) {
+                Some((attempts, map)) => {
+                    trace!(attempts, "Generated a safe start opening");
+                    tile_map = map;
+                }
+                None => warn!("No safe start opening of {} tiles was generated", self.min_opening),
+            }
+        }
+        #[cfg(feature = "debug")]
+        bevy::log::info!("{}", tile_map.console_output());
+        tile_map
+    }
+
+    /// Generates the layout again with the seeds following `seed` until the safe start opening
+    /// is big enough, so a seed always gives the same board. Returns the first valid attempt out
+    /// of `attempts`, the `seed` layout being the first one
+    fn find_opening(&self, seed: u64, attempts: u64) -> Option<(u64, TileMap)> {
+        let attempt = |attempt| (attempt, self.generate_map(seed.wrapping_add(attempt)));
+        let valid = |(_, map): &(u64, TileMap)| self.has_min_opening(map);
+        // Large maps try their attempts in parallel, the first valid one being kept
+        let tile_count = self.map_size.0 as usize * self.map_size.1 as usize;
+        if tile_count >= PARALLEL_TILE_COUNT {
+            (1..attempts).into_par_iter().map(attempt).find_first(valid)
+        } else {
+            (1..attempts).map(attempt).find(valid)
+        }
+    }
+
+    /// Does the safe start of `map` open at least `min_opening` tiles. Only empty tiles open
+    /// with an `EmptyOnly` cascade, like in `compute_cascades`
+    fn has_min_opening(&self, map: &TileMap) -> bool {
+        let size = map.safe_start().map_or(0, |c| match self.cascade {
+            Cascade::EmptyOnly => map
+                .opening_at(c)
+                .iter()
+                .filter(|c| map[c.y as usize][c.x as usize] == Tile::Empty)
+                .count(),
+            _ => map.opening_at(c).len(),
+        });
+        size >= self.min_opening as usize
+    }
+
+    /// Generates the layout of the `seed`
+    fn generate_map(&self, seed: u64) -> TileMap {
+        let mut tile_map = TileMap::empty(self.map_size.0, self.map_size.1);
+        if let Some(mask) = &self.mask {
+            tile_map.set_mask(mask);
+        }
+        match &self.layout {
+            BoardLayout::Random { count } => {
+                tile_map.set_bombs(*count, seed, &self.variant, self.symmetry, &self.density)
+            }
+            BoardLayout::Explicit(positions) => {
+                tile_map.set_bomb_positions(positions, seed, &self.variant)
+            }
+        }
+        tile_map
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(min_opening: u16) -> BoardOptions {
+        BoardOptions {
+            map_size: (9, 9),
+            layout: BoardLayout::Random { count: 10 },
+            seed: Some(11),
+            safe_start: true,
+            min_opening,
+            ..Default::default()
+        }
+    }
+
+    /// Tiles opened by the safe start
+    fn opening(map: &TileMap) -> usize {
+        map.safe_start().map_or(0, |c| map.opening_at(c).len())
+    }
+
+    #[test]
+    fn safe_start_opens_min_opening_tiles() {
+        for min_opening in [1, 5, 10] {
+            let map = options(min_opening).build_map();
+            assert!(opening(&map) >= min_opening as usize, "{}", map.seed());
+            // A seed always gives the same board
+            assert_eq!(options(min_opening).build_map().seed(), map.seed());
+        }
+    }
+
+    #[test]
+    fn retries_stop_at_the_attempt_cap() {
+        // Find a seed and min opening only reached after some attempts
+        let (seed, min_opening, first) = (0..10)
+            .flat_map(|seed| (2..81).rev().map(move |min_opening| (seed, min_opening)))
+            .find_map(|(seed, min_opening)| {
+                let (attempt, _) = options(min_opening).find_opening(seed, MAX_OPENING_ATTEMPTS)?;
+                (attempt > 1).then(|| (seed, min_opening, attempt))
+            })
+            .expect("An opening needs several attempts");
+        let options = options(min_opening);
+        assert!(options.find_opening(seed, first).is_none());
+        assert_eq!(options.find_opening(seed, first + 1).map(|(a, _)| a), Some(first));
+        // Unreachable openings fall back to the layout of the seed
+        let options = BoardOptions { min_opening: 82, ..options };
+        assert!(options.find_opening(11, MAX_OPENING_ATTEMPTS).is_none());
+        assert_eq!(options.build_map().seed(), 11);
+    }
+}
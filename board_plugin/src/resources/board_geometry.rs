@@ -0,0 +1,101 @@
+use crate::bounds::Bounds2;
+use crate::Coordinates;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Board placement in the world. It is kept apart from the `Board` so resizes don't flag the
+/// tiles as changed, and systems laying visuals out only read what they need. Must be used as
+/// a resource, inserted and removed along with the `Board`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BoardGeometry {
+    /// Board area before its view is rotated or mirrored
+    pub bounds: Bounds2,
+    pub tile_size: f32,
+    /// Visual rotation and mirroring of the board
+    pub view: BoardView,
+}
+
+/// Rotation and mirroring of the displayed board around its center, for disorientation
+/// challenges. Coordinates are unchanged, input being mapped through the view
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BoardView {
+    /// Counterclockwise rotation in degrees, from 0 to 360
+    pub rotation: f32,
+    /// Are the columns mirrored, before the rotation
+    pub mirrored: bool,
+}
+
+impl BoardView {
+    fn rotation(&self) -> Quat {
+        Quat::from_rotation_z(self.rotation.to_radians())
+    }
+
+    fn scale(&self) -> Vec3 {
+        Vec3::new(if self.mirrored { -1. } else { 1. }, 1., 1.)
+    }
+}
+
+impl BoardGeometry {
+    /// Translates a mouse position to board coordinates, through the camera rendering the board.
+    /// Without camera, the default 2D camera centered on the world origin is assumed
+    pub fn mouse_position(
+        &self,
+        window: &Window,
+        camera: Option<(&Camera, &GlobalTransform)>,
+        position: Vec2,
+    ) -> Option<Coordinates> {
+        self.world_position(Self::screen_to_world(window, camera, position))
+    }
+
+    /// Translates a window position, with a bottom left origin, to world space
+    pub fn screen_to_world(
+        window: &Window,
+        camera: Option<(&Camera, &GlobalTransform)>,
+        position: Vec2,
+    ) -> Vec2 {
+        let window_size = Vec2::new(window.width(), window.height());
+        match camera {
+            None => position - window_size / 2.,
+            Some((camera, transform)) => {
+                // Window to normalized device coordinates, then back through the projection
+                let ndc = position / window_size * 2. - Vec2::ONE;
+                let ndc_to_world = transform.compute_matrix() * camera.projection_matrix.inverse();
+                ndc_to_world.project_point3(ndc.extend(-1.)).truncate()
+            }
+        }
+    }
+
+    /// Translates a world position to board coordinates
+    pub fn world_position(&self, position: Vec2) -> Option<Coordinates> {
+        // Back to the board area as laid out, the mirroring being its own inverse
+        let center = self.bounds.center();
+        let local = self.view.rotation().inverse() * (position - center).extend(0.);
+        let position = center + (self.view.scale() * local).truncate();
+        self.bounds.tile_at(position, self.tile_size)
+    }
+
+    /// World position of the center of the tile at `coords`, to place effects or UI over it
+    pub fn tile_center(&self, coords: Coordinates) -> Vec2 {
+        let center = self.bounds.center();
+        let local = (self.bounds.tile_center(coords, self.tile_size) - center).extend(0.);
+        center + (self.view.rotation() * (self.view.scale() * local)).truncate()
+    }
+
+    /// Rotates the displayed board counterclockwise by `degrees`
+    pub fn rotate_view(&mut self, degrees: f32) {
+        self.view.rotation = (self.view.rotation + degrees).rem_euclid(360.);
+    }
+
+    /// Mirrors the displayed board, or restores it if it already is
+    pub fn mirror_view(&mut self) {
+        self.view.mirrored = !self.view.mirrored;
+    }
+
+    /// Board entity transform displaying the view, at the `z` depth
+    pub fn view_transform(&self, z: f32) -> Transform {
+        let (rotation, scale) = (self.view.rotation(), self.view.scale());
+        let half_size = (self.bounds.size / 2.).extend(0.);
+        let translation = self.bounds.center().extend(z) - rotation * (scale * half_size);
+        Transform { translation, rotation, scale }
+    }
+}
@@ -0,0 +1,34 @@
+use bevy::prelude::Entity;
+
+/// Tiles of a new board spawned over several frames, see `BoardOptions::spawn_budget`. Must be
+/// used as a resource, removed once the board is ready
+#[derive(Debug, Clone)]
+pub struct BoardLoading {
+    /// Tiles spawned so far
+    pub spawned: usize,
+    /// Tiles inside the board shape
+    pub total: usize,
+    /// Row-major index of the next tile position to spawn
+    pub(crate) next: usize,
+    /// Tile uncovered once every tile is spawned, with `BoardOptions::safe_start`
+    pub(crate) safe_start: Option<Entity>,
+}
+
+impl BoardLoading {
+    pub(crate) fn new(total: usize) -> Self {
+        Self { spawned: 0, total, next: 0, safe_start: None }
+    }
+
+    /// Spawned share of the tiles, from 0 to 1
+    pub fn progress(&self) -> f32 {
+        match self.total {
+            0 => 1.,
+            total => self.spawned as f32 / total as f32,
+        }
+    }
+
+    /// Is every tile spawned
+    pub fn is_done(&self) -> bool {
+        self.spawned >= self.total
+    }
+}
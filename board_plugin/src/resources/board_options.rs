@@ -1,4 +1,9 @@
-use bevy::prelude::Vec3;
+use crate::components::Coordinates;
+use crate::resources::{
+    BoardView, DailyChallenge, Difficulty, MineKinds, PowerUpOptions, TileModifiers,
+};
+use bevy::prelude::{Vec2, Vec3};
+use bevy::utils::HashSet;
 use serde::{Deserialize, Serialize};
 
 /// Tile size options
@@ -17,24 +22,481 @@ pub enum BoardPosition {
     Centered { offset: Vec3 },
     /// Custom position
     Custom(Vec3),
+    /// Board anchored to a window corner or edge
+    Anchored {
+        anchor: BoardAnchor,
+        /// Offset applied after anchoring
+        offset: Vec3,
+        /// Space kept between the board and the window borders
+        margin: f32,
+    },
+}
+
+/// Window corners and edges a board can be anchored to
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BoardAnchor {
+    TopLeft,
+    Top,
+    TopRight,
+    Left,
+    Right,
+    BottomLeft,
+    Bottom,
+    BottomRight,
+}
+
+/// Window space kept free around the board for HUD elements, in logical pixels
+#[derive(Debug, Copy, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Margins {
+    pub top: f32,
+    pub bottom: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Margins {
+    /// Window space left to the board
+    pub fn available_size(&self, window_size: Vec2) -> Vec2 {
+        let reserved = Vec2::new(self.left + self.right, self.top + self.bottom);
+        (window_size - reserved).max(Vec2::ZERO)
+    }
+
+    /// Center of the space left to the board, relative to the window center
+    pub fn center(&self) -> Vec2 {
+        Vec2::new(self.left - self.right, self.bottom - self.top) / 2.
+    }
+}
+
+/// Bomb layout symmetries
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Symmetry {
+    /// Bombs are placed independently
+    None,
+    /// Bombs are mirrored between the left and right halves
+    Horizontal,
+    /// Bombs are mirrored between the top and bottom halves
+    Vertical,
+    /// Bombs are mirrored through the board center, a half turn rotation
+    Rotational,
+}
+
+impl Symmetry {
+    /// Mirror of the `(x, y)` tile on a map of `(width, height)` tiles
+    pub fn mirror(
+        &self,
+        (x, y): (usize, usize),
+        (width, height): (usize, usize),
+    ) -> (usize, usize) {
+        match self {
+            Self::None => (x, y),
+            Self::Horizontal => (width - 1 - x, y),
+            Self::Vertical => (x, height - 1 - y),
+            Self::Rotational => (width - 1 - x, height - 1 - y),
+        }
+    }
+}
+
+impl Default for Symmetry {
+    fn default() -> Self {
+        Self::None
+    }
+}
+
+/// Bomb density over the board, weighting the random bomb placement
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum BombDensity {
+    /// Every tile is as likely to get a bomb
+    Uniform,
+    /// Bombs are denser toward the center, a center tile being `1 + strength` times as likely
+    /// to get a bomb as a corner tile
+    CenterGradient { strength: f32 },
+    /// Bombs are denser toward the edges, a corner tile being `1 + strength` times as likely to
+    /// get a bomb as a center tile
+    EdgeGradient { strength: f32 },
+    /// Explicit tile weights, as rows from top to bottom. Missing weights are `1` and tiles
+    /// weighing `0` or less only get bombs if there is no room elsewhere
+    Grid(Vec<Vec<f32>>),
+}
+
+impl BombDensity {
+    /// Weight of the `(x, y)` tile on a map of `(width, height)` tiles
+    pub fn weight(&self, (x, y): (usize, usize), (width, height): (usize, usize)) -> f32 {
+        // Distance to the center, from 0 at the center to 1 in the corners
+        let distance = || {
+            let center = Vec2::new(width as f32 - 1., height as f32 - 1.) / 2.;
+            let offset = Vec2::new(x as f32, y as f32) - center;
+            offset.length() / center.length().max(f32::EPSILON)
+        };
+        match self {
+            Self::Uniform => 1.,
+            Self::CenterGradient { strength } => 1. + strength * (1. - distance()),
+            Self::EdgeGradient { strength } => 1. + strength * distance(),
+            Self::Grid(rows) => height
+                .checked_sub(y + 1)
+                .and_then(|row| rows.get(row))
+                .and_then(|row| row.get(x))
+                .copied()
+                .unwrap_or(1.),
+        }
+    }
+}
+
+impl Default for BombDensity {
+    fn default() -> Self {
+        Self::Uniform
+    }
+}
+
+/// Camera shake options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct CameraShake {
+    /// Maximum camera offset in world units
+    pub amplitude: f32,
+    /// Shake duration in seconds
+    pub duration: f32,
+}
+
+/// Camera drag options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct CameraDrag {
+    /// Distance in logical pixels a press must move to pan the camera, instead of clicking
+    pub threshold: f32,
+}
+
+/// Mini-map options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct MiniMap {
+    /// Length of the longest mini-map side, in logical pixels
+    pub size: f32,
+    /// Distance to the top right corner of the window, in logical pixels
+    pub margin: f32,
+}
+
+impl Default for MiniMap {
+    fn default() -> Self {
+        Self { size: 160., margin: 10. }
+    }
+}
+
+/// Progress bar options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct ProgressBar {
+    /// Bar height, in logical pixels
+    pub height: f32,
+    /// Distance to the top and side edges of the window, in logical pixels
+    pub margin: f32,
+}
+
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self { height: 8., margin: 4. }
+    }
+}
+
+/// Board completion celebration options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Celebration {
+    /// Number of confetti particles falling over the board
+    pub confetti_count: usize,
+    /// Confetti lifetime in seconds
+    pub duration: f32,
+    /// Does a color wave cross the tiles
+    pub color_wave: bool,
+}
+
+/// Arcade ruleset time penalties, in seconds
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct TimePenalties {
+    /// Chording a number whose flags don't match its count
+    pub wrong_chord: f32,
+    /// Clicking a flagged tile
+    pub flagged_click: f32,
+}
+
+/// Hard mode moving one random covered mine to another covered tile on a schedule, the visible
+/// numbers being updated. Mines never move while no schedule is set
+#[derive(Debug, Copy, Clone, Default, Serialize, Deserialize)]
+pub struct MovingMines {
+    /// Seconds of play between two moves
+    pub interval: Option<f32>,
+    /// Uncovered tiles between two moves
+    pub uncovers: Option<u32>,
+}
+
+/// Highest bomb armor, so the neighbor count of eight armored bombs fits in an `i8`
+pub const MAX_ARMOR: u8 = 15;
+
+/// Game rules variants
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BoardVariant {
+    /// Regular Minesweeper rules
+    Classic,
+    /// Some bombs are armored and must be flagged several times. Neighbor counts add up the
+    /// armor of adjacent bombs, so a tile next to a 3 armor bomb shows at least 3
+    Armored {
+        /// Number of armored bombs, out of the board bomb count
+        armored_count: u16,
+        /// Maximum armor of a bomb, from 2 to `MAX_ARMOR`
+        max_armor: u8,
+    },
+    /// Some bombs are anti-mines, subtracting one from neighbor counts which may then be
+    /// negative. Chording is disabled as flags can't tell both kinds apart
+    AntiMine {
+        /// Number of anti-mines, out of the board bomb count
+        anti_mine_count: u16,
+    },
+    /// Tiles belong to colored regions, and numbers count their adjacent mines of each region
+    /// separately, in the region colors
+    ColorRegions {
+        /// Number of regions, colored with `BoardAssets::region_colors`
+        regions: u8,
+    },
+}
+
+impl BoardVariant {
+    /// Maximum number of flags a single tile can hold
+    pub const fn max_flags(&self) -> u8 {
+        match self {
+            Self::Classic | Self::AntiMine { .. } | Self::ColorRegions { .. } => 1,
+            // Armor is generated from 2 to `MAX_ARMOR`
+            Self::Armored { max_armor, .. } if *max_armor < 2 => 2,
+            Self::Armored { max_armor, .. } if *max_armor > MAX_ARMOR => MAX_ARMOR,
+            Self::Armored { max_armor, .. } => *max_armor,
+        }
+    }
+
+    /// Can numbered tiles be chorded
+    pub const fn allows_chording(&self) -> bool {
+        !matches!(self, Self::AntiMine { .. })
+    }
+}
+
+/// How far the uncover cascade spreads from an empty tile
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Cascade {
+    /// The connected empty tiles are uncovered along with their numbered border
+    Classic,
+    /// Only the connected empty tiles are uncovered, numbered tiles staying covered
+    EmptyOnly,
+}
+
+impl Default for Cascade {
+    fn default() -> Self {
+        Self::Classic
+    }
+}
+
+/// Endless mode progression curve, applied to the first level options
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct Progression {
+    /// Columns and rows added to the map each level
+    pub size_step: (u16, u16),
+    /// Maximum map size
+    pub max_size: (u16, u16),
+    /// Bomb density (bombs per tile) added each level
+    pub density_step: f32,
+    /// Maximum bomb density
+    pub max_density: f32,
+}
+
+impl Progression {
+    /// Board options of `level`, the first level using the `base` options
+    pub fn options_for_level(&self, base: &BoardOptions, level: u32) -> BoardOptions {
+        let steps = level.saturating_sub(1);
+        let grow = |size: u16, step: u16, max: u16| {
+            (size as u32 + step as u32 * steps).min(max.max(size) as u32) as u16
+        };
+        let map_size = (
+            grow(base.map_size.0, self.size_step.0, self.max_size.0),
+            grow(base.map_size.1, self.size_step.1, self.max_size.1),
+        );
+        let base_tiles = base.map_size.0 as f32 * base.map_size.1 as f32;
+        let base_density = base.layout.bomb_count() as f32 / base_tiles.max(1.);
+        let density = (base_density + self.density_step * steps as f32)
+            .min(self.max_density.max(base_density));
+        let tiles = map_size.0 as f32 * map_size.1 as f32;
+        // At least one bomb and one safe tile
+        let bomb_count = (density * tiles).round().clamp(1., (tiles - 1.).min(u16::MAX as f32));
+        BoardOptions {
+            map_size,
+            layout: BoardLayout::Random { count: bomb_count as u16 },
+            seed: base.seed.map(|s| s.wrapping_add(steps as u64)),
+            ..base.clone()
+        }
+    }
+}
+
+/// Bomb placement options
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum BoardLayout {
+    /// Randomly placed bombs, according to the board seed
+    Random { count: u16 },
+    /// Bombs at exact positions, out of bounds and duplicate positions being ignored
+    Explicit(Vec<Coordinates>),
+}
+
+impl BoardLayout {
+    /// Number of bombs of the layout. Duplicate explicit positions are counted once, but out of
+    /// bounds and hole positions are ignored only when the map is generated, so for explicit
+    /// layouts this is an upper bound of `TileMap::bomb_count`
+    pub fn bomb_count(&self) -> u16 {
+        match self {
+            Self::Random { count } => *count,
+            Self::Explicit(positions) => positions.iter().collect::<HashSet<_>>().len() as u16,
+        }
+    }
+}
+
+/// Board shape, as rows of characters from top to bottom: `#` is a tile and any other
+/// character a hole. Missing rows and columns are holes
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BoardMask(pub Vec<String>);
+
+impl BoardMask {
+    /// Is `coords` a tile of a board with `height` rows
+    pub fn contains(&self, coords: Coordinates, height: u16) -> bool {
+        let row = match (height as usize).checked_sub(coords.y as usize + 1) {
+            None => return false,
+            Some(r) => r,
+        };
+        self.0
+            .get(row)
+            .and_then(|line| line.chars().nth(coords.x as usize))
+            .map_or(false, |c| c == '#')
+    }
 }
 
 /// Board generation options. Must be used as a resource
-// We use serde to allow saving option presets and loading them at runtime
+// We use serde to allow saving option presets and loading them at runtime, missing fields of
+// older presets taking their default value
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct BoardOptions {
     /// Tile map size
     pub map_size: (u16, u16),
-    /// bomb count
-    pub bomb_count: u16,
+    /// Bomb placement
+    pub layout: BoardLayout,
+    /// Board shape, rectangular if not set
+    pub mask: Option<BoardMask>,
+    /// Random bomb layout symmetry
+    pub symmetry: Symmetry,
+    /// Random bomb layout density
+    pub density: BombDensity,
     /// Board world position
     pub position: BoardPosition,
+    /// Rotation and mirroring of the displayed board, see `BoardGeometry::rotate_view`
+    pub view: BoardView,
+    /// Window space reserved for HUD elements, the board being fitted and positioned in the
+    /// remaining space. Custom positions ignore them
+    pub margins: Margins,
     /// Tile world size
     pub tile_size: TileSize,
+    /// Tile size multiplier, applied to fixed sizes and to the adaptive size constraints
+    pub ui_scale: f32,
     /// Padding between tiles
     pub tile_padding: f32,
     /// Does the board generate a safe place to start
     pub safe_start: bool,
+    /// Minimum number of tiles uncovered by the safe start, random layouts being generated
+    /// again until the opening is big enough. Only used with `safe_start`
+    pub min_opening: u16,
+    /// Bomb placement seed, a random one is used if not set
+    pub seed: Option<u64>,
+    /// Does the board get hidden while paused, to prevent scouting
+    pub hide_on_pause: bool,
+    /// Does the plugin display a win or lose screen over the board when the game is over,
+    /// pressing `R` starting a new game
+    pub builtin_end_screens: bool,
+    /// Does the camera shake when a bomb explodes
+    pub camera_shake: Option<CameraShake>,
+    /// Does dragging with the uncover button, or a finger, pan the board camera
+    pub camera_drag: Option<CameraDrag>,
+    /// Minimum delay in seconds between two clicks or taps of the same action on a tile, faster
+    /// repeats like accidental double-clicks being dropped
+    pub input_debounce: Option<f32>,
+    /// Does completing the board spawn confetti and a tile color wave, until any input
+    pub celebration: Option<Celebration>,
+    /// Game rules variant
+    pub variant: BoardVariant,
+    /// Number of bombs that can be hit before losing, `1` being the classic rules
+    pub lives: u8,
+    /// Time in seconds to clear the board before losing, unlimited if not set
+    pub time_limit: Option<f32>,
+    /// Arcade ruleset adding time penalties for misclicks, reported by `BoardCompletedEvent`
+    pub time_penalties: Option<TimePenalties>,
+    /// Hard mode moving covered mines around while playing
+    pub moving_mines: Option<MovingMines>,
+    /// Shares of mega mines and decoys among the bombs
+    pub mine_kinds: MineKinds,
+    /// Do numbers show their orthogonal and diagonal mine counts separately, one above the
+    /// other. Color region boards keep their region counts
+    pub split_counts: bool,
+    /// Does completing the board generate a bigger and denser one, and how
+    pub endless: Option<Progression>,
+    /// Daily challenge of the board, see `DailyChallenge::board_options`
+    pub daily: Option<DailyChallenge>,
+    /// Spawns the tile entities by square chunks of this size around the camera view only,
+    /// for huge boards. Every tile is spawned if not set
+    pub chunk_size: Option<u16>,
+    /// Fog-of-war sight radius: once a tile is uncovered, only the covered tiles within this
+    /// distance of an uncovered tile can be uncovered or marked, the others being darkened
+    pub fog_radius: Option<u16>,
+    /// Power-up charges and grants, power-ups being disabled if not set
+    pub power_ups: Option<PowerUpOptions>,
+    /// Does the uncover cascade spread ring by ring over several frames, instead of revealing
+    /// the whole opening at once
+    pub animated_cascade: bool,
+    /// Does hovering a numbered tile for a second show its adjacent mine and flag counts
+    pub tooltips: bool,
+    /// Assist flagging the tiles proven to be mines after each uncover, classic boards only.
+    /// Auto flags are tinted with `BoardAssets::auto_flag_material`
+    pub auto_flag: bool,
+    /// Assist uncovering the remaining neighbors of numbers once their flags match their count.
+    /// Like chords, wrong flags make it uncover mines. It may be toggled at runtime
+    pub auto_open: bool,
+    /// Practice mode rejecting flags on safe tiles, with a shake of the tile
+    pub mistake_proof_flags: bool,
+    /// Uncover cascade spread around empty tiles
+    pub cascade: Cascade,
+    /// Does completing the board flag the remaining covered tiles, before the completion event
+    pub flag_on_win: bool,
+    /// Do numbers show their count minus their adjacent flags, grayed out at zero. It may be
+    /// toggled at runtime
+    pub remaining_numbers: bool,
+    /// Minimum tile count of the boards whose openings are computed on the async compute pool,
+    /// over the following frames, instead of blocking the frame on large flood fills
+    pub async_cascade: Option<u32>,
+    /// Minimum tile count of the boards generated in a background task, on start, restart or
+    /// retry. The current board stays displayed with a loading indicator until the new one is
+    /// ready
+    pub background_generation: Option<u32>,
+    /// Maximum number of tiles spawned per frame, spreading the spawning of large boards over
+    /// several frames. `BoardReadyEvent` is sent once every tile is spawned. Every tile is
+    /// spawned at once if not set
+    pub spawn_budget: Option<u32>,
+    /// Are invalid options clamped to the closest valid board, see `BoardOptions::clamped`,
+    /// instead of failing the generation with a `BoardGenerationFailedEvent`
+    pub lenient: bool,
+    /// Are the tiles, covers, bombs and bomb counters drawn from a single texture atlas, so
+    /// large boards render in a few draw calls. Counters then use a built-in pixel font
+    pub texture_atlas: bool,
+    /// Does a mini-map of the uncovered tiles and flags show in a corner while the board
+    /// overflows the window, with `camera_drag` enabled. Clicking it moves the camera there
+    pub mini_map: Option<MiniMap>,
+    /// Does a bar at the top of the window fill up as the safe tiles are uncovered
+    pub progress_bar: Option<ProgressBar>,
+    /// Ice, locked, key and teleport tiles, usually set by levels
+    pub tile_modifiers: TileModifiers,
+    /// Local versus race: a rival board with the same layout is played beside this one with the
+    /// keyboard cursor or a gamepad, the mouse playing this one. See `VersusRace`
+    pub versus: bool,
+}
+
+impl BoardOptions {
+    /// Difficulty matching the map size and bomb count
+    pub fn difficulty(&self) -> Difficulty {
+        Difficulty::from_params(self.map_size, self.layout.bomb_count())
+    }
 }
 
 impl Default for TileSize {
@@ -54,15 +516,118 @@ impl Default for BoardPosition {
     }
 }
 
+impl Default for Progression {
+    fn default() -> Self {
+        Self { size_step: (2, 2), max_size: (30, 24), density_step: 0.01, max_density: 0.25 }
+    }
+}
+
+impl Default for BoardVariant {
+    fn default() -> Self {
+        Self::Classic
+    }
+}
+
+impl Default for BoardLayout {
+    fn default() -> Self {
+        Self::Random { count: 30 }
+    }
+}
+
 impl Default for BoardOptions {
     fn default() -> Self {
         Self {
             map_size: (15, 15),
-            bomb_count: 30,
+            layout: Default::default(),
+            mask: None,
+            symmetry: Default::default(),
+            density: Default::default(),
             position: Default::default(),
+            view: Default::default(),
+            margins: Default::default(),
             tile_size: Default::default(),
+            ui_scale: 1.,
             tile_padding: 0.,
             safe_start: false,
+            min_opening: 0,
+            seed: None,
+            hide_on_pause: false,
+            builtin_end_screens: false,
+            camera_shake: None,
+            camera_drag: None,
+            input_debounce: None,
+            celebration: None,
+            variant: Default::default(),
+            lives: 1,
+            time_limit: None,
+            time_penalties: None,
+            moving_mines: None,
+            mine_kinds: Default::default(),
+            split_counts: false,
+            endless: None,
+            daily: None,
+            chunk_size: None,
+            fog_radius: None,
+            power_ups: None,
+            animated_cascade: false,
+            tooltips: false,
+            auto_flag: false,
+            auto_open: false,
+            mistake_proof_flags: false,
+            cascade: Cascade::Classic,
+            flag_on_win: true,
+            remaining_numbers: false,
+            async_cascade: None,
+            background_generation: None,
+            spawn_budget: None,
+            lenient: false,
+            texture_atlas: false,
+            mini_map: None,
+            progress_bar: None,
+            tile_modifiers: Default::default(),
+            versus: false,
         }
     }
 }
+
+// Only the options regenerating the board when tuned are displayed by the inspector
+#[cfg(feature = "debug")]
+impl bevy_inspector_egui::Inspectable for BoardOptions {
+    type Attributes = ();
+
+    fn ui(
+        &mut self,
+        ui: &mut bevy_inspector_egui::egui::Ui,
+        _options: Self::Attributes,
+        context: &mut bevy_inspector_egui::Context,
+    ) -> bool {
+        use bevy_inspector_egui::{egui, options::NumberAttributes, Inspectable};
+
+        let side = NumberAttributes { min: Some(1), ..Default::default() };
+        let mut changed = false;
+        egui::Grid::new(context.id()).show(ui, |ui| {
+            ui.label("Width");
+            changed |= self.map_size.0.ui(ui, side.clone(), &mut context.with_id(0));
+            ui.end_row();
+            ui.label("Height");
+            changed |= self.map_size.1.ui(ui, side, &mut context.with_id(1));
+            ui.end_row();
+            ui.label("Bombs");
+            let bomb_count = self.layout.bomb_count();
+            match &mut self.layout {
+                BoardLayout::Random { count } => {
+                    changed |= count.ui(ui, Default::default(), &mut context.with_id(2));
+                }
+                BoardLayout::Explicit(_) => {
+                    ui.label(format!("{} (explicit)", bomb_count));
+                }
+            }
+            ui.end_row();
+            ui.label("Tile padding");
+            let padding = NumberAttributes { min: Some(0.), ..Default::default() };
+            changed |= self.tile_padding.ui(ui, padding, &mut context.with_id(3));
+            ui.end_row();
+        });
+        changed
+    }
+}
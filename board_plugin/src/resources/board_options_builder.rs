@@ -0,0 +1,118 @@
+use crate::resources::{
+    BoardLayout, BoardOptions, BoardOptionsError, BoardVariant, CameraShake, Celebration, TileSize,
+};
+
+/// Builds `BoardOptions` checked by `BoardOptions::validate`, starting from the defaults or from
+/// existing options. Fields without a setter keep their value
+///
+/// ```ignore
+/// let options = BoardOptionsBuilder::default().map_size(30, 16).bomb_count(99).build()?;
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct BoardOptionsBuilder {
+    options: BoardOptions,
+}
+
+impl From<BoardOptions> for BoardOptionsBuilder {
+    fn from(options: BoardOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl BoardOptionsBuilder {
+    /// Tile map size
+    pub fn map_size(mut self, width: u16, height: u16) -> Self {
+        self.options.map_size = (width, height);
+        self
+    }
+
+    /// Randomly placed bombs
+    pub fn bomb_count(mut self, count: u16) -> Self {
+        self.options.layout = BoardLayout::Random { count };
+        self
+    }
+
+    /// Bomb placement
+    pub fn layout(mut self, layout: BoardLayout) -> Self {
+        self.options.layout = layout;
+        self
+    }
+
+    /// Layout seed
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.options.seed = Some(seed);
+        self
+    }
+
+    /// Tile world size
+    pub fn tile_size(mut self, tile_size: TileSize) -> Self {
+        self.options.tile_size = tile_size;
+        self
+    }
+
+    /// Padding between tiles
+    pub fn tile_padding(mut self, padding: f32) -> Self {
+        self.options.tile_padding = padding;
+        self
+    }
+
+    /// Safe start opening
+    pub fn safe_start(mut self, safe_start: bool) -> Self {
+        self.options.safe_start = safe_start;
+        self
+    }
+
+    /// Rule variant
+    pub fn variant(mut self, variant: BoardVariant) -> Self {
+        self.options.variant = variant;
+        self
+    }
+
+    /// Number of bombs that can be hit before losing
+    pub fn lives(mut self, lives: u8) -> Self {
+        self.options.lives = lives;
+        self
+    }
+
+    /// Board hidden while paused
+    pub fn hide_on_pause(mut self, hide: bool) -> Self {
+        self.options.hide_on_pause = hide;
+        self
+    }
+
+    /// Built-in win and loss screens
+    pub fn builtin_end_screens(mut self, enabled: bool) -> Self {
+        self.options.builtin_end_screens = enabled;
+        self
+    }
+
+    /// Delay in seconds ignoring repeated input on a tile
+    pub fn input_debounce(mut self, delay: f32) -> Self {
+        self.options.input_debounce = Some(delay);
+        self
+    }
+
+    /// Camera shake on explosions
+    pub fn camera_shake(mut self, shake: CameraShake) -> Self {
+        self.options.camera_shake = Some(shake);
+        self
+    }
+
+    /// Win celebration
+    pub fn celebration(mut self, celebration: Celebration) -> Self {
+        self.options.celebration = Some(celebration);
+        self
+    }
+
+    /// Local versus race on two boards of the same layout
+    pub fn versus(mut self, versus: bool) -> Self {
+        self.options.versus = versus;
+        self
+    }
+
+    /// Validated options
+    pub fn build(self) -> Result<BoardOptions, BoardOptionsError> {
+        self.options.validate()?;
+        Ok(self.options)
+    }
+}
@@ -0,0 +1,49 @@
+use crate::components::Coordinates;
+use serde::{Deserialize, Serialize};
+
+/// Tile as seen by the player
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum VisibleTile {
+    /// Covered tile holding `flags` flags
+    Covered { flags: u8 },
+    /// Uncovered empty tile
+    Empty,
+    /// Uncovered bomb count, negative around anti-mines
+    Number(i8),
+    /// Uncovered bomb, ending the game
+    Exploded,
+    /// Bomb uncovered while lives remained
+    Detonated,
+    /// Outside of the board shape
+    Hole,
+}
+
+/// Visible state of a board, for external UIs mirroring it without querying entities
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub struct BoardSnapshot {
+    pub width: u16,
+    pub height: u16,
+    /// Row-major tiles, from the bottom left
+    pub tiles: Vec<VisibleTile>,
+}
+
+impl BoardSnapshot {
+    /// Visible tile at `coords`, `None` out of the board
+    pub fn get(&self, coords: Coordinates) -> Option<VisibleTile> {
+        if coords.x >= self.width || coords.y >= self.height {
+            return None;
+        }
+        self.tiles.get(coords.to_index(self.width)).copied()
+    }
+
+    /// Tiles of `self` differing from `previous`, every tile if the board sizes differ
+    pub fn diff(&self, previous: &Self) -> Vec<(Coordinates, VisibleTile)> {
+        let resized = (self.width, self.height) != (previous.width, previous.height);
+        self.tiles
+            .iter()
+            .enumerate()
+            .filter(|(i, tile)| resized || previous.tiles[*i] != **tile)
+            .map(|(i, tile)| (Coordinates::from_index(i, self.width), *tile))
+            .collect()
+    }
+}
@@ -0,0 +1,22 @@
+/// State of the current board, managed by the plugin. Must be used as a resource
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum BoardState {
+    /// The tiles are spawned over several frames, see `BoardOptions::spawn_budget`. Input and
+    /// timer wait for them
+    Loading,
+    /// The board accepts input
+    Playing,
+    /// Input, timer and uncovering are frozen
+    Paused,
+    /// All safe tiles were uncovered, or the rival board of a versus race exploded
+    Won,
+    /// A bomb exploded, or the rival board of a versus race was completed first
+    Lost,
+}
+
+impl BoardState {
+    /// Is the game over, won or lost
+    pub const fn is_over(&self) -> bool {
+        matches!(self, Self::Won | Self::Lost)
+    }
+}
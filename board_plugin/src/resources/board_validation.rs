@@ -0,0 +1,165 @@
+use crate::resources::{
+    BoardLayout, BoardOptions, BoardVariant, BoardView, TileMap, TileSize, MAX_ARMOR,
+};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Board options no board can be generated from
+#[derive(Debug, Clone, PartialEq)]
+pub enum BoardOptionsError {
+    /// The map size or shape leaves no tile
+    EmptyMap,
+    /// The random bombs leave no safe tile
+    TooManyBombs { count: u16, tiles: usize },
+    /// The tile size is not positive, or the adaptive minimum exceeds the maximum
+    InvalidTileSize { min: f32, max: f32 },
+    /// The padding is negative or leaves nothing of the smallest tiles
+    InvalidPadding { padding: f32, tile_size: f32 },
+    /// The armored bombs maximum armor exceeds `MAX_ARMOR`, overflowing the neighbor counts
+    InvalidArmor { max_armor: u8 },
+    /// Moving mines are set on a variant or with split counts whose numbers aren't updated
+    UnsupportedMovingMines,
+    /// A versus race is set on a board whose rules the rival board doesn't follow
+    UnsupportedVersus,
+}
+
+impl Display for BoardOptionsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyMap => write!(f, "the board has no tile"),
+            Self::TooManyBombs { count, tiles } => {
+                write!(f, "{} bombs leave no safe tile on a board of {} tiles", count, tiles)
+            }
+            Self::InvalidTileSize { min, max } => {
+                write!(f, "tile size must be positive, from {} to {}", min, max)
+            }
+            Self::InvalidPadding { padding, tile_size } => {
+                write!(
+                    f,
+                    "padding {} must be positive and below the tile size {}",
+                    padding, tile_size
+                )
+            }
+            Self::InvalidArmor { max_armor } => {
+                write!(f, "bomb armor {} exceeds the maximum of {}", max_armor, MAX_ARMOR)
+            }
+            Self::UnsupportedMovingMines => {
+                write!(f, "moving mines need the classic variant without split counts")
+            }
+            Self::UnsupportedVersus => {
+                write!(
+                    f,
+                    "versus races need a classic single life board without chunks, fog, moving \
+                     mines, endless levels, time limit, power-ups, tile modifiers or view"
+                )
+            }
+        }
+    }
+}
+
+impl Error for BoardOptionsError {}
+
+impl BoardOptions {
+    /// Checks that a board can be generated from the options
+    pub fn validate(&self) -> Result<(), BoardOptionsError> {
+        let tiles = self.tile_count();
+        if tiles == 0 {
+            return Err(BoardOptionsError::EmptyMap);
+        }
+        if let BoardLayout::Random { count } = self.layout {
+            if count as usize >= tiles {
+                return Err(BoardOptionsError::TooManyBombs { count, tiles });
+            }
+        }
+        let (min, max) = self.tile_size_range();
+        if min <= 0. || min > max {
+            return Err(BoardOptionsError::InvalidTileSize { min, max });
+        }
+        if self.tile_padding < 0. || self.tile_padding >= min {
+            return Err(BoardOptionsError::InvalidPadding {
+                padding: self.tile_padding,
+                tile_size: min,
+            });
+        }
+        if let BoardVariant::Armored { max_armor, .. } = self.variant {
+            if max_armor > MAX_ARMOR {
+                return Err(BoardOptionsError::InvalidArmor { max_armor });
+            }
+        }
+        if self.moving_mines.is_some() && !self.supports_moving_mines() {
+            return Err(BoardOptionsError::UnsupportedMovingMines);
+        }
+        if self.versus && !self.supports_versus() {
+            return Err(BoardOptionsError::UnsupportedVersus);
+        }
+        Ok(())
+    }
+
+    /// Closest valid options, used in lenient mode: empty maps get a tile and a rectangular
+    /// shape, random bombs leave a safe tile, invalid tile sizes are reset, paddings and armor
+    /// clamped and unsupported moving mines and versus races dropped
+    pub fn clamped(&self) -> Self {
+        let mut options = self.clone();
+        options.map_size = (options.map_size.0.max(1), options.map_size.1.max(1));
+        if options.tile_count() == 0 {
+            options.mask = None;
+        }
+        let tiles = options.tile_count();
+        if let BoardLayout::Random { count } = &mut options.layout {
+            *count = (*count).min(tiles.saturating_sub(1).min(u16::MAX as usize) as u16);
+        }
+        let (min, max) = options.tile_size_range();
+        if min <= 0. || min > max {
+            options.tile_size = TileSize::default();
+        }
+        let (min, _) = options.tile_size_range();
+        // Half the smallest tile stays visible
+        options.tile_padding = options.tile_padding.clamp(0., min / 2.);
+        if let BoardVariant::Armored { max_armor, .. } = &mut options.variant {
+            *max_armor = (*max_armor).min(MAX_ARMOR);
+        }
+        if !options.supports_moving_mines() {
+            options.moving_mines = None;
+        }
+        if !options.supports_versus() {
+            options.versus = false;
+        }
+        options
+    }
+
+    /// Can mines move, `TileMap::move_bomb` only updating plain neighbor counts
+    fn supports_moving_mines(&self) -> bool {
+        matches!(self.variant, BoardVariant::Classic) && !self.split_counts
+    }
+
+    /// Can the rival board of a versus race follow the rules, it only plays plain boards
+    fn supports_versus(&self) -> bool {
+        matches!(self.variant, BoardVariant::Classic)
+            && self.lives <= 1
+            && self.chunk_size.is_none()
+            && self.fog_radius.is_none()
+            && self.moving_mines.is_none()
+            && self.endless.is_none()
+            && self.time_limit.is_none()
+            && self.power_ups.is_none()
+            && self.tile_modifiers.is_empty()
+            && self.view == BoardView::default()
+    }
+
+    /// Smallest and biggest tile sizes
+    fn tile_size_range(&self) -> (f32, f32) {
+        match self.tile_size {
+            TileSize::Fixed(size) => (size, size),
+            TileSize::Adaptive { min, max } => (min, max),
+        }
+    }
+
+    /// Number of tiles inside the board shape
+    fn tile_count(&self) -> usize {
+        let mut tile_map = TileMap::empty(self.map_size.0, self.map_size.1);
+        if let Some(mask) = &self.mask {
+            tile_map.set_mask(mask);
+        }
+        tile_map.tile_count()
+    }
+}
@@ -0,0 +1,30 @@
+use bevy::render::camera::{Camera, CameraPlugin, RenderTarget};
+use bevy::window::WindowId;
+
+/// Window displaying the board and receiving its input. Must be used as a resource, insert it
+/// before the board is created to use a secondary window
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BoardWindow {
+    pub window: WindowId,
+    /// Name of the camera rendering the board in the window. Secondary windows need their own
+    /// camera name, as a single camera is active per name
+    pub camera: String,
+}
+
+impl BoardWindow {
+    pub fn new(window: WindowId, camera: impl Into<String>) -> Self {
+        Self { window, camera: camera.into() }
+    }
+
+    /// Is `camera` the one rendering the board
+    pub fn is_board_camera(&self, camera: &Camera) -> bool {
+        camera.name.as_deref() == Some(self.camera.as_str())
+            && matches!(camera.target, RenderTarget::Window(id) if id == self.window)
+    }
+}
+
+impl Default for BoardWindow {
+    fn default() -> Self {
+        Self::new(WindowId::primary(), CameraPlugin::CAMERA_2D)
+    }
+}
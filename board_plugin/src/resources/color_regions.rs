@@ -0,0 +1,67 @@
+use crate::components::{Adjacency, Coordinates};
+use crate::resources::TileMap;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use std::collections::VecDeque;
+
+/// Colored regions of a `ColorRegions` variant board, grown from random tiles so each region is
+/// connected
+#[derive(Debug, Clone)]
+pub struct ColorRegions {
+    width: u16,
+    /// Region of each tile, in row-major order
+    regions: Vec<u8>,
+    count: u8,
+}
+
+impl ColorRegions {
+    /// Splits the `tile_map` into `count` regions, from its seed so a retried board keeps them
+    pub fn generate(tile_map: &TileMap, count: u8) -> Self {
+        let (width, height) = (tile_map.width(), tile_map.height());
+        let len = width as usize * height as usize;
+        let count = count.clamp(1, len.clamp(1, u8::MAX as usize) as u8);
+        let mut rng = StdRng::seed_from_u64(tile_map.seed());
+        let mut tiles: Vec<Coordinates> =
+            (0..height).flat_map(|y| (0..width).map(move |x| Coordinates { x, y })).collect();
+        tiles.shuffle(&mut rng);
+        let mut regions = vec![u8::MAX; len];
+        let mut queue = VecDeque::new();
+        for (region, coords) in tiles.into_iter().take(count as usize).enumerate() {
+            regions[coords.y as usize * width as usize + coords.x as usize] = region as u8;
+            queue.push_back(coords);
+        }
+        // Regions grow one ring at a time, so they end up about the same size
+        while let Some(coords) = queue.pop_front() {
+            let region = regions[coords.y as usize * width as usize + coords.x as usize];
+            for neighbor in coords.neighbors(Adjacency::Orthogonal) {
+                if neighbor.x >= width || neighbor.y >= height {
+                    continue;
+                }
+                let index = neighbor.y as usize * width as usize + neighbor.x as usize;
+                if regions[index] == u8::MAX {
+                    regions[index] = region;
+                    queue.push_back(neighbor);
+                }
+            }
+        }
+        Self { width, regions, count }
+    }
+
+    /// Number of regions
+    pub fn count(&self) -> u8 {
+        self.count
+    }
+
+    /// Region of the tile at `coords`
+    pub fn region_at(&self, coords: Coordinates) -> u8 {
+        self.regions[coords.y as usize * self.width as usize + coords.x as usize]
+    }
+
+    /// Bombs around the tile at `coords`, by region
+    pub fn bomb_counts(&self, tile_map: &TileMap, coords: Coordinates) -> Vec<u8> {
+        let mut counts = vec![0; self.count as usize];
+        for neighbor in tile_map.neighbors(coords).filter(|c| tile_map.is_bomb_at(*c)) {
+            counts[self.region_at(neighbor) as usize] += 1;
+        }
+        counts
+    }
+}
@@ -0,0 +1,37 @@
+/// Remaining time of a timed board. Must be used as a resource
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Countdown {
+    limit: f32,
+    remaining: f32,
+}
+
+impl Countdown {
+    /// Full countdown of `limit` seconds
+    pub fn new(limit: f32) -> Self {
+        Self { limit, remaining: limit }
+    }
+
+    // Getter for `limit`
+    pub fn limit(&self) -> f32 {
+        self.limit
+    }
+
+    // Getter for `remaining`
+    pub fn remaining(&self) -> f32 {
+        self.remaining
+    }
+
+    /// Did the countdown reach zero
+    pub fn is_expired(&self) -> bool {
+        self.remaining <= 0.
+    }
+
+    /// Advances the countdown by `delta` seconds, returning `true` if it just expired
+    pub(crate) fn tick(&mut self, delta: f32) -> bool {
+        if self.is_expired() {
+            return false;
+        }
+        self.remaining = (self.remaining - delta).max(0.);
+        self.is_expired()
+    }
+}
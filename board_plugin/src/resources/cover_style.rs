@@ -0,0 +1,29 @@
+use bevy::prelude::Color;
+
+/// Tile cover effects of the custom cover material, drawn with the `cover_material` feature
+/// instead of the flat color covers, see `BoardAssets::cover_style`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct CoverStyle {
+    /// Width of the beveled border, as a fraction of the cover size. Zero draws flat covers
+    pub bevel: f32,
+    /// Bevel shading strength, negative values engraving the cover instead of embossing it
+    pub emboss: f32,
+    /// Glow color of the hovered cover
+    pub glow_color: Color,
+    /// Duration in seconds for the glow to fade in or out
+    pub glow_fade: f32,
+    /// Duration in seconds of the dissolve effect of uncovered tiles. Zero removes covers at once
+    pub dissolve_duration: f32,
+}
+
+impl Default for CoverStyle {
+    fn default() -> Self {
+        Self {
+            bevel: 0.12,
+            emboss: 0.35,
+            glow_color: Color::rgb(1., 0.9, 0.5),
+            glow_fade: 0.15,
+            dissolve_duration: 0.3,
+        }
+    }
+}
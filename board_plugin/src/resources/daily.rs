@@ -0,0 +1,135 @@
+use crate::resources::{BoardLayout, BoardOptions, Difficulty};
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Display, Formatter};
+
+/// Daily challenge: every player gets the same board on the same UTC day
+#[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct DailyChallenge {
+    /// Days since the Unix epoch
+    day: i64,
+}
+
+impl DailyChallenge {
+    /// Challenge of the current UTC day
+    pub fn today() -> Self {
+        Self { day: (now_millis() / 86_400_000.).floor() as i64 }
+    }
+
+    /// Challenge of a given UTC date
+    pub fn from_date(year: i32, month: u32, day: u32) -> Self {
+        // Inverse of the civil date conversion, see `date`
+        let year = year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = month as i64;
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        Self { day: era * 146_097 + day_of_era - 719_468 }
+    }
+
+    pub fn yesterday(&self) -> Self {
+        Self { day: self.day - 1 }
+    }
+
+    pub fn tomorrow(&self) -> Self {
+        Self { day: self.day + 1 }
+    }
+
+    /// UTC date of the challenge, as `(year, month, day)`
+    pub fn date(&self) -> (i32, u32, u32) {
+        // Civil from days algorithm, by Howard Hinnant
+        let z = self.day + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z - era * 146_097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = (year_of_era + era * 400 + if month <= 2 { 1 } else { 0 }) as i32;
+        (year, month, day)
+    }
+
+    /// Board seed of the challenge
+    pub fn seed(&self) -> u64 {
+        // SplitMix64 finalizer, so consecutive days get unrelated boards
+        let mut z = (self.day as u64).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Difficulty of the challenge, harder towards the weekend
+    pub fn difficulty(&self) -> Difficulty {
+        // The Unix epoch was a thursday
+        match (self.day + 3).rem_euclid(7) {
+            0..=2 => Difficulty::Beginner,
+            3 | 4 => Difficulty::Intermediate,
+            _ => Difficulty::Expert,
+        }
+    }
+
+    /// Board options of the challenge, the display options being taken from `base`
+    pub fn board_options(&self, base: &BoardOptions) -> BoardOptions {
+        let difficulty = self.difficulty();
+        BoardOptions {
+            map_size: difficulty.map_size(),
+            layout: BoardLayout::Random { count: difficulty.bomb_count() },
+            mask: None,
+            seed: Some(self.seed()),
+            daily: Some(*self),
+            endless: None,
+            ..base.clone()
+        }
+    }
+}
+
+impl Display for DailyChallenge {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let (year, month, day) = self.date();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn now_millis() -> f64 {
+    use std::time::{SystemTime, UNIX_EPOCH};
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as f64).unwrap_or_default()
+}
+
+// The standard clock is not available in browsers
+#[cfg(target_arch = "wasm32")]
+fn now_millis() -> f64 {
+    js_sys::Date::now()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_date_always_gets_the_same_board() {
+        let (challenge, again) =
+            (DailyChallenge::from_date(2024, 2, 29), DailyChallenge::from_date(2024, 2, 29));
+        assert_eq!(challenge, again);
+        assert_eq!(challenge.seed(), again.seed());
+        let base = BoardOptions::default();
+        let (options, other) = (challenge.board_options(&base), again.board_options(&base));
+        assert_eq!(options.seed, Some(challenge.seed()));
+        assert_eq!(options.seed, other.seed);
+        assert_eq!(options.map_size, other.map_size);
+        assert_eq!(options.layout, other.layout);
+        assert_eq!(options.daily, Some(challenge));
+    }
+
+    #[test]
+    fn days_get_their_own_board() {
+        let challenge = DailyChallenge::from_date(2024, 2, 29);
+        assert_eq!(challenge.date(), (2024, 2, 29));
+        assert_eq!(challenge.to_string(), "2024-02-29");
+        assert_eq!(challenge.tomorrow(), DailyChallenge::from_date(2024, 3, 1));
+        assert_ne!(challenge.seed(), challenge.tomorrow().seed());
+        assert_eq!(DailyChallenge::from_date(1970, 1, 1).date(), (1970, 1, 1));
+    }
+}
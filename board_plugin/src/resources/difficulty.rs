@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+/// Board difficulty, used to classify played games
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum Difficulty {
+    /// 9x9 board with 10 bombs
+    Beginner,
+    /// 16x16 board with 40 bombs
+    Intermediate,
+    /// 30x16 board with 99 bombs
+    Expert,
+    /// Any other board
+    Custom { width: u16, height: u16, bomb_count: u16 },
+}
+
+impl Difficulty {
+    /// Retrieves the difficulty matching the given board parameters
+    pub const fn from_params((width, height): (u16, u16), bomb_count: u16) -> Self {
+        match (width, height, bomb_count) {
+            (9, 9, 10) => Self::Beginner,
+            (16, 16, 40) => Self::Intermediate,
+            (30, 16, 99) => Self::Expert,
+            _ => Self::Custom { width, height, bomb_count },
+        }
+    }
+
+    /// Board map size of the difficulty
+    pub const fn map_size(&self) -> (u16, u16) {
+        match self {
+            Self::Beginner => (9, 9),
+            Self::Intermediate => (16, 16),
+            Self::Expert => (30, 16),
+            Self::Custom { width, height, .. } => (*width, *height),
+        }
+    }
+
+    /// Bomb count of the difficulty
+    pub const fn bomb_count(&self) -> u16 {
+        match self {
+            Self::Beginner => 10,
+            Self::Intermediate => 40,
+            Self::Expert => 99,
+            Self::Custom { bomb_count, .. } => *bomb_count,
+        }
+    }
+}
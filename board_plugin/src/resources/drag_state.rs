@@ -0,0 +1,14 @@
+use bevy::math::Vec2;
+
+/// Camera drag of the current press, see `BoardOptions::camera_drag`. Must be used as a
+/// resource
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+pub struct DragState {
+    /// Window position where the press started
+    pub origin: Option<Vec2>,
+    /// Window position of the press last frame
+    pub last: Vec2,
+    /// Did the press leave the dead zone, panning the camera instead of clicking. It stays set
+    /// after the release, until the next press
+    pub dragging: bool,
+}
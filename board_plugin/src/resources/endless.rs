@@ -0,0 +1,28 @@
+use crate::resources::BoardOptions;
+
+/// Endless mode run state, carried over from one level to the next. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct EndlessProgress {
+    /// Current level, starting at 1
+    pub level: u32,
+    /// Cumulative score, each level adding its 3BV times the level number and its
+    /// decoy bonus
+    pub score: u64,
+    /// Options of the first level, the progression being applied to them
+    pub(crate) base: BoardOptions,
+}
+
+impl EndlessProgress {
+    /// New run, starting at the first level
+    pub fn new(base: BoardOptions) -> Self {
+        Self { level: 1, score: 0, base }
+    }
+
+    /// Board options of the current level
+    pub fn level_options(&self) -> BoardOptions {
+        match self.base.endless {
+            None => self.base.clone(),
+            Some(progression) => progression.options_for_level(&self.base, self.level),
+        }
+    }
+}
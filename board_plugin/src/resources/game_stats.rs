@@ -0,0 +1,141 @@
+use crate::resources::{DailyChallenge, Difficulty};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Summary of a single finished game
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GameRecord {
+    /// Difficulty of the played board
+    pub difficulty: Difficulty,
+    /// Was the board completed
+    pub won: bool,
+    /// Play time in seconds
+    pub time: f32,
+    /// Number of safe tiles uncovered
+    pub tiles_cleared: u32,
+    /// Number of uncover clicks
+    pub clicks: u32,
+    /// 3BV of the board
+    pub bbbv: u32,
+    /// Daily challenge of the board, if any
+    #[serde(default)]
+    pub daily: Option<DailyChallenge>,
+}
+
+impl GameRecord {
+    /// Ratio between the board 3BV and the clicks used, `1.0` being a perfect game
+    pub fn efficiency(&self) -> f32 {
+        if self.clicks == 0 {
+            return 0.;
+        }
+        self.bbbv as f32 / self.clicks as f32
+    }
+
+    /// 3BV solved per second, only meaningful for won games
+    pub fn bbbv_per_second(&self) -> f32 {
+        if self.time <= 0. {
+            return 0.;
+        }
+        self.bbbv as f32 / self.time
+    }
+}
+
+/// Game statistics, updated by the plugin on every game end. Must be used as a resource
+// We use serde to allow saving the statistics to disk between runs
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GameStats {
+    /// Number of completed boards
+    pub wins: u32,
+    /// Number of exploded boards
+    pub losses: u32,
+    /// Best completion time in seconds for each difficulty
+    pub best_times: HashMap<Difficulty, f32>,
+    /// Total number of safe tiles uncovered
+    pub tiles_cleared: u64,
+    /// Every finished game
+    pub games: Vec<GameRecord>,
+    /// Uncover clicks of the current game
+    #[serde(skip)]
+    current_clicks: u32,
+}
+
+impl GameStats {
+    /// Loads statistics from a RON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        ron::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Saves statistics to a RON file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Total number of finished games
+    pub fn games_played(&self) -> u32 {
+        self.wins + self.losses
+    }
+
+    /// Ratio of won games
+    pub fn win_rate(&self) -> f32 {
+        match self.games_played() {
+            0 => 0.,
+            played => self.wins as f32 / played as f32,
+        }
+    }
+
+    /// Best completion time for `difficulty`
+    pub fn best_time(&self, difficulty: &Difficulty) -> Option<f32> {
+        self.best_times.get(difficulty).copied()
+    }
+
+    /// Average efficiency of won games
+    pub fn average_efficiency(&self) -> Option<f32> {
+        let won: Vec<_> = self.games.iter().filter(|g| g.won).collect();
+        if won.is_empty() {
+            return None;
+        }
+        Some(won.iter().map(|g| g.efficiency()).sum::<f32>() / won.len() as f32)
+    }
+
+    /// Games played on the `challenge` day
+    pub fn daily_games(&self, challenge: DailyChallenge) -> impl Iterator<Item = &GameRecord> {
+        self.games.iter().filter(move |g| g.daily == Some(challenge))
+    }
+
+    /// Best completion time of the `challenge` day
+    pub fn daily_best_time(&self, challenge: DailyChallenge) -> Option<f32> {
+        self.daily_games(challenge).filter(|g| g.won).map(|g| g.time).reduce(f32::min)
+    }
+
+    /// Uncover clicks of the current game
+    pub fn current_clicks(&self) -> u32 {
+        self.current_clicks
+    }
+
+    pub(crate) fn start_game(&mut self) {
+        self.current_clicks = 0;
+    }
+
+    pub(crate) fn add_click(&mut self) {
+        self.current_clicks += 1;
+    }
+
+    /// Records a finished game
+    pub fn record(&mut self, record: GameRecord) {
+        if record.won {
+            self.wins += 1;
+            let best = self.best_times.entry(record.difficulty).or_insert(record.time);
+            *best = best.min(record.time);
+        } else {
+            self.losses += 1;
+        }
+        self.tiles_cleared += record.tiles_cleared as u64;
+        self.games.push(record);
+    }
+}
@@ -0,0 +1,49 @@
+/// Play time of the current board. Must be used as a resource
+#[derive(Debug, Clone, Default)]
+pub struct GameTimer {
+    elapsed: f32,
+    running: bool,
+    penalty: f32,
+}
+
+impl GameTimer {
+    /// Resets and starts the timer
+    pub fn start(&mut self) {
+        self.elapsed = 0.;
+        self.running = true;
+        self.penalty = 0.;
+    }
+
+    /// Stops the timer, keeping the elapsed time
+    pub fn stop(&mut self) {
+        self.running = false;
+    }
+
+    /// Advances the timer by `delta` seconds if it is running
+    pub fn tick(&mut self, delta: f32) {
+        if self.running {
+            self.elapsed += delta;
+        }
+    }
+
+    /// Adds a time penalty of `seconds`, counted in the elapsed time
+    pub fn add_penalty(&mut self, seconds: f32) {
+        self.elapsed += seconds;
+        self.penalty += seconds;
+    }
+
+    /// Elapsed time in seconds, penalties included
+    pub fn elapsed(&self) -> f32 {
+        self.elapsed
+    }
+
+    // Getter for `penalty`
+    pub fn penalty(&self) -> f32 {
+        self.penalty
+    }
+
+    // Getter for `running`
+    pub fn is_running(&self) -> bool {
+        self.running
+    }
+}
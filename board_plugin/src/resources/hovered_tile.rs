@@ -0,0 +1,5 @@
+use crate::components::Coordinates;
+
+/// Board tile currently under the cursor. Must be used as a resource
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct HoveredTile(pub Option<Coordinates>);
@@ -0,0 +1,52 @@
+use bevy::prelude::{KeyCode, MouseButton};
+use serde::{Deserialize, Serialize};
+
+/// Mouse and keyboard bindings of the board. Must be used as a resource
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct InputBindings {
+    /// Mouse button uncovering a tile on release
+    pub uncover_button: MouseButton,
+    /// Mouse button marking a tile
+    pub flag_button: MouseButton,
+    /// Mouse button chording a numbered tile on release
+    pub chord_button: MouseButton,
+    /// Does holding both the uncover and flag buttons chord
+    pub chord_with_both_buttons: bool,
+    /// Keyboard cursor movement keys
+    pub cursor_up: KeyCode,
+    pub cursor_down: KeyCode,
+    pub cursor_left: KeyCode,
+    pub cursor_right: KeyCode,
+    /// Keyboard cursor action keys
+    pub cursor_uncover: KeyCode,
+    pub cursor_flag: KeyCode,
+    pub cursor_chord: KeyCode,
+    /// Key starting a new game from the built-in end screens
+    pub restart: KeyCode,
+    /// Key replaying the same board from the built-in lose screen, flags included
+    pub retry: KeyCode,
+    /// Key toggling the board window fullscreen
+    pub fullscreen: KeyCode,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        Self {
+            uncover_button: MouseButton::Left,
+            flag_button: MouseButton::Right,
+            chord_button: MouseButton::Middle,
+            chord_with_both_buttons: true,
+            cursor_up: KeyCode::Up,
+            cursor_down: KeyCode::Down,
+            cursor_left: KeyCode::Left,
+            cursor_right: KeyCode::Right,
+            cursor_uncover: KeyCode::Space,
+            cursor_flag: KeyCode::F,
+            cursor_chord: KeyCode::D,
+            restart: KeyCode::R,
+            retry: KeyCode::T,
+            fullscreen: KeyCode::F11,
+        }
+    }
+}
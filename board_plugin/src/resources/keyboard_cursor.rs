@@ -0,0 +1,5 @@
+use crate::components::Coordinates;
+
+/// Keyboard cursor position, hidden until a cursor key is pressed. Must be used as a resource
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct KeyboardCursor(pub Option<Coordinates>);
@@ -0,0 +1,183 @@
+use crate::resources::{BoardOptions, DailyChallenge, Difficulty};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Maximum number of entries kept per leaderboard
+const MAX_ENTRIES: usize = 100;
+
+/// Identifies a leaderboard: boards are only comparable with the same difficulty and seed
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct LeaderboardKey {
+    pub difficulty: Difficulty,
+    /// Fixed board seed, `None` for random boards
+    pub seed: Option<u64>,
+    /// Daily challenges get a leaderboard per day
+    #[serde(default)]
+    pub daily: Option<DailyChallenge>,
+}
+
+impl LeaderboardKey {
+    /// Leaderboard key matching the given board options
+    pub fn from_options(options: &BoardOptions) -> Self {
+        Self { difficulty: options.difficulty(), seed: options.seed, daily: options.daily }
+    }
+
+    /// Leaderboard key of a daily challenge
+    pub fn daily(challenge: DailyChallenge) -> Self {
+        Self {
+            difficulty: challenge.difficulty(),
+            seed: Some(challenge.seed()),
+            daily: Some(challenge),
+        }
+    }
+}
+
+/// Leaderboard entry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScoreEntry {
+    /// Player name
+    pub name: String,
+    /// Completion time in seconds
+    pub time: f32,
+    /// 3BV of the completed board
+    pub bbbv: u32,
+    /// Uncover clicks used
+    pub clicks: u32,
+}
+
+/// Single leaderboard, sorted by completion time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboard {
+    entries: Vec<ScoreEntry>,
+}
+
+impl Leaderboard {
+    /// Inserts a score, returning its rank if it made the leaderboard
+    pub fn submit(&mut self, entry: ScoreEntry) -> Option<usize> {
+        let rank = self.entries.partition_point(|e| e.time <= entry.time);
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        self.entries.insert(rank, entry);
+        self.entries.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+
+    /// Retrieves the `n` best scores
+    pub fn top(&self, n: usize) -> &[ScoreEntry] {
+        &self.entries[..n.min(self.entries.len())]
+    }
+
+    /// Best score
+    pub fn best(&self) -> Option<&ScoreEntry> {
+        self.entries.first()
+    }
+}
+
+/// File-backed local leaderboards. Must be used as a resource
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Leaderboards {
+    boards: HashMap<LeaderboardKey, Leaderboard>,
+    /// Save file, leaderboards are kept in memory only if not set
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Leaderboards {
+    /// Default save file, in the platform data directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|dir| dir.join("minesweeper-tutorial").join("leaderboard.ron"))
+    }
+
+    /// Browsers have no file system, leaderboards are kept in memory
+    #[cfg(target_arch = "wasm32")]
+    pub fn default_path() -> Option<PathBuf> {
+        None
+    }
+
+    /// Loads the leaderboards from a RON file, starting empty if the file doesn't exist yet.
+    /// Later submissions are saved to the same file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut leaderboards: Self = match fs::read_to_string(path) {
+            Ok(content) => ron::from_str(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e),
+        };
+        leaderboards.path = Some(path.to_path_buf());
+        Ok(leaderboards)
+    }
+
+    /// Saves the leaderboards to their file, if any
+    pub fn save(&self) -> io::Result<()> {
+        let path = match &self.path {
+            None => return Ok(()),
+            Some(p) => p,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Retrieves the leaderboard for `key`
+    pub fn get(&self, key: &LeaderboardKey) -> Option<&Leaderboard> {
+        self.boards.get(key)
+    }
+
+    /// Inserts a score in the `key` leaderboard, returning its rank if it made the leaderboard
+    pub fn submit(&mut self, key: LeaderboardKey, entry: ScoreEntry) -> Option<usize> {
+        self.boards.entry(key).or_default().submit(entry)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, time: f32) -> ScoreEntry {
+        ScoreEntry { name: name.to_string(), time, bbbv: 10, clicks: 12 }
+    }
+
+    fn names(entries: &[ScoreEntry]) -> Vec<&str> {
+        entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    #[test]
+    fn top_is_sorted_by_time() {
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.submit(entry("slow", 30.)), Some(0));
+        assert_eq!(leaderboard.submit(entry("fast", 10.)), Some(0));
+        assert_eq!(leaderboard.submit(entry("middle", 20.)), Some(1));
+        assert_eq!(names(leaderboard.top(2)), ["fast", "middle"]);
+        assert_eq!(leaderboard.best().map(|e| e.name.as_str()), Some("fast"));
+    }
+
+    #[test]
+    fn ties_keep_the_earlier_score_first() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.submit(entry("first", 10.));
+        assert_eq!(leaderboard.submit(entry("second", 10.)), Some(1));
+        assert_eq!(names(leaderboard.top(2)), ["first", "second"]);
+    }
+
+    #[test]
+    fn top_is_capped_by_the_entry_count() {
+        let mut leaderboard = Leaderboard::default();
+        assert!(leaderboard.top(3).is_empty());
+        leaderboard.submit(entry("only", 10.));
+        assert_eq!(names(leaderboard.top(3)), ["only"]);
+        for i in 0..MAX_ENTRIES {
+            leaderboard.submit(entry("other", i as f32));
+        }
+        assert_eq!(leaderboard.top(MAX_ENTRIES + 1).len(), MAX_ENTRIES);
+        assert_eq!(leaderboard.submit(entry("last", 1000.)), None);
+    }
+}
@@ -0,0 +1,119 @@
+use crate::components::Coordinates;
+use crate::resources::{
+    BoardLayout, BoardMask, BoardOptions, BoardVariant, TextBoard, TileMapTextError, TileModifiers,
+};
+use bevy::asset::{AssetLoader, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+
+/// Bombs of a level
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum LevelBombs {
+    /// Randomly placed bombs
+    Count(u16),
+    /// Hand placed bombs, as `(x, y)` with `(0, 0)` being the bottom left tile
+    Positions(Vec<(u16, u16)>),
+}
+
+/// Rules modifiers of a level, the base options being kept when not set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LevelModifiers {
+    pub safe_start: Option<bool>,
+    pub seed: Option<u64>,
+    pub variant: Option<BoardVariant>,
+    pub lives: Option<u8>,
+    pub time_limit: Option<f32>,
+}
+
+/// Hand-crafted level, loaded from `.level.ron` asset files, or `.level.txt` text boards as
+/// written by `TileMap::to_text`
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "6b1f7c1e-3b0a-4d52-9a8e-2f4c7d15e0a3"]
+pub struct LevelDefinition {
+    /// Displayed level name
+    pub name: String,
+    /// Tile map size
+    pub map_size: (u16, u16),
+    pub bombs: LevelBombs,
+    /// Board shape, rectangular if not set
+    #[serde(default)]
+    pub mask: Option<Vec<String>>,
+    #[serde(default)]
+    pub modifiers: LevelModifiers,
+    /// Ice, locked, key and teleport tiles
+    #[serde(default)]
+    pub tile_modifiers: TileModifiers,
+}
+
+impl LevelDefinition {
+    /// Level of a text board, with the default modifiers
+    pub fn from_text(name: String, text: &str) -> Result<Self, TileMapTextError> {
+        let board = TextBoard::parse(text)?;
+        Ok(Self {
+            name,
+            map_size: (board.width, board.height),
+            bombs: LevelBombs::Positions(board.bombs.iter().map(|c| (c.x, c.y)).collect()),
+            mask: board.mask.map(|m| m.0),
+            modifiers: LevelModifiers::default(),
+            tile_modifiers: TileModifiers::default(),
+        })
+    }
+
+    /// Board options of the level, the layout and display options being taken from `base`.
+    /// Insert them as a resource before entering the board state to play the level
+    pub fn board_options(&self, base: &BoardOptions) -> BoardOptions {
+        let layout = match &self.bombs {
+            LevelBombs::Count(count) => BoardLayout::Random { count: *count },
+            LevelBombs::Positions(positions) => BoardLayout::Explicit(
+                positions.iter().map(|(x, y)| Coordinates { x: *x, y: *y }).collect(),
+            ),
+        };
+        let modifiers = &self.modifiers;
+        BoardOptions {
+            map_size: self.map_size,
+            layout,
+            mask: self.mask.clone().map(BoardMask),
+            safe_start: modifiers.safe_start.unwrap_or(base.safe_start),
+            seed: modifiers.seed.or(base.seed),
+            variant: modifiers.variant.unwrap_or(base.variant),
+            lives: modifiers.lives.unwrap_or(base.lives),
+            time_limit: modifiers.time_limit.or(base.time_limit),
+            tile_modifiers: self.tile_modifiers.clone(),
+            // Levels are hand-crafted, they don't grow
+            endless: None,
+            ..base.clone()
+        }
+    }
+}
+
+/// Asset loader for `.level.ron` level definitions and `.level.txt` text boards, named after
+/// their file
+#[derive(Debug, Default)]
+pub struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), anyhow::Error>> {
+        Box::pin(async move {
+            let path = load_context.path();
+            let level = match path.file_name().and_then(|n| n.to_str()) {
+                Some(name) if name.ends_with(".level.txt") => {
+                    let name = name.trim_end_matches(".level.txt").to_string();
+                    LevelDefinition::from_text(name, std::str::from_utf8(bytes)?)?
+                }
+                _ => ron::de::from_bytes(bytes)?,
+            };
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron", "level.txt"]
+    }
+}
@@ -0,0 +1,36 @@
+/// Remaining lives of the current board. Must be used as a resource
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub struct Lives {
+    remaining: u8,
+    max: u8,
+}
+
+impl Lives {
+    /// Full lives, a board always having at least one
+    pub fn new(max: u8) -> Self {
+        let max = max.max(1);
+        Self { remaining: max, max }
+    }
+
+    // Getter for `remaining`
+    pub fn remaining(&self) -> u8 {
+        self.remaining
+    }
+
+    // Getter for `max`
+    pub fn max(&self) -> u8 {
+        self.max
+    }
+
+    /// Loses a life, returning the remaining ones
+    pub(crate) fn lose(&mut self) -> u8 {
+        self.remaining = self.remaining.saturating_sub(1);
+        self.remaining
+    }
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self::new(1)
+    }
+}
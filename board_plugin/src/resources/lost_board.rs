@@ -0,0 +1,15 @@
+use crate::components::Coordinates;
+
+/// Board captured when the game is lost, replayed by `RetryBoardEvent`. Must be used as a
+/// resource
+#[derive(Debug, Clone)]
+pub struct LostBoard {
+    /// Layout seed of the board
+    pub seed: u64,
+    /// Flag counts of the tiles marked before the fatal click
+    pub flags: Vec<(Coordinates, u8)>,
+}
+
+/// Flags to place on the retried board once spawned
+#[derive(Debug, Clone)]
+pub(crate) struct RestoredFlags(pub Vec<(Coordinates, u8)>);
@@ -0,0 +1,60 @@
+use crate::components::Coordinates;
+use crate::resources::tile::Tile;
+use crate::resources::TileMap;
+use bevy::utils::HashMap;
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+/// Behavior of a mine, see `BoardOptions::mine_kinds`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum MineKind {
+    /// Regular mine
+    Standard,
+    /// Mine whose blast uncovers its safe neighbors when it costs a life
+    Mega,
+    /// Mine worth bonus points when flagged on completion
+    Decoy,
+}
+
+impl Default for MineKind {
+    fn default() -> Self {
+        Self::Standard
+    }
+}
+
+/// Shares of the plain bombs turned into special mines, the others staying standard
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MineKinds {
+    /// Share of mega mines, from 0 to 1
+    pub mega: f32,
+    /// Share of decoys, from 0 to 1
+    pub decoy: f32,
+    /// Points of each decoy flagged on completion, see `BoardCompletedEvent::bonus`
+    pub decoy_bonus: u32,
+}
+
+impl Default for MineKinds {
+    fn default() -> Self {
+        Self { mega: 0., decoy: 0., decoy_bonus: 10 }
+    }
+}
+
+impl MineKinds {
+    /// Picks the special mines among the plain bombs of `tile_map`. The map seed is used, so a
+    /// retried board keeps its mines
+    pub fn assign(&self, tile_map: &TileMap) -> HashMap<Coordinates, MineKind> {
+        let mut bombs: Vec<Coordinates> = tile_map
+            .bomb_coordinates()
+            .filter(|c| tile_map[c.y as usize][c.x as usize] == Tile::Bomb)
+            .collect();
+        bombs.shuffle(&mut StdRng::seed_from_u64(tile_map.seed()));
+        let count = |share: f32| (share.clamp(0., 1.) * bombs.len() as f32).round() as usize;
+        let mega = count(self.mega);
+        let decoy = count(self.decoy).min(bombs.len() - mega);
+        let kinds = std::iter::repeat(MineKind::Mega)
+            .take(mega)
+            .chain(std::iter::repeat(MineKind::Decoy).take(decoy));
+        bombs.into_iter().zip(kinds).collect()
+    }
+}
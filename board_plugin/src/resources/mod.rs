@@ -1,7 +1,90 @@
 pub(crate) mod tile;
 pub(crate) mod tile_map;
+pub use accessibility::*;
 pub(crate) use board::*;
+pub use board_assets::*;
+pub use board_code::*;
+pub use board_geometry::*;
+pub use board_loading::*;
 pub use board_options::*;
+pub use board_options_builder::*;
+pub use board_snapshot::*;
+pub use board_state::*;
+pub use board_validation::*;
+pub use board_window::*;
+pub use color_regions::*;
+pub use countdown::*;
+pub use cover_style::*;
+pub use daily::*;
+pub use difficulty::*;
+pub use drag_state::*;
+pub use endless::*;
+pub use game_stats::*;
+pub use game_timer::*;
+pub use hovered_tile::*;
+pub use input_bindings::*;
+pub use keyboard_cursor::*;
+pub use leaderboard::*;
+pub use level::*;
+pub use lives::*;
+pub use lost_board::*;
+pub use mine_kind::*;
+pub use night_mode::*;
+pub use number_palette::*;
+pub(crate) use pending_board::*;
+pub use power_ups::*;
+pub use replay::*;
+pub use reveal_sequence::*;
+pub use settings::*;
+pub use tile::Tile;
+pub use tile_atlas::*;
+pub use tile_chunks::*;
+pub use tile_map::{TileGrid, TileMap, TileRow};
+pub use tile_map_text::*;
+pub use tile_modifiers::*;
+pub use tutorial::*;
+pub use versus::*;
 
+mod accessibility;
+mod board;
+mod board_assets;
+mod board_code;
+mod board_generation;
+mod board_geometry;
+mod board_loading;
 mod board_options;
-mod board;
\ No newline at end of file
+mod board_options_builder;
+mod board_snapshot;
+mod board_state;
+mod board_validation;
+mod board_window;
+mod color_regions;
+mod countdown;
+mod cover_style;
+mod daily;
+mod difficulty;
+mod drag_state;
+mod endless;
+mod game_stats;
+mod game_timer;
+mod hovered_tile;
+mod input_bindings;
+mod keyboard_cursor;
+mod leaderboard;
+mod level;
+mod lives;
+mod lost_board;
+mod mine_kind;
+mod night_mode;
+mod number_palette;
+mod pending_board;
+mod power_ups;
+mod replay;
+mod reveal_sequence;
+mod settings;
+mod tile_atlas;
+mod tile_chunks;
+mod tile_map_text;
+mod tile_modifiers;
+mod tutorial;
+mod versus;
@@ -0,0 +1,17 @@
+use bevy::prelude::Color;
+
+/// Night mode of a theme: the board is dark, uncovered tiles lighting their surroundings. See
+/// `BoardAssets::night_mode`
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct NightMode {
+    /// Darkness color, its alpha being the opacity of the unlit tiles
+    pub darkness: Color,
+    /// Radius in tiles of the light around uncovered tiles, fading out towards its edge
+    pub light_radius: f32,
+}
+
+impl Default for NightMode {
+    fn default() -> Self {
+        Self { darkness: Color::rgba(0., 0., 0.05, 0.9), light_radius: 2.5 }
+    }
+}
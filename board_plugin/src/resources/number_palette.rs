@@ -0,0 +1,74 @@
+use bevy::prelude::Color;
+use serde::{Deserialize, Serialize};
+
+/// Bomb counter color palettes, see `BoardAssets::set_palette`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum NumberPalette {
+    /// Default colors
+    Classic,
+    /// Red-green colorblind friendly colors, also suiting protanopia
+    Deuteranopia,
+    /// Blue-yellow colorblind friendly colors
+    Tritanopia,
+    /// Saturated colors standing out on dark tiles
+    HighContrast,
+}
+
+impl NumberPalette {
+    /// Colors for the bomb neighbor counters, from 1 upwards
+    pub fn colors(&self) -> Vec<Color> {
+        match self {
+            Self::Classic => {
+                vec![Color::WHITE, Color::GREEN, Color::YELLOW, Color::ORANGE, Color::PURPLE]
+            }
+            // Okabe-Ito colors
+            Self::Deuteranopia => vec![
+                Color::rgb_u8(86, 180, 233),
+                Color::rgb_u8(230, 159, 0),
+                Color::rgb_u8(240, 228, 66),
+                Color::rgb_u8(213, 94, 0),
+                Color::rgb_u8(204, 121, 167),
+                Color::rgb_u8(0, 114, 178),
+                Color::WHITE,
+            ],
+            Self::Tritanopia => vec![
+                Color::WHITE,
+                Color::rgb_u8(0, 158, 115),
+                Color::rgb_u8(230, 40, 40),
+                Color::rgb_u8(255, 140, 200),
+                Color::rgb_u8(150, 30, 90),
+                Color::rgb_u8(120, 120, 120),
+            ],
+            Self::HighContrast => vec![
+                Color::WHITE,
+                Color::YELLOW,
+                Color::CYAN,
+                Color::FUCHSIA,
+                Color::LIME_GREEN,
+                Color::ORANGE_RED,
+            ],
+        }
+    }
+
+    /// Colors for the zero and negative bomb neighbor counters, from 0 downwards
+    pub fn negative_colors(&self) -> Vec<Color> {
+        match self {
+            Self::Classic => {
+                vec![Color::GRAY, Color::CYAN, Color::BLUE, Color::INDIGO, Color::MIDNIGHT_BLUE]
+            }
+            Self::Deuteranopia | Self::Tritanopia => vec![
+                Color::GRAY,
+                Color::rgb_u8(0, 114, 178),
+                Color::rgb_u8(86, 180, 233),
+                Color::rgb_u8(0, 158, 115),
+            ],
+            Self::HighContrast => vec![Color::SILVER, Color::AQUAMARINE, Color::TURQUOISE],
+        }
+    }
+}
+
+impl Default for NumberPalette {
+    fn default() -> Self {
+        Self::Classic
+    }
+}
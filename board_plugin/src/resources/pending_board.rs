@@ -0,0 +1,14 @@
+use crate::resources::{BoardOptions, RestoredFlags};
+use crate::TileMap;
+use bevy::tasks::Task;
+
+/// Board being generated in a background task, the current board if any staying displayed
+/// until it is ready. See `BoardOptions::background_generation`
+pub(crate) struct PendingBoard {
+    pub task: Task<(BoardOptions, TileMap)>,
+    /// Do the generated options replace the `BoardOptions` resource, retried boards keeping the
+    /// current ones
+    pub replace_options: bool,
+    /// Flags to place once the board is spawned, see `RetryBoardEvent::restore_flags`
+    pub restored_flags: Option<RestoredFlags>,
+}
@@ -0,0 +1,101 @@
+use bevy::core::Timer;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Power-up abilities, used with `UsePowerUpEvent`
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub enum PowerUp {
+    /// Uncovers a random covered safe tile
+    RevealSafeTile,
+    /// Flags a random unflagged bomb
+    DefuseMine,
+    /// Stops the game timer and countdown for a while
+    FreezeTimer,
+}
+
+impl PowerUp {
+    /// Every power-up, in grant order
+    pub const ALL: [Self; 3] = [Self::RevealSafeTile, Self::DefuseMine, Self::FreezeTimer];
+}
+
+/// Power-up options
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PowerUpOptions {
+    /// Power-ups available from the start of the board
+    pub starting: Vec<PowerUp>,
+    /// Delay in seconds between random power-up grants, none being granted if not set
+    pub grant_interval: Option<f32>,
+    /// Duration in seconds of the timer freeze
+    pub freeze_duration: f32,
+}
+
+impl Default for PowerUpOptions {
+    fn default() -> Self {
+        Self { starting: Vec::new(), grant_interval: Some(30.), freeze_duration: 10. }
+    }
+}
+
+/// Power-up charges of the current board. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct PowerUps {
+    charges: HashMap<PowerUp, u8>,
+    grant_timer: Option<Timer>,
+    freeze: Option<Timer>,
+    freeze_duration: f32,
+}
+
+impl PowerUps {
+    pub fn new(options: &PowerUpOptions) -> Self {
+        let mut power_ups = Self {
+            charges: HashMap::default(),
+            grant_timer: options.grant_interval.map(|i| Timer::from_seconds(i, true)),
+            freeze: None,
+            freeze_duration: options.freeze_duration,
+        };
+        for power_up in options.starting.iter() {
+            power_ups.grant(*power_up);
+        }
+        power_ups
+    }
+
+    /// Number of available `power_up` charges
+    pub fn charges(&self, power_up: PowerUp) -> u8 {
+        self.charges.get(&power_up).copied().unwrap_or_default()
+    }
+
+    /// Adds a `power_up` charge
+    pub fn grant(&mut self, power_up: PowerUp) {
+        let charges = self.charges.entry(power_up).or_default();
+        *charges = charges.saturating_add(1);
+    }
+
+    /// Are the game timer and countdown frozen
+    pub fn is_frozen(&self) -> bool {
+        self.freeze.is_some()
+    }
+
+    /// Removes a `power_up` charge, returning whether one was available
+    pub(crate) fn take(&mut self, power_up: PowerUp) -> bool {
+        match self.charges.get_mut(&power_up) {
+            Some(charges) if *charges > 0 => {
+                *charges -= 1;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Freezes the timers, restarting a running freeze
+    pub(crate) fn freeze(&mut self) {
+        self.freeze = Some(Timer::from_seconds(self.freeze_duration, false));
+    }
+
+    /// Advances the freeze and grant timers, returning the number of grants due
+    pub(crate) fn tick(&mut self, delta: Duration) -> u32 {
+        if self.freeze.as_mut().map_or(false, |t| t.tick(delta).finished()) {
+            self.freeze = None;
+        }
+        self.grant_timer.as_mut().map_or(0, |t| t.tick(delta).times_finished())
+    }
+}
@@ -0,0 +1,201 @@
+use crate::components::Coordinates;
+use crate::events::BoardGeneratedEvent;
+use crate::resources::tile::Tile;
+use crate::resources::tile_map::TileMap;
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Player action of a replay
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum ReplayAction {
+    Uncover(Coordinates),
+    Mark(Coordinates),
+    Chord(Coordinates),
+}
+
+/// Timed player action of a replay
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct ReplayEvent {
+    /// Play time in seconds
+    pub time: f32,
+    pub action: ReplayAction,
+}
+
+/// Recorded game, replayable on a board with the same seed
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Replay {
+    pub seed: u64,
+    pub map_size: (u16, u16),
+    pub bomb_count: u16,
+    /// Actions, sorted by time
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Replay {
+    /// Empty replay of a generated board
+    pub fn new(board: &BoardGeneratedEvent) -> Self {
+        Self {
+            seed: board.seed,
+            map_size: (board.width, board.height),
+            bomb_count: board.bomb_count,
+            events: Vec::new(),
+        }
+    }
+
+    /// Loads a replay from a RON file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let content = fs::read_to_string(path)?;
+        ron::from_str(&content).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    /// Saves the replay to a RON file
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let content = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+
+    /// Can the replay be played on `tile_map`
+    pub fn matches(&self, tile_map: &TileMap) -> bool {
+        self.seed == tile_map.seed()
+            && self.map_size == (tile_map.width(), tile_map.height())
+            && self.bomb_count == tile_map.bomb_count()
+    }
+}
+
+/// Replay of the current board, recorded by the plugin. Must be used as a resource
+#[derive(Debug, Clone, Default)]
+pub struct ReplayRecorder {
+    replay: Replay,
+}
+
+impl ReplayRecorder {
+    // Getter for `replay`
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    pub(crate) fn start(&mut self, board: &BoardGeneratedEvent) {
+        self.replay = Replay::new(board);
+    }
+
+    pub(crate) fn record(&mut self, time: f32, action: ReplayAction) {
+        self.replay.events.push(ReplayEvent { time, action });
+    }
+}
+
+/// Another player's replay, displayed as a translucent overlay racing the current board.
+/// Insert it as a resource before the board is generated; it never affects the real game
+#[derive(Debug, Clone)]
+pub struct Ghost {
+    replay: Replay,
+    next_event: usize,
+    uncovered: HashSet<Coordinates>,
+    marked: HashSet<Coordinates>,
+    exploded: bool,
+}
+
+impl Ghost {
+    pub fn new(replay: Replay) -> Self {
+        Self {
+            replay,
+            next_event: 0,
+            uncovered: HashSet::default(),
+            marked: HashSet::default(),
+            exploded: false,
+        }
+    }
+
+    // Getter for `replay`
+    pub fn replay(&self) -> &Replay {
+        &self.replay
+    }
+
+    /// Tiles uncovered by the ghost so far
+    pub fn uncovered(&self) -> &HashSet<Coordinates> {
+        &self.uncovered
+    }
+
+    /// Did the ghost hit a bomb
+    pub fn exploded(&self) -> bool {
+        self.exploded
+    }
+
+    /// Is the replay over
+    pub fn is_finished(&self) -> bool {
+        self.next_event >= self.replay.events.len()
+    }
+
+    /// Restarts the ghost from the beginning of its replay
+    pub(crate) fn reset(&mut self) {
+        self.next_event = 0;
+        self.uncovered.clear();
+        self.marked.clear();
+        self.exploded = false;
+    }
+
+    /// Plays the replay actions up to `time` on `tile_map`, returning the newly uncovered tiles
+    pub(crate) fn advance(&mut self, time: f32, tile_map: &TileMap) -> Vec<Coordinates> {
+        let mut uncovered = Vec::new();
+        while let Some(event) = self.replay.events.get(self.next_event) {
+            if event.time > time || self.exploded {
+                break;
+            }
+            self.next_event += 1;
+            match event.action {
+                ReplayAction::Uncover(coords) => self.uncover(coords, tile_map, &mut uncovered),
+                ReplayAction::Mark(coords) => {
+                    if !self.uncovered.contains(&coords) && !self.marked.remove(&coords) {
+                        self.marked.insert(coords);
+                    }
+                }
+                ReplayAction::Chord(coords) => {
+                    let neighbors: Vec<_> = tile_map
+                        .safe_square_at(coords)
+                        .filter(|c| c.x < tile_map.width() && c.y < tile_map.height())
+                        .collect();
+                    let marked = neighbors.iter().filter(|c| self.marked.contains(c)).count();
+                    let count = match tile_map[coords.y as usize][coords.x as usize] {
+                        Tile::BombNeighbor(count) => count,
+                        _ => continue,
+                    };
+                    if !self.uncovered.contains(&coords) || marked as isize != count as isize {
+                        continue;
+                    }
+                    for neighbor in neighbors {
+                        self.uncover(neighbor, tile_map, &mut uncovered);
+                    }
+                }
+            }
+        }
+        uncovered
+    }
+
+    /// Uncovers `coords` and the opening it belongs to
+    fn uncover(
+        &mut self,
+        coords: Coordinates,
+        tile_map: &TileMap,
+        uncovered: &mut Vec<Coordinates>,
+    ) {
+        let mut stack = vec![coords];
+        while let Some(coords) = stack.pop() {
+            if coords.x >= tile_map.width()
+                || coords.y >= tile_map.height()
+                || self.marked.contains(&coords)
+                || !self.uncovered.insert(coords)
+            {
+                continue;
+            }
+            uncovered.push(coords);
+            match tile_map[coords.y as usize][coords.x as usize] {
+                Tile::Empty => stack.extend(tile_map.safe_square_at(coords)),
+                tile if tile.is_bomb() => self.exploded = true,
+                _ => (),
+            }
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::components::Coordinates;
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// Bombs left covered on loss, revealed one at a time. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct RevealSequence {
+    /// Bombs still to reveal, in order
+    pub pending: VecDeque<Coordinates>,
+    /// Delay between two reveals
+    pub timer: Timer,
+}
+
+impl RevealSequence {
+    /// Reveals `pending` in order, one every `delay` seconds
+    pub fn new(pending: impl IntoIterator<Item = Coordinates>, delay: f32) -> Self {
+        Self { pending: pending.into_iter().collect(), timer: Timer::from_seconds(delay, true) }
+    }
+
+    /// Advances the sequence, returning the bombs to reveal
+    pub fn tick(&mut self, delta: Duration) -> Vec<Coordinates> {
+        let count = self.timer.tick(delta).times_finished();
+        (0..count).map_while(|_| self.pending.pop_front()).collect()
+    }
+
+    /// Were all bombs revealed?
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
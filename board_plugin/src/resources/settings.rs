@@ -0,0 +1,77 @@
+use crate::resources::{Difficulty, InputBindings, NumberPalette};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// User-facing settings, loaded at startup and saved on exit. Changing them applies the bindings
+/// and palette to the plugin. Must be used as a resource
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    /// Last chosen difficulty
+    pub difficulty: Difficulty,
+    /// Bomb counter palette
+    pub palette: NumberPalette,
+    /// Mouse and keyboard bindings
+    pub bindings: InputBindings,
+    /// Audio volume between `0.0` and `1.0`, for host apps playing sounds
+    pub volume: f32,
+    /// Save file, settings are kept in memory only if not set
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            difficulty: Difficulty::Beginner,
+            palette: NumberPalette::Classic,
+            bindings: InputBindings::default(),
+            volume: 1.,
+            path: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Default save file, in the platform config directory
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|dir| dir.join("minesweeper-tutorial").join("settings.ron"))
+    }
+
+    /// Browsers have no file system, settings are kept in memory
+    #[cfg(target_arch = "wasm32")]
+    pub fn default_path() -> Option<PathBuf> {
+        None
+    }
+
+    /// Loads the settings from a RON file, using the defaults if the file doesn't exist yet.
+    /// Later saves go to the same file
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let mut settings: Self = match fs::read_to_string(path) {
+            Ok(content) => ron::from_str(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(e) => return Err(e),
+        };
+        settings.path = Some(path.to_path_buf());
+        Ok(settings)
+    }
+
+    /// Saves the settings to their file, if any
+    pub fn save(&self) -> io::Result<()> {
+        let path = match &self.path {
+            None => return Ok(()),
+            Some(p) => p,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let content = ron::ser::to_string_pretty(self, Default::default())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(path, content)
+    }
+}
@@ -6,16 +6,57 @@ use colored::Colorize;
 pub enum Tile {
     /// Is a bomb
     Bomb,
-    /// Is a bomb neighbor
-    BombNeighbor(u8),
+    /// Is an armored bomb, requiring the given number of flags
+    ArmoredBomb(u8),
+    /// Is an anti-mine, a bomb subtracting one from its neighbors counts
+    AntiMine,
+    /// Is a bomb neighbor, the count being negative or zero around anti-mines
+    BombNeighbor(i8),
     /// Empty tile
     Empty,
+    /// Outside of the board shape, never spawned
+    Hole,
 }
 
 impl Tile {
     /// Is the tile a bomb?
     pub const fn is_bomb(&self) -> bool {
-        matches!(self, Self::Bomb)
+        matches!(self, Self::Bomb | Self::ArmoredBomb(_) | Self::AntiMine)
+    }
+
+    /// Value the tile adds to its neighbors bomb count, armored bombs counting for their armor
+    pub const fn bomb_weight(&self) -> i8 {
+        match self {
+            Self::Bomb => 1,
+            Self::ArmoredBomb(armor) => *armor as i8,
+            Self::AntiMine => -1,
+            _ => 0,
+        }
+    }
+
+    /// Single byte encoding of the tile, see `TileGrid`. Armor is kept up to `MAX_ARMOR` and
+    /// counts from -96, below any count of 8 neighbors
+    pub(crate) const fn code(&self) -> u8 {
+        match self {
+            Self::Empty => 0,
+            Self::Hole => 1,
+            Self::Bomb => 2,
+            Self::AntiMine => 3,
+            Self::ArmoredBomb(armor) => 16 + (*armor & 15),
+            Self::BombNeighbor(count) => (*count as u8) ^ 0x80,
+        }
+    }
+
+    /// Tile of a `code`, unused codes being empty tiles
+    pub(crate) const fn from_code(code: u8) -> Self {
+        match code {
+            1 => Self::Hole,
+            2 => Self::Bomb,
+            3 => Self::AntiMine,
+            16..=31 => Self::ArmoredBomb(code - 16),
+            32..=255 => Self::BombNeighbor((code ^ 0x80) as i8),
+            _ => Self::Empty,
+        }
     }
 
     #[cfg(feature = "debug")]
@@ -24,13 +65,18 @@ impl Tile {
             "{}",
             match self {
                 Tile::Bomb => "*".bright_red(),
+                Tile::ArmoredBomb(armor) => armor.to_string().bright_magenta(),
+                Tile::AntiMine => "+".bright_blue(),
                 Tile::BombNeighbor(v) => match v {
                     1 => "1".cyan(),
                     2 => "2".green(),
                     3 => "3".yellow(),
+                    // Single characters keep the map aligned
+                    v if *v <= 0 => v.abs().to_string().bright_blue(),
                     _ => v.to_string().red(),
                 },
                 Tile::Empty => " ".normal(),
+                Tile::Hole => "#".dimmed(),
             }
         )
     }
@@ -0,0 +1,31 @@
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::ops::RangeInclusive;
+
+/// Bomb counters drawn from the tile atlas, the others being clamped
+pub const ATLAS_NUMBERS: RangeInclusive<i8> = -9..=99;
+
+/// Shared texture atlas of the tile visuals: the tile, cover and bomb textures, and white bomb
+/// counters tinted by their sprite color. Built by `build_tile_atlas` with
+/// `BoardOptions::texture_atlas`, so the tiles render in a few batched draw calls
+#[derive(Debug, Clone)]
+pub struct TileAtlas {
+    pub atlas: Handle<TextureAtlas>,
+    /// Atlas index of each packed texture
+    pub textures: HashMap<Handle<Image>, usize>,
+    /// Atlas index of each bomb counter of `ATLAS_NUMBERS`, in order
+    pub numbers: Vec<usize>,
+}
+
+impl TileAtlas {
+    /// Atlas index of a packed texture
+    pub fn texture_index(&self, texture: &Handle<Image>) -> Option<usize> {
+        self.textures.get(texture).copied()
+    }
+
+    /// Atlas index of a bomb counter
+    pub fn number_index(&self, count: i8) -> usize {
+        let count = count.clamp(*ATLAS_NUMBERS.start(), *ATLAS_NUMBERS.end());
+        self.numbers[(count as isize - *ATLAS_NUMBERS.start() as isize) as usize]
+    }
+}
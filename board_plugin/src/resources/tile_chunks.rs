@@ -0,0 +1,61 @@
+use crate::components::Coordinates;
+use crate::resources::{Tile, TileMap};
+use bevy::prelude::Entity;
+use bevy::utils::{HashMap, HashSet};
+
+/// Chunk position, in chunks from the bottom left of the board
+pub type ChunkKey = (u16, u16);
+
+/// Tile entities of huge boards, only spawned by square chunks around the camera view.
+/// The covered tiles of unspawned chunks are only tracked by their coordinates
+#[derive(Debug, Clone)]
+pub struct TileChunks {
+    /// Chunk width and height, in tiles
+    size: u16,
+    /// Spawned chunk entities, parents of their tiles
+    pub(crate) spawned: HashMap<ChunkKey, Entity>,
+    /// Covered tiles of the unspawned chunks
+    pub(crate) covered: HashSet<Coordinates>,
+}
+
+impl TileChunks {
+    /// Starts with no spawned chunk and every tile covered, except the safe start opening
+    pub fn new(size: u16, tile_map: &TileMap, safe_start: bool) -> Self {
+        let mut covered: HashSet<Coordinates> = (0..tile_map.height())
+            .flat_map(|y| (0..tile_map.width()).map(move |x| Coordinates { x, y }))
+            .filter(|c| tile_map[c.y as usize][c.x as usize] != Tile::Hole)
+            .collect();
+        if let (true, Some(start)) = (safe_start, tile_map.safe_start()) {
+            for coords in tile_map.opening_at(start) {
+                covered.remove(&coords);
+            }
+        }
+        Self { size: size.max(1), spawned: HashMap::default(), covered }
+    }
+
+    // Getter for `size`
+    pub fn size(&self) -> u16 {
+        self.size
+    }
+
+    /// Chunk containing `coords`
+    pub fn chunk_at(&self, coords: Coordinates) -> ChunkKey {
+        (coords.x / self.size, coords.y / self.size)
+    }
+
+    /// Tile area of the `key` chunk as bottom left and top right exclusive coordinates,
+    /// clamped to the `tile_map` bounds
+    pub fn area(&self, key: ChunkKey, tile_map: &TileMap) -> (Coordinates, Coordinates) {
+        let min = Coordinates { x: key.0 * self.size, y: key.1 * self.size };
+        let max = Coordinates {
+            x: min.x.saturating_add(self.size).min(tile_map.width()),
+            y: min.y.saturating_add(self.size).min(tile_map.height()),
+        };
+        (min, max)
+    }
+
+    /// Is the `key` chunk spawned
+    pub fn is_spawned(&self, key: ChunkKey) -> bool {
+        self.spawned.contains_key(&key)
+    }
+}
@@ -1,7 +1,10 @@
-use crate::components::Coordinates;
+use crate::components::{Adjacency, Coordinates};
 use crate::resources::tile::Tile;
-use rand::{thread_rng, Rng};
-use std::ops::{Deref, DerefMut};
+use crate::resources::{BoardMask, BoardVariant, BombDensity, Symmetry, MAX_ARMOR};
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use rayon::prelude::*;
+use std::cmp::Ordering;
+use std::ops::{Deref, Index};
 
 /// Delta coordinates for all 8 square neighbors
 const SQUARE_COORDINATES: [(i8, i8); 8] = [
@@ -15,30 +18,177 @@ const SQUARE_COORDINATES: [(i8, i8); 8] = [
     (1, 1),   // Top right
 ];
 
+/// Tile count from which bomb neighbors and safe start attempts are computed in parallel
+pub(crate) const PARALLEL_TILE_COUNT: usize = 250_000;
+
+/// Every tile by its code, so rows storing codes can still lend `Tile` references
+static TILES: [Tile; 256] = tile_table();
+
+const fn tile_table() -> [Tile; 256] {
+    let mut tiles = [Tile::Empty; 256];
+    let mut code = 0;
+    while code < 256 {
+        tiles[code] = Tile::from_code(code as u8);
+        code += 1;
+    }
+    tiles
+}
+
+/// Flat row-major tile storage, indexed by row then column like nested vectors. Bombs are kept
+/// in a bitset and every tile in a single byte, its bomb count or bomb kind, see `Tile::code`
+#[derive(Debug, Clone)]
+pub struct TileGrid {
+    width: usize,
+    /// One bit per tile, set for bombs, making neighbor lookups cache friendly
+    bombs: Vec<u64>,
+    codes: Vec<u8>,
+}
+
+impl TileGrid {
+    fn new(width: usize, height: usize) -> Self {
+        let len = width * height;
+        Self { width, bombs: vec![0; (len + 63) / 64], codes: vec![Tile::Empty.code(); len] }
+    }
+
+    /// Iterates over the rows, bottom row first
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &TileRow> + ExactSizeIterator {
+        self.codes.chunks_exact(self.width.max(1)).map(TileRow::new)
+    }
+
+    /// Tile at the flat row-major `index`
+    fn tile(&self, index: usize) -> Tile {
+        TILES[self.codes[index] as usize]
+    }
+
+    /// Is there a bomb at the flat row-major `index`
+    fn is_bomb(&self, index: usize) -> bool {
+        self.bombs[index / 64] & (1 << (index % 64)) != 0
+    }
+
+    /// Replaces the tile at the flat row-major `index`, keeping the bitset in sync
+    fn set(&mut self, index: usize, tile: Tile) {
+        self.codes[index] = tile.code();
+        if tile.is_bomb() {
+            self.bombs[index / 64] |= 1 << (index % 64);
+        } else {
+            self.bombs[index / 64] &= !(1 << (index % 64));
+        }
+    }
+}
+
+impl Index<usize> for TileGrid {
+    type Output = TileRow;
+
+    fn index(&self, y: usize) -> &Self::Output {
+        TileRow::new(&self.codes[y * self.width..(y + 1) * self.width])
+    }
+}
+
+/// Row of a `TileGrid`, indexed by column
+#[repr(transparent)]
+pub struct TileRow([u8]);
+
+impl TileRow {
+    fn new(codes: &[u8]) -> &Self {
+        // Safety: `TileRow` is a transparent wrapper of `[u8]`, like `Path` of `OsStr`
+        unsafe { &*(codes as *const [u8] as *const Self) }
+    }
+
+    /// Iterates over the tiles of the row
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Tile> + ExactSizeIterator {
+        self.0.iter().map(|code| &TILES[*code as usize])
+    }
+
+    /// Number of tiles in the row
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Is the row without tiles, for maps without width
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Index<usize> for TileRow {
+    type Output = Tile;
+
+    fn index(&self, x: usize) -> &Self::Output {
+        &TILES[self.0[x] as usize]
+    }
+}
+
 /// Base tile map
 #[derive(Debug, Clone)]
 pub struct TileMap {
     bomb_count: u16,
+    seed: u64,
     height: u16,
     width: u16,
-    map: Vec<Vec<Tile>>,
+    map: TileGrid,
+    /// Flat index offsets of the neighbors inside the map, by tile position class, see
+    /// `neighbor_indices`
+    neighbor_offsets: Vec<Vec<isize>>,
 }
 
 impl TileMap {
     /// Generates an empty map
     pub fn empty(width: u16, height: u16) -> Self {
-        let map = (0..height)
-            .into_iter()
-            .map(|_| (0..width).into_iter().map(|_| Tile::Empty).collect())
-            .collect();
         Self {
             bomb_count: 0,
+            seed: 0,
             height,
             width,
-            map,
+            map: TileGrid::new(width as usize, height as usize),
+            neighbor_offsets: Self::neighbor_offsets(width as isize),
         }
     }
 
+    /// Precomputes the neighbor index offsets of the 16 tile position classes, each class bit
+    /// telling whether the tile is on the left, right, bottom or top border
+    fn neighbor_offsets(width: isize) -> Vec<Vec<isize>> {
+        (0..16)
+            .map(|class| {
+                SQUARE_COORDINATES
+                    .iter()
+                    .filter(|(dx, dy)| {
+                        !(*dx < 0 && class & 1 != 0
+                            || *dx > 0 && class & 2 != 0
+                            || *dy < 0 && class & 4 != 0
+                            || *dy > 0 && class & 8 != 0)
+                    })
+                    .map(|(dx, dy)| *dy as isize * width + *dx as isize)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Flat row-major indices of the neighbors of the tile at `index` inside the map, from the
+    /// precomputed offsets of its position class
+    fn neighbor_indices(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let (x, y) = (index % width, index / width);
+        let class = (x == 0) as usize
+            | ((x + 1 == width) as usize) << 1
+            | ((y == 0) as usize) << 2
+            | ((y + 1 == height) as usize) << 3;
+        self.neighbor_offsets[class].iter().map(move |offset| (index as isize + offset) as usize)
+    }
+
+    /// Coordinates of the tile at the flat row-major `index`
+    fn coordinates_of(&self, index: usize) -> Coordinates {
+        let width = self.width as usize;
+        Coordinates { x: (index % width) as u16, y: (index / width) as u16 }
+    }
+
+    /// Generates a map with bombs at the given positions, out of bounds and duplicate positions
+    /// being ignored
+    pub fn from_bomb_positions(width: u16, height: u16, positions: &[Coordinates]) -> Self {
+        let mut tile_map = Self::empty(width, height);
+        tile_map.set_bomb_positions(positions, 0, &BoardVariant::Classic);
+        tile_map
+    }
+
     #[cfg(feature = "debug")]
     pub fn console_output(&self) -> String {
         let mut buffer = format!(
@@ -72,6 +222,12 @@ impl TileMap {
         self.bomb_count
     }
 
+    // Getter for `seed`
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// The 8 coordinates around `coordinates`, out of bounds ones included, see `neighbors`
     // Incorrect name, "squares_around" would be better
     pub fn safe_square_at(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
         SQUARE_COORDINATES
@@ -80,29 +236,248 @@ impl TileMap {
             .map(move |tuple| coordinates + tuple)
     }
 
+    /// Neighbors of `coordinates` inside the map
+    pub fn neighbors(&self, coordinates: Coordinates) -> impl Iterator<Item = Coordinates> {
+        let (width, height) = (self.width, self.height);
+        coordinates.neighbors(Adjacency::Square).filter(move |c| c.x < width && c.y < height)
+    }
+
+    /// Bomb count around `coordinates` split between its orthogonal and diagonal neighbors,
+    /// special bombs counting for their weight
+    pub fn split_bomb_count(&self, coordinates: Coordinates) -> (i8, i8) {
+        let (width, height) = (self.width, self.height);
+        let count = |adjacency| {
+            coordinates
+                .neighbors(adjacency)
+                .filter(|c| c.x < width && c.y < height && self.is_bomb_at(*c))
+                .map(|c| self[c.y as usize][c.x as usize].bomb_weight())
+                .sum()
+        };
+        (count(Adjacency::Orthogonal), count(Adjacency::Diagonal))
+    }
+
+    /// Is there a bomb at `coordinates`, out of bounds coordinates having none
     pub fn is_bomb_at(&self, coordinates: Coordinates) -> bool {
         if coordinates.x >= self.width || coordinates.y >= self.height {
             return false;
         };
-        self.map[coordinates.y as usize][coordinates.x as usize].is_bomb()
+        self.map.is_bomb(coordinates.y as usize * self.width as usize + coordinates.x as usize)
     }
 
-    pub fn bomb_count_at(&self, coordinates: Coordinates) -> u8 {
+    /// Places a bomb on an empty tile
+    fn place_bomb(&mut self, x: usize, y: usize) {
+        self.map.set(y * self.width as usize + x, Tile::Bomb);
+    }
+
+    /// Moves the plain bomb at `from` to the empty or bomb neighbor tile at `to`, updating the
+    /// bomb counts around both. Returns the tiles which changed, or nothing if the move isn't
+    /// possible
+    pub fn move_bomb(&mut self, from: Coordinates, to: Coordinates) -> Vec<Coordinates> {
+        let in_bounds = |c: Coordinates| c.x < self.width && c.y < self.height;
+        if !in_bounds(from) || !in_bounds(to) {
+            return Vec::new();
+        }
+        let destination = self[to.y as usize][to.x as usize];
+        if self[from.y as usize][from.x as usize] != Tile::Bomb
+            || destination.is_bomb()
+            || destination == Tile::Hole
+        {
+            return Vec::new();
+        }
+        self.map.set(from.y as usize * self.width as usize + from.x as usize, Tile::Empty);
+        self.place_bomb(to.x as usize, to.y as usize);
+        let mut changed: Vec<Coordinates> =
+            [from, to].into_iter().chain(self.neighbors(from)).chain(self.neighbors(to)).collect();
+        changed.sort_unstable();
+        changed.dedup();
+        for coords in changed.iter().copied() {
+            let tile = self[coords.y as usize][coords.x as usize];
+            if tile.is_bomb() || tile == Tile::Hole {
+                continue;
+            }
+            let count =
+                self.neighbors(coords).filter(|c| self.is_bomb_at(*c)).fold(None, |count, c| {
+                    let weight = self[c.y as usize][c.x as usize].bomb_weight();
+                    Some(count.unwrap_or_default() + weight)
+                });
+            let index = coords.y as usize * self.width as usize + coords.x as usize;
+            self.map.set(index, count.map_or(Tile::Empty, Tile::BombNeighbor));
+        }
+        changed
+    }
+
+    /// Every tile inside the board shape with its coordinates, in row-major order
+    pub fn tiles(&self) -> impl Iterator<Item = (Coordinates, Tile)> + '_ {
+        self.iter().enumerate().flat_map(|(y, line)| {
+            line.iter()
+                .enumerate()
+                .filter(|(_, tile)| **tile != Tile::Hole)
+                .map(move |(x, tile)| (Coordinates { x: x as u16, y: y as u16 }, *tile))
+        })
+    }
+
+    /// Coordinates of every bomb, found from the bitset
+    pub(crate) fn bomb_coordinates(&self) -> impl Iterator<Item = Coordinates> + '_ {
+        let width = self.width.max(1) as usize;
+        self.map.bombs.iter().enumerate().flat_map(move |(word_index, word)| {
+            (0..64).filter(move |bit| word & (1 << bit) != 0).map(move |bit| {
+                let index = word_index * 64 + bit;
+                Coordinates { x: (index % width) as u16, y: (index / width) as u16 }
+            })
+        })
+    }
+
+    /// Sum of the neighbor bombs weights, armored bombs counting for their armor and anti-mines
+    /// subtracting one
+    pub fn bomb_count_at(&self, coordinates: Coordinates) -> i8 {
         if self.is_bomb_at(coordinates) {
             return 0;
         }
-        let res = self
-            .safe_square_at(coordinates)
+        self.neighbors(coordinates)
             .filter(|coord| self.is_bomb_at(*coord))
+            .map(|coord| self[coord.y as usize][coord.x as usize].bomb_weight())
+            .sum()
+    }
+
+    /// Does `coordinates` have any bomb around, whatever their weights
+    pub fn has_bomb_around(&self, coordinates: Coordinates) -> bool {
+        self.safe_square_at(coordinates).any(|coord| self.is_bomb_at(coord))
+    }
+
+    /// Computes the board 3BV (Bechtel's Board Benchmark Value), the minimum number of
+    /// clicks required to clear the board
+    pub fn bbbv(&self) -> u32 {
+        let mut visited = vec![vec![false; self.width as usize]; self.height as usize];
+        let mut count = 0;
+        // Every opening counts as one click and reveals its numbered border
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if visited[y as usize][x as usize] || self[y as usize][x as usize] != Tile::Empty {
+                    continue;
+                }
+                count += 1;
+                visited[y as usize][x as usize] = true;
+                let mut stack = vec![Coordinates { x, y }];
+                while let Some(coords) = stack.pop() {
+                    for neighbor in self.neighbors(coords) {
+                        let (nx, ny) = (neighbor.x as usize, neighbor.y as usize);
+                        if visited[ny][nx] {
+                            continue;
+                        }
+                        visited[ny][nx] = true;
+                        if self[ny][nx] == Tile::Empty {
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+        }
+        // Every other safe tile requires its own click
+        let isolated = self
+            .iter()
+            .zip(visited.iter())
+            .flat_map(|(line, visited)| line.iter().zip(visited.iter()))
+            .filter(|(tile, visited)| !tile.is_bomb() && **tile != Tile::Hole && !**visited)
             .count();
-        res as u8
+        count + isolated as u32
     }
 
-    /// Places bombs and bomb neighbor tiles
-    pub fn set_bombs(&mut self, bomb_count: u16) {
+    /// Tiles uncovered by opening `coordinates`: the connected empty tiles and their numbered
+    /// border, computed iteratively. A numbered tile or a bomb only opens itself
+    pub fn opening_at(&self, coordinates: Coordinates) -> Vec<Coordinates> {
+        self.opening_within(coordinates, |_| false)
+    }
+
+    /// Tiles uncovered by opening `coordinates` like `opening_at`, the `stops` tiles being
+    /// neither opened nor spread through
+    pub fn opening_within(
+        &self,
+        coordinates: Coordinates,
+        stops: impl Fn(&Coordinates) -> bool,
+    ) -> Vec<Coordinates> {
+        let (width, height) = (self.width as usize, self.height as usize);
+        if coordinates.x as usize >= width || coordinates.y as usize >= height {
+            return Vec::new();
+        }
+        let start = coordinates.y as usize * width + coordinates.x as usize;
+        let mut visited = vec![false; width * height];
+        visited[start] = true;
+        let mut opening = vec![coordinates];
+        let mut stack = vec![start];
+        while let Some(index) = stack.pop() {
+            if self.map.tile(index) != Tile::Empty {
+                continue;
+            }
+            for neighbor in self.neighbor_indices(index) {
+                if visited[neighbor] || self.map.tile(neighbor) == Tile::Hole {
+                    continue;
+                }
+                let coords = self.coordinates_of(neighbor);
+                if stops(&coords) {
+                    continue;
+                }
+                visited[neighbor] = true;
+                opening.push(coords);
+                stack.push(neighbor);
+            }
+        }
+        opening
+    }
+
+    /// Safe start tile uncovered when the board is spawned, the first empty tile from the bottom
+    /// left
+    pub fn safe_start(&self) -> Option<Coordinates> {
+        (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| Coordinates { x, y }))
+            .find(|c| self[c.y as usize][c.x as usize] == Tile::Empty)
+    }
+
+    /// Overall difficulty score: the 3BV weighted by the bomb density.
+    /// Roughly 3 for a beginner board and 30 for an expert board
+    pub fn difficulty_score(&self) -> f32 {
+        let tile_count = self.tile_count() as f32;
+        if tile_count == 0. {
+            return 0.;
+        }
+        self.bbbv() as f32 * self.bomb_count as f32 / tile_count
+    }
+
+    /// Turns the tiles outside of `mask` into holes, to be done before placing bombs
+    pub fn set_mask(&mut self, mask: &BoardMask) {
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if !mask.contains(Coordinates { x, y }, self.height) {
+                    self.map.set(y as usize * self.width as usize + x as usize, Tile::Hole);
+                }
+            }
+        }
+    }
+
+    /// Number of tiles inside the board shape
+    pub fn tile_count(&self) -> usize {
+        self.iter().flat_map(|line| line.iter()).filter(|tile| **tile != Tile::Hole).count()
+    }
+
+    /// Places bombs and bomb neighbor tiles, the same `seed` always giving the same layout
+    pub fn set_bombs(
+        &mut self,
+        bomb_count: u16,
+        seed: u64,
+        variant: &BoardVariant,
+        symmetry: Symmetry,
+        density: &BombDensity,
+    ) {
+        // Bombs without an empty tile left would be placed forever, see `BoardOptions::validate`
+        let empty = self.iter().flat_map(|line| line.iter()).filter(|t| **t == Tile::Empty).count();
+        let bomb_count = bomb_count.min(empty.min(u16::MAX as usize) as u16);
         self.bomb_count = bomb_count;
+        self.seed = seed;
         let mut remaining_bombs = bomb_count;
-        let mut rng = thread_rng();
+        let mut rng = StdRng::seed_from_u64(seed);
+        if symmetry != Symmetry::None || *density != BombDensity::Uniform {
+            self.place_bomb_groups(&mut rng, bomb_count, symmetry, density);
+            remaining_bombs = 0;
+        }
         // Place bombs
         while remaining_bombs > 0 {
             let (x, y) = (
@@ -110,38 +485,265 @@ impl TileMap {
                 rng.gen_range(0..self.height) as usize,
             );
             if let Tile::Empty = self[y][x] {
-                self[y][x] = Tile::Bomb;
+                self.place_bomb(x, y);
                 remaining_bombs -= 1;
             }
         }
-        // Place bomb neighbors
-        for y in 0..self.height {
-            for x in 0..self.width {
-                let coords = Coordinates { x, y };
-                if self.is_bomb_at(coords) {
+        self.apply_variant(&mut rng, variant);
+        self.set_bomb_neighbors();
+    }
+
+    /// Places bombs by groups of mirrored tiles, picked according to the density weights. An odd
+    /// bomb count puts a bomb on a tile that is its own mirror, if the map has one. Bombs that
+    /// can't be placed this way, because of the count parity, an asymmetric mask or too few
+    /// weighted tiles, are placed uniformly
+    fn place_bomb_groups(
+        &mut self,
+        rng: &mut StdRng,
+        bomb_count: u16,
+        symmetry: Symmetry,
+        density: &BombDensity,
+    ) {
+        let (width, height) = (self.width as usize, self.height as usize);
+        let mut singles = Vec::new();
+        let mut pairs = Vec::new();
+        for y in 0..height {
+            for x in 0..width {
+                let mirror = symmetry.mirror((x, y), (width, height));
+                // Each pair is only listed from its first tile
+                if mirror < (x, y) || self.map[y][x] != Tile::Empty {
                     continue;
                 }
-                let num = self.bomb_count_at(coords);
-                if num == 0 {
-                    continue;
+                if mirror == (x, y) {
+                    singles.push(vec![(x, y)]);
+                } else if self.map[mirror.1][mirror.0] == Tile::Empty {
+                    pairs.push(vec![(x, y), mirror]);
+                }
+            }
+        }
+        Self::weighted_shuffle(&mut singles, rng, density, (width, height));
+        Self::weighted_shuffle(&mut pairs, rng, density, (width, height));
+        let odd = (bomb_count % 2 == 1 && !singles.is_empty()).then(|| singles.remove(0));
+        let mut remaining = bomb_count as usize;
+        for group in odd.into_iter().chain(pairs).chain(singles) {
+            if group.len() > remaining {
+                continue;
+            }
+            for (x, y) in group.iter() {
+                self.place_bomb(*x, *y);
+            }
+            remaining -= group.len();
+        }
+        if remaining > 0 {
+            let mut empty: Vec<_> = (0..height)
+                .flat_map(|y| (0..width).map(move |x| (x, y)))
+                .filter(|(x, y)| self.map[*y][*x] == Tile::Empty)
+                .collect();
+            empty.shuffle(rng);
+            for (x, y) in empty.into_iter().take(remaining) {
+                self.place_bomb(x, y);
+            }
+        }
+    }
+
+    /// Shuffles tile groups so heavier groups, weighted by their first tile, tend to come first.
+    /// Groups without weight are removed
+    fn weighted_shuffle(
+        groups: &mut Vec<Vec<(usize, usize)>>,
+        rng: &mut StdRng,
+        density: &BombDensity,
+        size: (usize, usize),
+    ) {
+        if *density == BombDensity::Uniform {
+            groups.shuffle(rng);
+            return;
+        }
+        // Weighted sampling keys (Efraimidis-Spirakis), in log space for precision
+        let mut keyed: Vec<_> = groups
+            .drain(..)
+            .filter_map(|group| {
+                let weight = density.weight(group[0], size);
+                (weight > 0.).then(|| (rng.gen::<f32>().ln() / weight, group))
+            })
+            .collect();
+        keyed.sort_by(|(a, _), (b, _)| b.partial_cmp(a).unwrap_or(Ordering::Equal));
+        groups.extend(keyed.into_iter().map(|(_, group)| group));
+    }
+
+    /// Places bombs at the given positions and bomb neighbor tiles, `seed` only being used by
+    /// the variant. Out of bounds, hole and duplicate positions are ignored
+    pub fn set_bomb_positions(
+        &mut self,
+        positions: &[Coordinates],
+        seed: u64,
+        variant: &BoardVariant,
+    ) {
+        self.bomb_count = 0;
+        self.seed = seed;
+        for coords in positions {
+            if coords.x >= self.width || coords.y >= self.height {
+                continue;
+            }
+            let (x, y) = (coords.x as usize, coords.y as usize);
+            if self[y][x] == Tile::Empty {
+                self.place_bomb(x, y);
+                self.bomb_count += 1;
+            }
+        }
+        let mut rng = StdRng::seed_from_u64(seed);
+        self.apply_variant(&mut rng, variant);
+        self.set_bomb_neighbors();
+    }
+
+    /// Turns some of the placed bombs into the variant special bombs
+    fn apply_variant(&mut self, rng: &mut StdRng, variant: &BoardVariant) {
+        let bomb_count = self.bomb_count;
+        // Armor some of the bombs
+        if let BoardVariant::Armored { armored_count, max_armor } = *variant {
+            let mut remaining_armored = armored_count.min(bomb_count);
+            while remaining_armored > 0 {
+                let (x, y) = (
+                    rng.gen_range(0..self.width) as usize,
+                    rng.gen_range(0..self.height) as usize,
+                );
+                if let Tile::Bomb = self[y][x] {
+                    let armor = rng.gen_range(2..=max_armor.clamp(2, MAX_ARMOR));
+                    self.map.set(y * self.width as usize + x, Tile::ArmoredBomb(armor));
+                    remaining_armored -= 1;
+                }
+            }
+        }
+        // Turn some of the bombs into anti-mines
+        if let BoardVariant::AntiMine { anti_mine_count } = *variant {
+            let mut remaining_anti_mines = anti_mine_count.min(bomb_count);
+            while remaining_anti_mines > 0 {
+                let (x, y) = (
+                    rng.gen_range(0..self.width) as usize,
+                    rng.gen_range(0..self.height) as usize,
+                );
+                if let Tile::Bomb = self[y][x] {
+                    self.map.set(y * self.width as usize + x, Tile::AntiMine);
+                    remaining_anti_mines -= 1;
                 }
-                let tile = &mut self[y as usize][x as usize];
-                *tile = Tile::BombNeighbor(num);
+            }
+        }
+    }
+
+    /// Places bomb neighbor tiles around the placed bombs. Every bomb adds its weight to its
+    /// neighbors, instead of every tile looking up its neighbors
+    fn set_bomb_neighbors(&mut self) {
+        let width = self.width as usize;
+        if width * self.height as usize >= PARALLEL_TILE_COUNT {
+            self.set_bomb_neighbors_parallel();
+            return;
+        }
+        let mut counts: Vec<Option<i8>> = vec![None; width * self.height as usize];
+        for coords in self.bomb_coordinates() {
+            let index = coords.y as usize * width + coords.x as usize;
+            let weight = self.map.tile(index).bomb_weight();
+            for neighbor in self.neighbor_indices(index) {
+                let count = &mut counts[neighbor];
+                *count = Some(count.unwrap_or_default() + weight);
+            }
+        }
+        self.apply_bomb_counts(counts);
+    }
+
+    /// Places bomb neighbor tiles on large maps, the rows looking up their neighbor bombs in
+    /// parallel
+    fn set_bomb_neighbors_parallel(&mut self) {
+        let (map, width) = (&*self, self.width as usize);
+        let counts: Vec<Option<i8>> = (0..self.height as usize)
+            .into_par_iter()
+            .flat_map_iter(|y| {
+                (0..width).map(move |x| {
+                    let coords = Coordinates { x: x as u16, y: y as u16 };
+                    map.neighbors(coords).filter(|c| map.is_bomb_at(*c)).fold(None, |count, c| {
+                        let weight = map[c.y as usize][c.x as usize].bomb_weight();
+                        Some(count.unwrap_or_default() + weight)
+                    })
+                })
+            })
+            .collect();
+        self.apply_bomb_counts(counts);
+    }
+
+    /// Turns the empty tiles with a bomb count, in row-major order, into bomb neighbor tiles
+    fn apply_bomb_counts(&mut self, counts: Vec<Option<i8>>) {
+        for (index, count) in counts.into_iter().enumerate() {
+            // Bombs and anti-mines may cancel out, the tile still isn't empty
+            if let (Some(count), Tile::Empty) = (count, self.map.tile(index)) {
+                self.map.set(index, Tile::BombNeighbor(count));
             }
         }
     }
 }
 
 impl Deref for TileMap {
-    type Target = Vec<Vec<Tile>>;
+    type Target = TileGrid;
 
     fn deref(&self) -> &Self::Target {
         &self.map
     }
 }
 
-impl DerefMut for TileMap {
-    fn deref_mut(&mut self) -> &mut Self::Target {
-        &mut self.map
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tile_map(width: u16, height: u16, bombs: &[(u16, u16)]) -> TileMap {
+        let bombs: Vec<_> = bombs.iter().map(|(x, y)| Coordinates { x: *x, y: *y }).collect();
+        TileMap::from_bomb_positions(width, height, &bombs)
+    }
+
+    #[test]
+    fn bbbv_counts_openings_and_isolated_numbers() {
+        // Every safe tile touches the bomb, none opens another
+        assert_eq!(tile_map(3, 3, &[(1, 1)]).bbbv(), 8);
+        // `*1..` : a single opening reveals its border
+        assert_eq!(tile_map(4, 1, &[(0, 0)]).bbbv(), 1);
+        // `.1*1.` : an opening on each side of the bomb
+        assert_eq!(tile_map(5, 1, &[(2, 0)]).bbbv(), 2);
+        // `1*1.1*1` : the isolated 1s on the edges need a click each
+        assert_eq!(tile_map(7, 1, &[(1, 0), (5, 0)]).bbbv(), 3);
+    }
+
+    #[test]
+    fn difficulty_score_weights_bbbv_by_density() {
+        assert_eq!(tile_map(3, 3, &[(1, 1)]).difficulty_score(), 8. / 9.);
+        assert_eq!(tile_map(7, 1, &[(1, 0), (5, 0)]).difficulty_score(), 3. * 2. / 7.);
+        assert_eq!(TileMap::empty(0, 0).difficulty_score(), 0.);
+    }
+
+    #[test]
+    fn symmetric_bombs_are_mirrored() {
+        for (symmetry, count) in
+            [(Symmetry::Horizontal, 10), (Symmetry::Vertical, 12), (Symmetry::Rotational, 9)]
+        {
+            let mut tile_map = TileMap::empty(7, 5);
+            tile_map.set_bombs(count, 3, &BoardVariant::Classic, symmetry, &BombDensity::Uniform);
+            let bombs: Vec<_> = tile_map.bomb_coordinates().collect();
+            assert_eq!(bombs.len(), count as usize);
+            for coords in bombs {
+                let (x, y) = symmetry.mirror((coords.x as usize, coords.y as usize), (7, 5));
+                let mirror = Coordinates { x: x as u16, y: y as u16 };
+                assert!(tile_map.is_bomb_at(mirror), "{:?} has no mirror", coords);
+            }
+        }
+    }
+
+    #[test]
+    fn density_biases_bomb_placement() {
+        // Bombs in the central quarter of the map
+        let central_bombs = |density: BombDensity| {
+            let mut tile_map = TileMap::empty(40, 40);
+            tile_map.set_bombs(200, 7, &BoardVariant::Classic, Symmetry::None, &density);
+            let central = |c: &Coordinates| (10..30).contains(&c.x) && (10..30).contains(&c.y);
+            tile_map.bomb_coordinates().filter(central).count()
+        };
+        let uniform = central_bombs(BombDensity::Uniform);
+        let center = central_bombs(BombDensity::CenterGradient { strength: 100. });
+        let edge = central_bombs(BombDensity::EdgeGradient { strength: 100. });
+        assert!(center > uniform && uniform > edge, "{} {} {}", center, uniform, edge);
     }
 }
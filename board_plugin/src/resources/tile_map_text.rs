@@ -0,0 +1,123 @@
+use crate::components::Coordinates;
+use crate::resources::tile::Tile;
+use crate::resources::{BoardMask, BoardVariant, TileMap};
+use std::error::Error;
+use std::fmt::{self, Display, Formatter};
+
+/// Text board parsing errors
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum TileMapTextError {
+    /// The text has no tile
+    Empty,
+    /// The text has more rows or columns than a map can hold
+    TooLarge,
+    /// The text contains a character which isn't a tile, on the given line from 1
+    InvalidCharacter { character: char, line: usize },
+}
+
+impl Display for TileMapTextError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => write!(f, "text board has no tile"),
+            Self::TooLarge => write!(f, "text board is too large"),
+            Self::InvalidCharacter { character, line } => {
+                write!(f, "invalid character '{}' on line {} of text board", character, line)
+            }
+        }
+    }
+}
+
+impl Error for TileMapTextError {}
+
+/// Board read from text, before its bomb neighbors are computed
+pub(crate) struct TextBoard {
+    pub width: u16,
+    pub height: u16,
+    pub bombs: Vec<Coordinates>,
+    /// Board shape, if the text has holes
+    pub mask: Option<BoardMask>,
+}
+
+impl TextBoard {
+    /// Parses rows of `.`, `*` and digits from top to bottom. Digits are recomputed from the
+    /// bombs, spaces and missing columns are holes, and empty lines are skipped
+    pub fn parse(text: &str) -> Result<Self, TileMapTextError> {
+        let rows: Vec<(usize, &str)> = text
+            .lines()
+            .enumerate()
+            .map(|(index, row)| (index + 1, row.trim_end()))
+            .filter(|(_, row)| !row.is_empty())
+            .collect();
+        let width = rows.iter().map(|(_, row)| row.chars().count()).max().unwrap_or_default();
+        if width == 0 {
+            return Err(TileMapTextError::Empty);
+        }
+        let too_large = |_| TileMapTextError::TooLarge;
+        let (width, height) = (
+            u16::try_from(width).map_err(too_large)?,
+            u16::try_from(rows.len()).map_err(too_large)?,
+        );
+        let mut bombs = Vec::new();
+        let mut holes = false;
+        for (row_index, (line, row)) in rows.iter().enumerate() {
+            let y = height - 1 - row_index as u16;
+            holes |= row.chars().count() < width as usize;
+            for (x, character) in row.chars().enumerate() {
+                match character {
+                    '*' => bombs.push(Coordinates { x: x as u16, y }),
+                    ' ' => holes = true,
+                    '.' | '0'..='9' => (),
+                    character => {
+                        return Err(TileMapTextError::InvalidCharacter { character, line: *line })
+                    }
+                }
+            }
+        }
+        let mask = holes.then(|| {
+            BoardMask(
+                rows.iter()
+                    .map(|(_, row)| row.chars().map(|c| if c == ' ' { '.' } else { '#' }).collect())
+                    .collect(),
+            )
+        });
+        Ok(Self { width, height, bombs, mask })
+    }
+}
+
+impl TileMap {
+    /// Writes the map in the text format shared by most minesweeper tools, rows from top to
+    /// bottom: `*` for bombs, digits for bomb counts and `.` for the other tiles. Special bombs
+    /// are written as plain ones, and holes as spaces
+    pub fn to_text(&self) -> String {
+        let mut rows: Vec<String> = self
+            .iter()
+            .rev()
+            .map(|line| {
+                let row: String = line
+                    .iter()
+                    .map(|tile| match tile {
+                        tile if tile.is_bomb() => '*',
+                        Tile::BombNeighbor(count @ 1..=9) => (b'0' + *count as u8) as char,
+                        Tile::Hole => ' ',
+                        _ => '.',
+                    })
+                    .collect();
+                row.trim_end().to_string()
+            })
+            .collect();
+        rows.push(String::new());
+        rows.join("\n")
+    }
+
+    /// Reads a map written by `to_text` or other minesweeper tools. Bomb counts are recomputed,
+    /// so any digit is accepted
+    pub fn from_text(text: &str) -> Result<Self, TileMapTextError> {
+        let board = TextBoard::parse(text)?;
+        let mut tile_map = Self::empty(board.width, board.height);
+        if let Some(mask) = &board.mask {
+            tile_map.set_mask(mask);
+        }
+        tile_map.set_bomb_positions(&board.bombs, 0, &BoardVariant::Classic);
+        Ok(tile_map)
+    }
+}
@@ -0,0 +1,87 @@
+use crate::components::{Coordinates, TileModifier};
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+/// Modifier tiles of a board, as `(x, y)` with `(0, 0)` being the bottom left tile
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TileModifiers {
+    /// Tiles taking two uncover attempts, the first one cracking the ice
+    pub ice: Vec<(u16, u16)>,
+    /// Tiles which can't be uncovered until every key is uncovered
+    pub locked: Vec<(u16, u16)>,
+    /// Tiles unlocking the locked ones
+    pub keys: Vec<(u16, u16)>,
+    /// Tile pairs, uncovering one tile of a pair uncovering the other one
+    pub teleports: Vec<((u16, u16), (u16, u16))>,
+}
+
+impl TileModifiers {
+    /// Is no modifier set, keys doing nothing without locks
+    pub fn is_empty(&self) -> bool {
+        self.ice.is_empty() && self.locked.is_empty() && self.teleports.is_empty()
+    }
+}
+
+/// Modifiers still applying to the tiles of a board, see `TileModifiers`
+#[derive(Debug, Clone, Default)]
+pub struct BoardModifiers {
+    /// Modifier of each affected tile. Cracked ice, found keys and opened locks are removed
+    pub tiles: HashMap<Coordinates, TileModifier>,
+    /// Paired tile of each teleport
+    pub teleports: HashMap<Coordinates, Coordinates>,
+}
+
+impl BoardModifiers {
+    /// Modifiers of a new board
+    pub fn new(modifiers: &TileModifiers) -> Self {
+        let coords = |(x, y): (u16, u16)| Coordinates { x, y };
+        let mut tiles = HashMap::default();
+        let mut teleports = HashMap::default();
+        for (a, b) in modifiers.teleports.iter().map(|(a, b)| (coords(*a), coords(*b))) {
+            tiles.insert(a, TileModifier::Teleport);
+            tiles.insert(b, TileModifier::Teleport);
+            teleports.insert(a, b);
+            teleports.insert(b, a);
+        }
+        // Keys are only meaningful with locks to open
+        if !modifiers.locked.is_empty() {
+            tiles.extend(modifiers.keys.iter().map(|c| (coords(*c), TileModifier::Key)));
+            tiles.extend(modifiers.locked.iter().map(|c| (coords(*c), TileModifier::Locked)));
+        }
+        tiles.extend(modifiers.ice.iter().map(|c| (coords(*c), TileModifier::Ice)));
+        Self { tiles, teleports }
+    }
+
+    /// Does the tile at `coords` resist an uncover attempt, cracking its ice if any
+    pub fn blocks(&mut self, coords: &Coordinates) -> bool {
+        match self.tiles.get(coords) {
+            Some(TileModifier::Ice) => {
+                self.tiles.remove(coords);
+                true
+            }
+            Some(TileModifier::Locked) => true,
+            _ => false,
+        }
+    }
+
+    /// Does the tile at `coords` stop uncover cascades, ice and locks needing their own attempt
+    pub fn stops_cascade(&self, coords: &Coordinates) -> bool {
+        matches!(self.tiles.get(coords), Some(TileModifier::Ice | TileModifier::Locked))
+    }
+
+    /// Applies the modifier of the tile uncovered at `coords`, returning the teleport to
+    /// uncover along with it
+    pub fn uncovered(&mut self, coords: &Coordinates) -> Option<Coordinates> {
+        match self.tiles.remove(coords)? {
+            TileModifier::Key => {
+                if !self.tiles.values().any(|m| *m == TileModifier::Key) {
+                    self.tiles.retain(|_, m| *m != TileModifier::Locked);
+                }
+                None
+            }
+            TileModifier::Teleport => self.teleports.get(coords).copied(),
+            TileModifier::Ice | TileModifier::Locked => None,
+        }
+    }
+}
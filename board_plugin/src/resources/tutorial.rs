@@ -0,0 +1,76 @@
+use crate::components::Coordinates;
+use serde::{Deserialize, Serialize};
+
+/// Player action expected by a tutorial step
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TutorialAction {
+    /// Uncover the tile
+    Uncover,
+    /// Mark or unmark the tile
+    Mark,
+    /// Chord the numbered tile
+    Chord,
+}
+
+/// Scripted tutorial step
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TutorialStep {
+    /// Highlighted tile
+    pub coords: Coordinates,
+    /// Action to perform on the highlighted tile
+    pub action: TutorialAction,
+    /// Instruction displayed above the highlighted tile, like "Click here"
+    pub message: String,
+}
+
+/// Guided tutorial: the tile of the current step is highlighted and every other input is
+/// blocked until the step is performed. Must be used as a resource, removing it ends the
+/// tutorial
+// We use serde to allow shipping tutorial scripts as files
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TutorialScript {
+    pub steps: Vec<TutorialStep>,
+    /// Index of the current step
+    #[serde(skip)]
+    current: usize,
+}
+
+impl TutorialScript {
+    /// Tutorial starting on its first step
+    pub fn new(steps: Vec<TutorialStep>) -> Self {
+        Self { steps, current: 0 }
+    }
+
+    /// Current step, `None` once the tutorial is finished
+    pub fn current_step(&self) -> Option<&TutorialStep> {
+        self.steps.get(self.current)
+    }
+
+    // Getter for `current`
+    pub fn current_index(&self) -> usize {
+        self.current
+    }
+
+    /// Were all the steps performed
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.steps.len()
+    }
+
+    /// Is the player `action` on `coords` allowed by the current step. Every action is allowed
+    /// once the tutorial is finished
+    pub fn allows(&self, action: TutorialAction, coords: Coordinates) -> bool {
+        self.current_step().map_or(true, |s| s.action == action && s.coords == coords)
+    }
+
+    /// Restarts the tutorial from its first step
+    pub fn restart(&mut self) {
+        self.current = 0;
+    }
+
+    /// Moves to the next step, returning the index of the completed one
+    pub(crate) fn advance(&mut self) -> usize {
+        let completed = self.current;
+        self.current += 1;
+        completed
+    }
+}
@@ -0,0 +1,164 @@
+use crate::components::Coordinates;
+use crate::resources::Board;
+use bevy::prelude::Entity;
+
+/// Player of a versus race
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum Player {
+    /// Plays the main board with the mouse
+    One,
+    /// Plays the rival board with the keyboard cursor or a gamepad
+    Two,
+}
+
+impl Player {
+    /// The other player
+    pub const fn rival(&self) -> Self {
+        match self {
+            Self::One => Self::Two,
+            Self::Two => Self::One,
+        }
+    }
+
+    const fn index(&self) -> usize {
+        match self {
+            Self::One => 0,
+            Self::Two => 1,
+        }
+    }
+}
+
+/// Race progress of a player board
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+pub struct RaceProgress {
+    /// Uncovered safe tiles
+    pub uncovered: usize,
+    /// Safe tiles of the board
+    pub total: usize,
+    /// Did the player uncover a bomb, losing the race
+    pub exploded: bool,
+}
+
+impl RaceProgress {
+    /// Share of the safe tiles uncovered, from 0 to 1
+    pub fn ratio(&self) -> f32 {
+        if self.total == 0 {
+            return 1.;
+        }
+        (self.uncovered as f32 / self.total as f32).min(1.)
+    }
+
+    /// Are all safe tiles uncovered
+    pub fn is_completed(&self) -> bool {
+        !self.exploded && self.uncovered >= self.total
+    }
+}
+
+/// Local versus race of a `BoardOptions::versus` board: player one plays the main board while
+/// player two plays a rival board with the same layout beside it. The first player to complete
+/// their board wins, an explosion handing the win to the other player. Managed by the plugin,
+/// must be used as a resource
+#[derive(Debug)]
+pub struct VersusRace {
+    progress: [RaceProgress; 2],
+    winner: Option<Player>,
+    pub(crate) rival: RivalBoard,
+}
+
+impl VersusRace {
+    /// New race, the rival board progress being counted from its spawned state
+    pub(crate) fn new(rival: RivalBoard) -> Self {
+        let safe = rival.safe_tiles();
+        let mut progress = [RaceProgress { uncovered: 0, total: safe, exploded: false }; 2];
+        progress[Player::Two.index()].uncovered = rival.uncovered_tiles();
+        Self { progress, winner: None, rival }
+    }
+
+    /// Progress of the `player` board
+    pub fn progress(&self, player: Player) -> RaceProgress {
+        self.progress[player.index()]
+    }
+
+    pub(crate) fn set_progress(&mut self, player: Player, progress: RaceProgress) {
+        self.progress[player.index()] = progress;
+    }
+
+    // Getter for `winner`
+    pub fn winner(&self) -> Option<Player> {
+        self.winner
+    }
+
+    /// Board of player two
+    pub fn rival_board(&self) -> &Board {
+        &self.rival.board
+    }
+
+    /// Player winning the race with the current progress, if decided. Completion is checked
+    /// before explosions, so a board completed by its last click still wins
+    pub(crate) fn decide(&mut self) -> Option<Player> {
+        if self.winner.is_some() {
+            return None;
+        }
+        let winner = [Player::One, Player::Two]
+            .into_iter()
+            .find(|p| self.progress(*p).is_completed())
+            .or_else(|| {
+                [Player::One, Player::Two]
+                    .into_iter()
+                    .find(|p| self.progress(*p).exploded)
+                    .map(|p| p.rival())
+            })?;
+        self.winner = Some(winner);
+        Some(winner)
+    }
+}
+
+/// Board of player two, sharing the tile map of the main board with its own covers and flags.
+/// It is a second `Board` so both boards resolve clicks alike, but assists, lives and modifiers
+/// don't apply to it, see `BoardOptions::versus`
+#[derive(Debug)]
+pub(crate) struct RivalBoard {
+    pub board: Board,
+    /// Keyboard and gamepad cursor position
+    pub cursor: Coordinates,
+    /// Cursor sprite
+    pub cursor_entity: Entity,
+    /// Tile size of the spawned tiles, the root being scaled when the board is resized
+    pub tile_size: f32,
+}
+
+impl RivalBoard {
+    /// Uncovers the tile at `coords` and, if it is empty, its whole opening. Flagged tiles stay
+    /// covered. Returns the removed covers and whether a bomb was uncovered
+    pub fn uncover(&mut self, coords: Coordinates) -> (Vec<Entity>, bool) {
+        if self.board.tile_to_uncover(&coords).is_none() {
+            return (Vec::new(), false);
+        }
+        let exploded = self.board.tile_map[coords.y as usize][coords.x as usize].is_bomb();
+        let covers = self.board.uncover_from(coords).into_iter().filter_map(|(_, c)| c).collect();
+        (covers, exploded)
+    }
+
+    /// Uncovers the chord targets of the uncovered number at `coords`, see `Board::chord_targets`
+    pub fn chord(&mut self, coords: Coordinates) -> (Vec<Entity>, bool) {
+        let mut covers = Vec::new();
+        let mut exploded = false;
+        for neighbor in self.board.chord_targets(coords) {
+            let (uncovered, bomb) = self.uncover(neighbor);
+            covers.extend(uncovered);
+            exploded |= bomb;
+        }
+        (covers, exploded)
+    }
+
+    /// Number of safe tiles
+    pub fn safe_tiles(&self) -> usize {
+        let tile_map = &self.board.tile_map;
+        tile_map.tile_count().saturating_sub(tile_map.bomb_count() as usize)
+    }
+
+    /// Number of uncovered tiles, the exploded bomb included
+    pub fn uncovered_tiles(&self) -> usize {
+        self.board.tile_map.tile_count() - self.board.covered_count()
+    }
+}
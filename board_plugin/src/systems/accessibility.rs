@@ -0,0 +1,52 @@
+use crate::events::{
+    BoardCompletedEvent, BombExplodedEvent, LifeLostEvent, TileFlaggedEvent, TileUncoveredEvent,
+    TileUnflaggedEvent, TimeExpiredEvent,
+};
+use crate::resources::{Accessibility, Announcement};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Announces the game state changes of the frame. Openings are announced as a whole, instead of
+/// tile by tile
+pub fn announce_events(
+    board: Res<Board>,
+    mut accessibility: ResMut<Accessibility>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut tile_flagged_evr: EventReader<TileFlaggedEvent>,
+    mut tile_unflagged_evr: EventReader<TileUnflaggedEvent>,
+    mut life_lost_evr: EventReader<LifeLostEvent>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+    mut time_expired_evr: EventReader<TimeExpiredEvent>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+) {
+    let uncovered: Vec<_> = tile_uncovered_evr.iter().filter(|e| !e.tile.is_bomb()).collect();
+    match uncovered.as_slice() {
+        [] => (),
+        [event] => {
+            accessibility
+                .announce(Announcement::TileUncovered { coords: event.coords, tile: event.tile });
+        }
+        events => accessibility.announce(Announcement::TilesUncovered { count: events.len() }),
+    }
+    let flags = tile_flagged_evr
+        .iter()
+        .map(|e| (e.0, true))
+        .chain(tile_unflagged_evr.iter().map(|e| (e.0, false)));
+    for (coords, flagged) in flags {
+        let remaining_mines = board.remaining_mines();
+        accessibility.announce(Announcement::FlagChanged { coords, flagged, remaining_mines });
+    }
+    for event in life_lost_evr.iter() {
+        let (coords, remaining) = (event.coords, event.remaining);
+        accessibility.announce(Announcement::LifeLost { coords, remaining });
+    }
+    for event in bomb_exploded_evr.iter() {
+        accessibility.announce(Announcement::BombExploded { coords: event.coords });
+    }
+    if time_expired_evr.iter().count() > 0 {
+        accessibility.announce(Announcement::TimeExpired);
+    }
+    for event in board_completed_evr.iter() {
+        accessibility.announce(Announcement::BoardCompleted { time: event.time });
+    }
+}
@@ -0,0 +1,13 @@
+use crate::events::PrintBoardEvent;
+use crate::Board;
+use bevy::prelude::*;
+
+pub fn print_board(board: Option<Res<Board>>, mut print_board_evr: EventReader<PrintBoardEvent>) {
+    if print_board_evr.iter().count() == 0 {
+        return;
+    }
+    match board {
+        None => info!("No board to print"),
+        Some(board) => info!("Board:\n{}", board.render_ascii()),
+    }
+}
@@ -0,0 +1,74 @@
+use crate::events::{AutoFlagEvent, TileFlaggedEvent, TileUncoveredEvent, UncoverTileEvent};
+use crate::resources::{BoardOptions, BoardState};
+use crate::Board;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Flags the covered tiles proven to be mines by the numbers around the tiles uncovered this
+/// frame, if enabled in `BoardOptions::auto_flag`
+pub fn auto_flag_mines(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut auto_flag_ewr: EventWriter<AutoFlagEvent>,
+) {
+    let uncovered: Vec<_> = tile_uncovered_evr.iter().map(|e| e.coords).collect();
+    if *board_state != BoardState::Playing || !board_options.map_or(false, |o| o.auto_flag) {
+        return;
+    }
+    // Only the numbers next to uncovered tiles gained information
+    let numbers: HashSet<_> =
+        uncovered.iter().flat_map(|c| board.tile_map.safe_square_at(*c).chain([*c])).collect();
+    let mines: HashSet<_> = numbers.into_iter().flat_map(|c| board.proven_mines(c)).collect();
+    for coords in mines {
+        debug!("Auto flagging proven mine on {}", coords);
+        auto_flag_ewr.send(AutoFlagEvent(coords));
+    }
+}
+
+/// Uncovers the unflagged neighbors of the numbers whose flags match their count, like a chord,
+/// if enabled in `BoardOptions::auto_open`. It runs after the flags of the frame are placed, so
+/// numbers still waiting for their auto flags are left alone until the next frame
+pub fn auto_open_satisfied(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut tile_flagged_evr: EventReader<TileFlaggedEvent>,
+    mut uncover_tile_ewr: EventWriter<UncoverTileEvent>,
+) {
+    let changed: Vec<_> = tile_uncovered_evr
+        .iter()
+        .map(|e| e.coords)
+        .chain(tile_flagged_evr.iter().map(|e| e.0))
+        .collect();
+    if *board_state != BoardState::Playing
+        || !board_options.map_or(false, |o| o.auto_open)
+        || !board.variant.allows_chording()
+    {
+        return;
+    }
+    let numbers: HashSet<_> =
+        changed.iter().flat_map(|c| board.tile_map.safe_square_at(*c).chain([*c])).collect();
+    let mut targets = HashSet::new();
+    for coords in numbers {
+        let count = match board.uncovered_bomb_count(coords) {
+            Some(c) if c > 0 => c as usize,
+            _ => continue,
+        };
+        if board.adjacent_marked_count(coords) != count {
+            continue;
+        }
+        targets.extend(
+            board
+                .tile_map
+                .neighbors(coords)
+                .filter(|c| board.is_covered(c) && board.flags_at(c) == 0),
+        );
+    }
+    for coords in targets {
+        debug!("Auto opening satisfied neighbor {}", coords);
+        uncover_tile_ewr.send(UncoverTileEvent(coords));
+    }
+}
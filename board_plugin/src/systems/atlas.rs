@@ -0,0 +1,136 @@
+use crate::resources::{BoardAssets, BoardOptions, SpriteMaterial, TileAtlas, ATLAS_NUMBERS};
+use crate::systems::export::glyph;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::render::texture::DEFAULT_IMAGE_HANDLE;
+use bevy::sprite::TextureAtlasBuilder;
+
+/// Size in pixels of the plain color and bomb counter cells
+const CELL_PX: u32 = 16;
+/// Size in pixels of a glyph pixel in the bomb counter cells
+const GLYPH_SCALE: u32 = 2;
+
+/// Cover sprite components, plain or drawn from the tile atlas
+pub(crate) type CoverSpriteParts = (
+    Option<&'static mut Sprite>,
+    Option<&'static mut Handle<Image>>,
+    Option<&'static mut TextureAtlasSprite>,
+);
+
+/// Applies a cover material, atlas sprites switching to its packed texture
+pub(crate) fn paint_cover(
+    (sprite, texture, atlas_sprite): (
+        Option<Mut<Sprite>>,
+        Option<Mut<Handle<Image>>>,
+        Option<Mut<TextureAtlasSprite>>,
+    ),
+    material: &SpriteMaterial,
+    atlas: Option<&TileAtlas>,
+) {
+    if let Some(mut sprite) = sprite {
+        sprite.color = material.color;
+    }
+    if let Some(mut texture) = texture {
+        *texture = material.texture.clone();
+    }
+    if let Some(mut sprite) = atlas_sprite {
+        sprite.color = material.color;
+        if let Some(index) = atlas.and_then(|a| a.texture_index(&material.texture)) {
+            sprite.index = index;
+        }
+    }
+}
+
+/// Builds the tile atlas with `BoardOptions::texture_atlas`, once the tile textures are loaded.
+/// Boards spawned beforehand keep plain sprites
+pub fn build_tile_atlas(
+    mut board_assets: ResMut<BoardAssets>,
+    board_options: Option<Res<BoardOptions>>,
+    images: Option<ResMut<Assets<Image>>>,
+    atlases: Option<ResMut<Assets<TextureAtlas>>>,
+    mut failed: Local<bool>,
+) {
+    if *failed || board_assets.atlas.is_some() || !board_options.map_or(false, |o| o.texture_atlas)
+    {
+        return;
+    }
+    // Headless apps render nothing
+    let (mut images, mut atlases) = match (images, atlases) {
+        (Some(images), Some(atlases)) => (images, atlases),
+        _ => return,
+    };
+    let materials = [
+        &board_assets.tile_material,
+        &board_assets.covered_tile_material,
+        &board_assets.hovered_tile_material,
+        &board_assets.pressed_tile_material,
+        &board_assets.bomb_material,
+        &board_assets.armored_bomb_material,
+        &board_assets.anti_mine_material,
+        &board_assets.mega_mine_material,
+        &board_assets.decoy_mine_material,
+    ];
+    let mut textures: Vec<Handle<Image>> = Vec::new();
+    for material in materials {
+        if !textures.contains(&material.texture) {
+            textures.push(material.texture.clone());
+        }
+    }
+    let default = DEFAULT_IMAGE_HANDLE.typed();
+    if textures.iter().any(|t| *t != default && images.get(t).is_none()) {
+        return;
+    }
+    // Plain color materials get a white cell, the 1 pixel default image bleeding into its
+    // neighbors once filtered
+    let white = images.add(cell_image(|_, _| true));
+    let numbers: Vec<_> = ATLAS_NUMBERS.map(|count| images.add(number_image(count))).collect();
+    let packed = |t: &Handle<Image>| if *t == default { white.clone() } else { t.clone() };
+    let mut builder = TextureAtlasBuilder::default();
+    for handle in textures.iter().map(packed).chain(numbers.iter().cloned()) {
+        if let Some(image) = images.get(&handle) {
+            builder.add_texture(handle, image);
+        }
+    }
+    let atlas = match builder.finish(&mut images) {
+        Ok(a) => a,
+        Err(e) => {
+            error!("Failed to build the tile atlas: {:?}", e);
+            *failed = true;
+            return;
+        }
+    };
+    let index = |handle: &Handle<Image>| atlas.get_texture_index(handle).unwrap_or_default();
+    let textures = textures.iter().map(|t| (t.clone(), index(&packed(t)))).collect();
+    let numbers = numbers.iter().map(index).collect();
+    info!("Built the tile atlas");
+    board_assets.atlas = Some(TileAtlas { atlas: atlases.add(atlas), textures, numbers });
+}
+
+/// White bomb counter cell, the sprite color tinting it
+fn number_image(count: i8) -> Image {
+    let text = count.to_string();
+    let width = text.len() as u32 * 4 * GLYPH_SCALE - GLYPH_SCALE;
+    let (left, top) = (CELL_PX.saturating_sub(width) / 2, (CELL_PX - 5 * GLYPH_SCALE) / 2);
+    cell_image(|x, y| {
+        let (x, y) = (x.wrapping_sub(left) / GLYPH_SCALE, y.wrapping_sub(top) / GLYPH_SCALE);
+        let (index, column) = (x as usize / 4, x % 4);
+        match text.chars().nth(index) {
+            Some(c) if column < 3 && y < 5 => glyph(c)[y as usize] & (0b100 >> column) != 0,
+            _ => false,
+        }
+    })
+}
+
+/// Cell image with white pixels where `lit` holds, from the top left corner
+fn cell_image(lit: impl Fn(u32, u32) -> bool) -> Image {
+    let data = (0..CELL_PX)
+        .flat_map(|y| (0..CELL_PX).map(move |x| (x, y)))
+        .flat_map(|(x, y)| if lit(x, y) { [255; 4] } else { [255, 255, 255, 0] })
+        .collect();
+    Image::new(
+        Extent3d { width: CELL_PX, height: CELL_PX, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
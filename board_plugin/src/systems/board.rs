@@ -0,0 +1,481 @@
+use crate::events::{
+    BoardCompletedEvent, BoardGeneratedEvent, BoardGenerationFailedEvent, BoardReadyEvent,
+    LevelCompletedEvent, RestartBoardEvent, RetryBoardEvent,
+};
+use crate::resources::{
+    Board, BoardAssets, BoardGeometry, BoardLoading, BoardOptions, BoardState, BoardWindow,
+    Countdown, EndlessProgress, GameStats, GameTimer, HoveredTile, KeyboardCursor, Lives,
+    LostBoard, PendingBoard, PowerUps, RestoredFlags, RevealSequence, TileMap, VersusRace,
+};
+use crate::systems::layout::{scale_factor, window_size};
+use crate::systems::spawn::{
+    release_tiles, spawn_board, spawn_board_tiles, spawn_loading_indicator,
+};
+use crate::{Coordinates, Uncover};
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use futures_lite::future;
+
+/// Delay in seconds before an endless mode level is replaced by the next one
+const LEVEL_TRANSITION_DELAY: f32 = 1.5;
+
+/// System to generate the complete board. Large boards are generated in a background task,
+/// see `BoardOptions::background_generation`
+pub fn create_board(
+    mut commands: Commands,
+    board_options: Option<Res<BoardOptions>>,
+    pool: Res<AsyncComputeTaskPool>,
+    board_assets: Res<BoardAssets>,
+    window: Res<WindowDescriptor>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    mut hovered: ResMut<HoveredTile>,
+    tiles: Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    mut board_generated_ewr: EventWriter<BoardGeneratedEvent>,
+    mut generation_failed_ewr: EventWriter<BoardGenerationFailedEvent>,
+) {
+    let options = match board_options {
+        None => BoardOptions::default(), // If no options is set we use the default one
+        Some(o) => o.clone(),
+    };
+    let options = match checked_options(options, &mut generation_failed_ewr) {
+        None => return,
+        Some(o) => o,
+    };
+    // Endless runs restart from their first level
+    if options.endless.is_some() {
+        commands.insert_resource(EndlessProgress::new(options.clone()));
+    }
+    if generates_in_background(&options) {
+        info!("Generating board in the background");
+        commands.insert_resource(PendingBoard {
+            task: spawn_generation(&pool, options),
+            replace_options: true,
+            restored_flags: None,
+        });
+        return;
+    }
+    replace_board(
+        &mut commands,
+        None,
+        &tiles,
+        &options,
+        options.build_map(),
+        &board_assets,
+        (&window, &board_window, &windows),
+        &mut hovered,
+        &mut board_generated_ewr,
+    );
+    commands.insert_resource(options);
+}
+
+/// Checks the `options` of a new board, clamping them in lenient mode. Invalid options are
+/// logged and reported by a `BoardGenerationFailedEvent`
+fn checked_options(
+    options: BoardOptions,
+    generation_failed_ewr: &mut EventWriter<BoardGenerationFailedEvent>,
+) -> Option<BoardOptions> {
+    match options.validate() {
+        Ok(()) => Some(options),
+        Err(error) if options.lenient => {
+            warn!("Clamping the board options: {}", error);
+            Some(options.clamped())
+        }
+        Err(error) => {
+            error!("Board generation failed: {}", error);
+            generation_failed_ewr.send(BoardGenerationFailedEvent { error });
+            None
+        }
+    }
+}
+
+/// System spawning the tiles of a new board left over by `BoardOptions::spawn_budget`, and
+/// sending `BoardReadyEvent` once every tile is spawned
+pub fn load_board(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    loading: Option<ResMut<BoardLoading>>,
+    mut board_state: ResMut<BoardState>,
+    mut board_ready_ewr: EventWriter<BoardReadyEvent>,
+) {
+    let mut loading = match loading {
+        None => return,
+        Some(l) => l,
+    };
+    if !loading.is_done() {
+        let (padding, budget, safe_start) = board_options
+            .map_or((0., None, false), |o| (o.tile_padding, o.spawn_budget, o.safe_start));
+        let budget = budget.map_or(usize::MAX, |budget| budget.max(1) as usize);
+        let board = &mut *board;
+        spawn_board_tiles(
+            &mut commands,
+            board.entity,
+            &board.tile_map,
+            geometry.tile_size,
+            padding,
+            &board_assets,
+            &board.mine_kinds,
+            &mut Vec::new(),
+            &mut board.covered_tiles,
+            &mut loading,
+            budget,
+        );
+        debug!("Spawned {}/{} board tiles", loading.spawned, loading.total);
+        if !loading.is_done() {
+            return;
+        }
+        if *board_state == BoardState::Loading {
+            *board_state = BoardState::Playing;
+        }
+        if safe_start {
+            if let Some(entity) = loading.safe_start {
+                commands.entity(entity).insert(Uncover);
+            }
+        }
+    }
+    board_ready_ewr.send(BoardReadyEvent);
+    commands.remove_resource::<BoardLoading>();
+}
+
+pub fn cleanup_board(
+    board: Option<Res<Board>>,
+    progress: Option<Res<EndlessProgress>>,
+    mut hovered: ResMut<HoveredTile>,
+    mut commands: Commands,
+) {
+    // The state may be left before any board was spawned
+    if let Some(board) = board {
+        commands.entity(board.entity).despawn_recursive();
+    }
+    // The options are restored to the first endless level
+    if let Some(progress) = progress {
+        commands.insert_resource(progress.base.clone());
+        commands.remove_resource::<EndlessProgress>();
+    }
+    commands.remove_resource::<Board>();
+    commands.remove_resource::<BoardGeometry>();
+    commands.remove_resource::<BoardState>();
+    commands.remove_resource::<Lives>();
+    commands.remove_resource::<Countdown>();
+    commands.remove_resource::<PowerUps>();
+    commands.remove_resource::<RevealSequence>();
+    commands.remove_resource::<LostBoard>();
+    commands.remove_resource::<PendingBoard>();
+    commands.remove_resource::<BoardLoading>();
+    commands.remove_resource::<VersusRace>();
+    hovered.0 = None;
+    commands.insert_resource(KeyboardCursor::default());
+}
+
+/// System replacing a completed endless mode board with the next level
+pub fn advance_endless_level(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+    window: Res<WindowDescriptor>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    progress: Option<ResMut<EndlessProgress>>,
+    // Grouped to stay within the system parameter limit
+    (mut timer, mut stats): (ResMut<GameTimer>, ResMut<GameStats>),
+    mut hovered: ResMut<HoveredTile>,
+    tiles: Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    mut transition: Local<Option<Timer>>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut level_completed_ewr: EventWriter<LevelCompletedEvent>,
+    mut board_generated_ewr: EventWriter<BoardGeneratedEvent>,
+    mut generation_failed_ewr: EventWriter<BoardGenerationFailedEvent>,
+) {
+    let mut progress = match progress {
+        None => return,
+        Some(p) => p,
+    };
+    for event in board_completed_evr.iter() {
+        progress.score += event.bbbv as u64 * progress.level as u64 + event.bonus as u64;
+        info!("Level {} completed, score: {}", progress.level, progress.score);
+        level_completed_ewr.send(LevelCompletedEvent { level: progress.level });
+        *transition = Some(Timer::from_seconds(LEVEL_TRANSITION_DELAY, false));
+    }
+    // The completed board stays displayed for a moment
+    match transition.as_mut() {
+        Some(t) if t.tick(time.delta()).finished() => *transition = None,
+        _ => return,
+    }
+    progress.level += 1;
+    let options = match checked_options(progress.level_options(), &mut generation_failed_ewr) {
+        Some(o) => o,
+        // The completed level stays displayed
+        None => {
+            progress.level -= 1;
+            return;
+        }
+    };
+    info!("Starting level {}", progress.level);
+    replace_board(
+        &mut commands,
+        Some(&*board),
+        &tiles,
+        &options,
+        options.build_map(),
+        &board_assets,
+        (&window, &board_window, &windows),
+        &mut hovered,
+        &mut board_generated_ewr,
+    );
+    commands.insert_resource(options);
+    timer.start();
+    stats.start_game();
+}
+
+/// System replacing the board with a new one on `RestartBoardEvent`. Large boards are
+/// generated in a background task, see `BoardOptions::background_generation`
+pub fn restart_board(
+    mut commands: Commands,
+    // Grouped to stay within the system parameter limit
+    (board, geometry): (Res<Board>, Res<BoardGeometry>),
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    window: Res<WindowDescriptor>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    progress: Option<Res<EndlessProgress>>,
+    (pending, pool): (Option<Res<PendingBoard>>, Res<AsyncComputeTaskPool>),
+    mut timer: ResMut<GameTimer>,
+    mut stats: ResMut<GameStats>,
+    mut hovered: ResMut<HoveredTile>,
+    tiles: Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    mut restart_board_evr: EventReader<RestartBoardEvent>,
+    (mut board_generated_ewr, mut generation_failed_ewr): (
+        EventWriter<BoardGeneratedEvent>,
+        EventWriter<BoardGenerationFailedEvent>,
+    ),
+) {
+    if restart_board_evr.iter().count() == 0 {
+        return;
+    }
+    // Endless runs restart from their first level
+    let options = match (progress, board_options) {
+        (Some(progress), _) => progress.base.clone(),
+        (None, Some(options)) => options.clone(),
+        (None, None) => BoardOptions::default(),
+    };
+    // Invalid options keep the current board
+    let options = match checked_options(options, &mut generation_failed_ewr) {
+        None => return,
+        Some(o) => o,
+    };
+    if options.endless.is_some() {
+        commands.insert_resource(EndlessProgress::new(options.clone()));
+    }
+    if generates_in_background(&options) {
+        info!("Generating board in the background");
+        if pending.is_none() {
+            spawn_loading_indicator(&mut commands, &board, &geometry, &board_assets);
+        }
+        // A pending board is dropped, cancelling its task
+        commands.insert_resource(PendingBoard {
+            task: spawn_generation(&pool, options),
+            replace_options: true,
+            restored_flags: None,
+        });
+        return;
+    }
+    let tile_map = options.build_map();
+    info!("Restarting board");
+    replace_board(
+        &mut commands,
+        Some(&*board),
+        &tiles,
+        &options,
+        tile_map,
+        &board_assets,
+        (&window, &board_window, &windows),
+        &mut hovered,
+        &mut board_generated_ewr,
+    );
+    commands.insert_resource(options);
+    timer.start();
+    stats.start_game();
+}
+
+/// System replacing a lost board with the same layout on `RetryBoardEvent`, the options
+/// keeping their own seed for the next restarts. Large boards are generated in a background
+/// task like on restart
+pub fn retry_board(
+    mut commands: Commands,
+    // Grouped to stay within the system parameter limit
+    (board, geometry): (Res<Board>, Res<BoardGeometry>),
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    window: Res<WindowDescriptor>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    lost_board: Option<Res<LostBoard>>,
+    (pending, pool): (Option<Res<PendingBoard>>, Res<AsyncComputeTaskPool>),
+    mut timer: ResMut<GameTimer>,
+    mut stats: ResMut<GameStats>,
+    mut hovered: ResMut<HoveredTile>,
+    tiles: Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    mut retry_board_evr: EventReader<RetryBoardEvent>,
+    (mut board_generated_ewr, mut generation_failed_ewr): (
+        EventWriter<BoardGeneratedEvent>,
+        EventWriter<BoardGenerationFailedEvent>,
+    ),
+) {
+    let restore_flags = match retry_board_evr.iter().last() {
+        None => return,
+        Some(e) => e.restore_flags,
+    };
+    let lost_board = match lost_board {
+        None => {
+            warn!("Only lost boards can be retried");
+            return;
+        }
+        Some(b) => b,
+    };
+    let options = BoardOptions {
+        seed: Some(lost_board.seed),
+        ..board_options.map(|o| o.clone()).unwrap_or_default()
+    };
+    let options = match checked_options(options, &mut generation_failed_ewr) {
+        None => return,
+        Some(o) => o,
+    };
+    info!("Retrying board with seed {}", lost_board.seed);
+    let restored_flags = restore_flags.then(|| RestoredFlags(lost_board.flags.clone()));
+    if generates_in_background(&options) {
+        info!("Generating board in the background");
+        if pending.is_none() {
+            spawn_loading_indicator(&mut commands, &board, &geometry, &board_assets);
+        }
+        commands.insert_resource(PendingBoard {
+            task: spawn_generation(&pool, options),
+            replace_options: false,
+            restored_flags,
+        });
+        return;
+    }
+    if let Some(flags) = restored_flags {
+        commands.insert_resource(flags);
+    }
+    replace_board(
+        &mut commands,
+        Some(&*board),
+        &tiles,
+        &options,
+        options.build_map(),
+        &board_assets,
+        (&window, &board_window, &windows),
+        &mut hovered,
+        &mut board_generated_ewr,
+    );
+    timer.start();
+    stats.start_game();
+}
+
+/// Is the board of `options` large enough to be generated in a background task, see
+/// `BoardOptions::background_generation`
+fn generates_in_background(options: &BoardOptions) -> bool {
+    let (width, height) = options.map_size;
+    let tile_count = width as u32 * height as u32;
+    options.background_generation.map_or(false, |min| tile_count >= min)
+}
+
+/// Generates the map of `options` on the async compute pool
+fn spawn_generation(
+    pool: &AsyncComputeTaskPool,
+    options: BoardOptions,
+) -> Task<(BoardOptions, TileMap)> {
+    pool.spawn(async move {
+        let tile_map = options.build_map();
+        (options, tile_map)
+    })
+}
+
+/// System spawning the board generated in the background by `create_board`, `restart_board`
+/// or `retry_board` once ready, replacing the current board if any
+pub fn spawn_pending_board(
+    mut commands: Commands,
+    board: Option<Res<Board>>,
+    board_assets: Res<BoardAssets>,
+    window: Res<WindowDescriptor>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    pending: Option<ResMut<PendingBoard>>,
+    mut timer: ResMut<GameTimer>,
+    mut stats: ResMut<GameStats>,
+    mut hovered: ResMut<HoveredTile>,
+    tiles: Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    mut board_generated_ewr: EventWriter<BoardGeneratedEvent>,
+) {
+    let mut pending = match pending {
+        None => return,
+        Some(p) => p,
+    };
+    let (options, tile_map) = match future::block_on(future::poll_once(&mut pending.task)) {
+        None => return,
+        Some(generated) => generated,
+    };
+    commands.remove_resource::<PendingBoard>();
+    if board.is_some() {
+        info!("Replacing board with the generated one");
+    }
+    if let Some(flags) = pending.restored_flags.take() {
+        commands.insert_resource(flags);
+    }
+    replace_board(
+        &mut commands,
+        board.as_deref(),
+        &tiles,
+        &options,
+        tile_map,
+        &board_assets,
+        (&window, &board_window, &windows),
+        &mut hovered,
+        &mut board_generated_ewr,
+    );
+    if pending.replace_options {
+        commands.insert_resource(options);
+    }
+    timer.start();
+    stats.start_game();
+}
+
+/// Spawns the board of `tile_map`, replacing the current `board` if any: its tile entities are
+/// reused by the new board, and the hovered tile, keyboard cursor and loss of the previous board
+/// are reset. Shared by every system spawning a board
+fn replace_board(
+    commands: &mut Commands,
+    board: Option<&Board>,
+    tiles: &Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+    options: &BoardOptions,
+    tile_map: TileMap,
+    board_assets: &BoardAssets,
+    (window, board_window, windows): (&WindowDescriptor, &BoardWindow, &Windows),
+    hovered: &mut HoveredTile,
+    board_generated_ewr: &mut EventWriter<BoardGeneratedEvent>,
+) {
+    let mut pooled = Vec::new();
+    if let Some(board) = board {
+        pooled = release_tiles(commands, board, tiles);
+        commands.entity(board.entity).despawn_recursive();
+    }
+    commands.remove_resource::<RevealSequence>();
+    commands.remove_resource::<LostBoard>();
+    hovered.0 = None;
+    commands.insert_resource(KeyboardCursor::default());
+    spawn_board(
+        commands,
+        options,
+        tile_map,
+        pooled,
+        board_assets,
+        window_size(window, board_window, windows),
+        scale_factor(window, board_window, windows),
+        board_generated_ewr,
+    );
+}
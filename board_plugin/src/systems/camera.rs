@@ -0,0 +1,57 @@
+use crate::events::{FitBoardEvent, FocusTileEvent};
+use crate::resources::{BoardGeometry, BoardOptions, BoardWindow};
+use crate::{Easing, Tween, TweenTarget};
+use bevy::prelude::*;
+
+/// Duration in seconds of the camera moves
+const CAMERA_MOVE_DURATION: f32 = 0.4;
+/// Board size multiplier leaving a margin around fitted boards
+const FIT_MARGIN: f32 = 1.1;
+
+/// Smoothly moves the board camera over the tile of a `FocusTileEvent`, keeping its zoom, or
+/// to the board center on a `FitBoardEvent`, zooming out until the board fits the window.
+/// Events are ignored unless `BoardOptions::camera_drag` is enabled
+pub fn frame_camera(
+    mut commands: Commands,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    mut focus_tile_evr: EventReader<FocusTileEvent>,
+    mut fit_board_evr: EventReader<FitBoardEvent>,
+    cameras: Query<(Entity, &Camera, &Transform)>,
+) {
+    let focus = focus_tile_evr.iter().last().map(|e| e.0);
+    let fit = fit_board_evr.iter().count() > 0;
+    if (focus.is_none() && !fit) || board_options.map_or(true, |o| o.camera_drag.is_none()) {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    for (entity, camera, transform) in cameras.iter() {
+        if !board_window.is_board_camera(camera) {
+            continue;
+        }
+        let mut to = *transform;
+        if fit {
+            // Rotated views show their bounding box
+            let (sin, cos) = geometry.view.rotation.to_radians().sin_cos();
+            let (sin, cos) = (sin.abs(), cos.abs());
+            let size = geometry.bounds.size;
+            let size = Vec2::new(size.x * cos + size.y * sin, size.x * sin + size.y * cos);
+            let window_size = Vec2::new(window.width(), window.height());
+            let zoom = (size * FIT_MARGIN / window_size).max_element().max(1.);
+            let center = geometry.bounds.center();
+            to.translation = center.extend(to.translation.z);
+            to.scale = Vec3::new(zoom, zoom, to.scale.z);
+        }
+        if let Some(coords) = focus {
+            let center = geometry.tile_center(coords);
+            to.translation = center.extend(to.translation.z);
+        }
+        let target = TweenTarget::Transform { from: *transform, to };
+        commands.entity(entity).insert(Tween::new(CAMERA_MOVE_DURATION, target, Easing::QuadOut));
+    }
+}
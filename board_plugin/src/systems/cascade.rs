@@ -0,0 +1,44 @@
+use crate::components::CascadeInProgress;
+use crate::resources::{Cascade, Tile};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+use bevy::tasks::AsyncComputeTaskPool;
+use bevy::utils::HashSet;
+use futures_lite::future;
+use std::sync::Arc;
+
+/// Spawns the flood fill tasks of the openings requested by `uncover_tiles`, and hands their
+/// result back to it through `Board::openings` once finished
+pub fn compute_cascades(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    pool: Res<AsyncComputeTaskPool>,
+    mut cascades: Query<(Entity, &mut CascadeInProgress)>,
+) {
+    for (entity, mut cascade) in cascades.iter_mut() {
+        let origin = cascade.origin;
+        if cascade.task.is_none() {
+            // The task shares the tile map, moving mines getting their own copy, and stops at the
+            // ice and locked tiles like `Board::cascades_to`
+            let tile_map = Arc::clone(&board.tile_map);
+            let modifiers = &board.modifiers;
+            let stops: HashSet<Coordinates> =
+                modifiers.tiles.keys().copied().filter(|c| modifiers.stops_cascade(c)).collect();
+            let empty_only = board.cascade == Cascade::EmptyOnly;
+            cascade.task = Some(pool.spawn(async move {
+                let mut opening = tile_map.opening_within(origin, |c| stops.contains(c));
+                if empty_only {
+                    opening.retain(|c| tile_map[c.y as usize][c.x as usize] == Tile::Empty);
+                }
+                opening
+            }));
+        }
+        let finished =
+            cascade.task.as_mut().and_then(|task| future::block_on(future::poll_once(task)));
+        if let Some(opening) = finished {
+            debug!(tiles = opening.len(), "Computed opening from {}", origin);
+            board.openings.push((origin, opening));
+            commands.entity(entity).despawn();
+        }
+    }
+}
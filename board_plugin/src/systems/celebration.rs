@@ -0,0 +1,122 @@
+use crate::components::{Confetti, Coordinates, Particle};
+use crate::events::BoardCompletedEvent;
+use crate::resources::{BoardAssets, BoardGeometry, BoardOptions};
+use crate::Board;
+use bevy::input::touch::Touches;
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+/// Duration in seconds of the tile color wave
+const WAVE_DURATION: f32 = 1.5;
+/// Confetti fall acceleration, in tiles per second squared. Drag caps the fall speed to a third
+const CONFETTI_GRAVITY: f32 = 20.;
+
+/// Celebrates board completions with confetti falling over the board and a color wave over the
+/// tiles, as set by `BoardOptions::celebration`. Any key, click or touch ends the celebration
+pub fn celebrate(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    keys: Res<Input<KeyCode>>,
+    buttons: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    mut wave: Local<Option<Timer>>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    confetti: Query<Entity, With<Confetti>>,
+    mut tiles: Query<(&Coordinates, Option<&mut Sprite>, Option<&mut TextureAtlasSprite>)>,
+) {
+    if board_completed_evr.iter().count() > 0 {
+        if let Some(options) = board_options.and_then(|o| o.celebration) {
+            spawn_confetti(&mut commands, &geometry, options.confetti_count, options.duration);
+            if options.color_wave {
+                *wave = Some(Timer::from_seconds(WAVE_DURATION, false));
+            }
+        }
+    }
+    let dismissed = keys.get_just_pressed().next().is_some()
+        || buttons.get_just_pressed().next().is_some()
+        || touches.iter_just_pressed().next().is_some();
+    if dismissed {
+        for entity in confetti.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    let timer = match wave.as_mut() {
+        None => return,
+        Some(t) => t,
+    };
+    timer.tick(time.delta());
+    let base = board_assets.tile_material.color;
+    if dismissed || timer.finished() {
+        for (_, sprite, atlas_sprite) in tiles.iter_mut() {
+            set_color(sprite, atlas_sprite, base);
+        }
+        *wave = None;
+        return;
+    }
+    // The wave crosses the board diagonally from the bottom left corner, each tile lighting up
+    // then fading back in half of the wave duration
+    let span = (board.tile_map.width() + board.tile_map.height()).max(1) as f32;
+    let progress = timer.percent() * 2.;
+    for (coords, sprite, atlas_sprite) in tiles.iter_mut() {
+        let phase = (coords.x as f32 + coords.y as f32) / span;
+        let local = progress - phase;
+        let strength =
+            if (0. ..1.).contains(&local) { (local * std::f32::consts::PI).sin() } else { 0. };
+        set_color(sprite, atlas_sprite, mix(base, Color::hsl(phase * 360., 0.8, 0.7), strength));
+    }
+}
+
+/// Tints a tile whether it is drawn from the tile atlas or not
+fn set_color(
+    sprite: Option<Mut<Sprite>>,
+    atlas_sprite: Option<Mut<TextureAtlasSprite>>,
+    color: Color,
+) {
+    if let Some(mut sprite) = sprite {
+        sprite.color = color;
+    }
+    if let Some(mut sprite) = atlas_sprite {
+        sprite.color = color;
+    }
+}
+
+/// Spawns confetti over the board, staggered above its top edge so they keep falling in
+fn spawn_confetti(commands: &mut Commands, geometry: &BoardGeometry, count: usize, duration: f32) {
+    let mut rng = thread_rng();
+    let (min, max) = (geometry.bounds.position, geometry.bounds.position + geometry.bounds.size);
+    for _ in 0..count {
+        let position = Vec2::new(
+            rng.gen_range(min.x..max.x),
+            max.y + rng.gen_range(0. ..geometry.bounds.size.y),
+        );
+        commands
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite {
+                    color: Color::hsl(rng.gen_range(0. ..360.), 0.8, 0.6),
+                    custom_size: Some(Vec2::new(geometry.tile_size / 4., geometry.tile_size / 6.)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(position.extend(20.)).with_rotation(
+                    Quat::from_rotation_z(rng.gen_range(0. ..std::f32::consts::TAU)),
+                ),
+                ..Default::default()
+            })
+            .insert(Particle {
+                velocity: Vec2::new(rng.gen_range(-1. ..1.) * geometry.tile_size, 0.),
+                gravity: CONFETTI_GRAVITY * geometry.tile_size,
+                lifetime: Timer::from_seconds(duration * rng.gen_range(0.7..1.), false),
+            })
+            .insert(Confetti)
+            .insert(Name::new("Confetti"));
+    }
+}
+
+fn mix(from: Color, to: Color, amount: f32) -> Color {
+    let (from, to) = (from.as_rgba_f32(), to.as_rgba_f32());
+    let channel = |i: usize| from[i] + (to[i] - from[i]) * amount;
+    Color::rgba(channel(0), channel(1), channel(2), channel(3))
+}
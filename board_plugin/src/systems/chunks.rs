@@ -0,0 +1,117 @@
+use crate::components::Flag;
+use crate::resources::{Board, BoardAssets, BoardGeometry, BoardOptions, BoardWindow};
+use crate::systems::mark::flag_bundle;
+use crate::systems::spawn::spawn_tiles;
+use crate::Coordinates;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::utils::{HashMap, HashSet};
+
+/// System spawning the chunks of a chunked board in view of the camera, and despawning the
+/// chunks out of view
+pub fn stream_chunks(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform, &OrthographicProjection)>,
+) {
+    let board = &mut *board;
+    let chunks = match board.chunks.as_mut() {
+        None => return,
+        Some(c) => c,
+    };
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let (transform, projection) =
+        match cameras.iter().find(|(camera, _, _)| board_window.is_board_camera(camera)) {
+            None => return,
+            Some((_, transform, projection)) => (transform, projection),
+        };
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    // Camera view in board space, with a one chunk margin to spawn chunks before they show
+    let half_view = Vec2::new(window.width(), window.height()) / 2. * projection.scale;
+    let center = transform.translation.xy() - geometry.bounds.position;
+    let chunk_size = chunks.size() as f32 * geometry.tile_size;
+    let (min, max) = ((center - half_view) / chunk_size - 1., (center + half_view) / chunk_size);
+    let chunk_count = (
+        (board.tile_map.width() + chunks.size() - 1) / chunks.size(),
+        (board.tile_map.height() + chunks.size() - 1) / chunks.size(),
+    );
+    let visible: HashSet<_> = (min.y.max(0.) as u16..=(max.y.max(0.) as u16).min(chunk_count.1))
+        .flat_map(|y| {
+            (min.x.max(0.) as u16..=(max.x.max(0.) as u16).min(chunk_count.0)).map(move |x| (x, y))
+        })
+        .filter(|(x, y)| *x < chunk_count.0 && *y < chunk_count.1)
+        .collect();
+
+    // Chunks out of view keep their covered tiles by coordinates only
+    let hidden: Vec<_> = chunks.spawned.keys().filter(|k| !visible.contains(*k)).copied().collect();
+    for key in hidden {
+        if let Some(entity) = chunks.spawned.remove(&key) {
+            commands.entity(entity).despawn_recursive();
+        }
+        let (min, max) = chunks.area(key, &board.tile_map);
+        for coords in (min.y..max.y).flat_map(|y| (min.x..max.x).map(move |x| Coordinates { x, y }))
+        {
+            if board.covered_tiles.remove(&coords).is_some() {
+                chunks.covered.insert(coords);
+            }
+        }
+    }
+    for key in visible {
+        if chunks.is_spawned(key) {
+            continue;
+        }
+        let area = chunks.area(key, &board.tile_map);
+        let mut covered_tiles = HashMap::new();
+        commands.entity(board.entity).with_children(|parent| {
+            let entity = parent
+                .spawn()
+                .insert(Name::new(format!("Chunk ({}, {})", key.0, key.1)))
+                .insert(Transform::default())
+                .insert(GlobalTransform::default())
+                .with_children(|parent| {
+                    spawn_tiles(
+                        parent,
+                        &board.tile_map,
+                        area,
+                        geometry.tile_size,
+                        padding,
+                        &board_assets,
+                        &board.mine_kinds,
+                        |coords| chunks.covered.contains(coords),
+                        &board.detonated_tiles,
+                        &mut covered_tiles,
+                        &mut None,
+                    );
+                })
+                .id();
+            chunks.spawned.insert(key, entity);
+        });
+        for (coords, entity) in covered_tiles {
+            chunks.covered.remove(&coords);
+            board.covered_tiles.insert(coords, entity);
+            // Flags are restored without their drop animation
+            let flags = board.marked_tiles.get(&coords).copied().unwrap_or_default();
+            commands.entity(entity).with_children(|parent| {
+                for index in 0..flags {
+                    parent
+                        .spawn_bundle(flag_bundle(
+                            &board_assets,
+                            geometry.tile_size,
+                            padding,
+                            index,
+                        ))
+                        .insert(Flag)
+                        .insert(Name::new("Flag"));
+                }
+            });
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::components::Cursor;
+use crate::events::{InputSource, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::resources::{BoardGeometry, BoardState, InputBindings, KeyboardCursor, VersusRace};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+
+pub fn keyboard_cursor_handling(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    bindings: Res<InputBindings>,
+    keys: Res<Input<KeyCode>>,
+    mut cursor: ResMut<KeyboardCursor>,
+    race: Option<Res<VersusRace>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    // The keyboard plays the rival board of versus races
+    if *board_state != BoardState::Playing || race.is_some() {
+        return;
+    }
+    let (width, height) = (board.tile_map.width(), board.tile_map.height());
+    let delta: (i8, i8) = [
+        (bindings.cursor_up, (0, 1)),
+        (bindings.cursor_down, (0, -1)),
+        (bindings.cursor_left, (-1, 0)),
+        (bindings.cursor_right, (1, 0)),
+    ]
+    .iter()
+    .filter(|(key, _)| keys.just_pressed(*key))
+    .fold((0, 0), |(x, y), (_, (dx, dy))| (x + dx, y + dy));
+    if delta != (0, 0) {
+        // The cursor appears at the board center and stays in bounds
+        cursor.0 = Some(match cursor.0 {
+            None => Coordinates { x: width / 2, y: height / 2 },
+            Some(Coordinates { x, y }) => Coordinates {
+                x: (x as i32 + delta.0 as i32).clamp(0, width as i32 - 1) as u16,
+                y: (y as i32 + delta.1 as i32).clamp(0, height as i32 - 1) as u16,
+            },
+        });
+    }
+    let coords = match cursor.0 {
+        None => return,
+        Some(c) => c,
+    };
+    if keys.just_pressed(bindings.cursor_uncover) {
+        info!("Trying to uncover tile on {}", coords);
+        let source = InputSource::Keyboard(bindings.cursor_uncover);
+        tile_trigger_ewr.send(TileTriggerEvent(coords, source));
+    }
+    if keys.just_pressed(bindings.cursor_flag) {
+        info!("Trying to mark tile on {}", coords);
+        tile_mark_ewr.send(TileMarkEvent(coords, InputSource::Keyboard(bindings.cursor_flag)));
+    }
+    if keys.just_pressed(bindings.cursor_chord) {
+        info!("Trying to chord tile on {}", coords);
+        let source = InputSource::Keyboard(bindings.cursor_chord);
+        tile_chord_ewr.send(TileChordEvent(coords, source));
+    }
+}
+
+pub fn display_keyboard_cursor(
+    geometry: Res<BoardGeometry>,
+    cursor: Res<KeyboardCursor>,
+    mut sprites: Query<(&mut Transform, &mut Sprite, &mut Visibility), With<Cursor>>,
+) {
+    for (mut transform, mut sprite, mut visibility) in sprites.iter_mut() {
+        visibility.is_visible = cursor.0.is_some();
+        if let Some(coords) = cursor.0 {
+            let size = geometry.tile_size;
+            transform.translation.x = coords.x as f32 * size + size / 2.;
+            transform.translation.y = coords.y as f32 * size + size / 2.;
+            sprite.custom_size = Some(Vec2::splat(size));
+        }
+    }
+}
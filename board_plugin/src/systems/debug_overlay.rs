@@ -0,0 +1,129 @@
+use crate::components::{DebugCounts, DebugOverlay};
+use crate::resources::tile::Tile;
+use crate::resources::{BoardAssets, BoardGeometry};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+
+/// Key toggling the debug overlay
+const DEBUG_OVERLAY_KEY: KeyCode = KeyCode::F3;
+/// Golden angle in degrees, giving distinct hues to consecutive regions
+const REGION_HUE_STEP: f32 = 137.5;
+
+/// Toggles the debug overlay, displaying bombs, connected empty regions and tile counts over the
+/// board. The overlay is respawned over regenerated boards while enabled
+pub fn debug_overlay(
+    mut commands: Commands,
+    keys: Res<Input<KeyCode>>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_assets: Res<BoardAssets>,
+    mut enabled: Local<bool>,
+    overlays: Query<Entity, With<DebugOverlay>>,
+    mut counts: Query<&mut Text, With<DebugCounts>>,
+) {
+    if keys.just_pressed(DEBUG_OVERLAY_KEY) {
+        *enabled = !*enabled;
+        info!("Debug overlay {}", if *enabled { "enabled" } else { "disabled" });
+    }
+    if !*enabled {
+        for entity in overlays.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    if overlays.is_empty() {
+        spawn_overlay(&mut commands, &board, &geometry, &board_assets);
+        return;
+    }
+    for mut text in counts.iter_mut() {
+        text.sections[0].value = counts_label(&board);
+    }
+}
+
+fn counts_label(board: &Board) -> String {
+    format!(
+        "Covered: {} Uncovered: {} Flags: {}",
+        board.covered_count(),
+        board.uncovered_tiles(),
+        board.marked_tiles.values().map(|f| *f as usize).sum::<usize>()
+    )
+}
+
+fn spawn_overlay(
+    commands: &mut Commands,
+    board: &Board,
+    geometry: &BoardGeometry,
+    board_assets: &BoardAssets,
+) {
+    let tile_map = &board.tile_map;
+    let size = geometry.tile_size;
+    let marker = |parent: &mut ChildBuilder, coords: Coordinates, color: Color| {
+        parent.spawn_bundle(SpriteBundle {
+            sprite: Sprite { color, custom_size: Some(Vec2::splat(size)), ..Default::default() },
+            transform: Transform::from_xyz(
+                coords.x as f32 * size + size / 2.,
+                coords.y as f32 * size + size / 2.,
+                0.,
+            ),
+            ..Default::default()
+        });
+    };
+    let overlay = commands
+        .spawn()
+        .insert(Transform::from_xyz(0., 0., 12.))
+        .insert(GlobalTransform::default())
+        .insert(DebugOverlay)
+        .insert(Name::new("Debug Overlay"))
+        .with_children(|parent| {
+            let (width, height) = (tile_map.width() as usize, tile_map.height() as usize);
+            let mut visited = vec![false; width * height];
+            let mut region = 0;
+            for y in 0..tile_map.height() {
+                for x in 0..tile_map.width() {
+                    let coords = Coordinates { x, y };
+                    let index = y as usize * width + x as usize;
+                    match tile_map[y as usize][x as usize] {
+                        tile if tile.is_bomb() => {
+                            marker(parent, coords, Color::rgba(1., 0., 0., 0.5));
+                        }
+                        Tile::Empty if !visited[index] => {
+                            let color =
+                                Color::hsla(region as f32 * REGION_HUE_STEP % 360., 0.8, 0.5, 0.35);
+                            for c in tile_map.opening_at(coords) {
+                                if tile_map[c.y as usize][c.x as usize] == Tile::Empty {
+                                    visited[c.y as usize * width + c.x as usize] = true;
+                                    marker(parent, c, color);
+                                }
+                            }
+                            region += 1;
+                        }
+                        _ => (),
+                    }
+                }
+            }
+            parent
+                .spawn_bundle(Text2dBundle {
+                    text: Text::with_section(
+                        counts_label(board),
+                        TextStyle {
+                            color: Color::WHITE,
+                            font: board_assets.bomb_counter_font.clone(),
+                            font_size: size.max(12.),
+                        },
+                        TextAlignment {
+                            vertical: VerticalAlign::Bottom,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    ),
+                    transform: Transform::from_xyz(
+                        geometry.bounds.size.x / 2.,
+                        height as f32 * size,
+                        0.,
+                    ),
+                    ..Default::default()
+                })
+                .insert(DebugCounts);
+        })
+        .id();
+    commands.entity(board.entity).add_child(overlay);
+}
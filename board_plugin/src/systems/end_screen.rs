@@ -0,0 +1,127 @@
+use crate::components::EndScreen;
+use crate::events::{RestartBoardEvent, RetryBoardEvent};
+use crate::resources::{
+    BoardAssets, BoardGeometry, BoardOptions, BoardState, EndlessProgress, GameTimer,
+    InputBindings, Player, VersusRace,
+};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Spawns the built-in end screen over the board once the game is over. Endless runs go on to
+/// their next level instead of showing a win screen
+pub fn spawn_end_screen(
+    mut commands: Commands,
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    timer: Res<GameTimer>,
+    bindings: Res<InputBindings>,
+    progress: Option<Res<EndlessProgress>>,
+    race: Option<Res<VersusRace>>,
+    screens: Query<(), With<EndScreen>>,
+) {
+    if !board_state.is_changed() || !board_options.map_or(false, |o| o.builtin_end_screens) {
+        return;
+    }
+    let winner = race.as_ref().and_then(|r| r.winner());
+    let title = match (*board_state, winner) {
+        (BoardState::Won | BoardState::Lost, Some(Player::One)) => "Player 1 Wins",
+        (BoardState::Won | BoardState::Lost, Some(Player::Two)) => "Player 2 Wins",
+        (BoardState::Won, _) if progress.is_none() => "You Win",
+        (BoardState::Lost, _) => "Boom!",
+        _ => return,
+    };
+    if !screens.is_empty() {
+        return;
+    }
+    let style = TextStyle {
+        color: Color::WHITE,
+        font: board_assets.bomb_counter_font.clone(),
+        font_size: 1.,
+    };
+    let mut lines = vec![
+        format!("{}\n", title),
+        format!("Time: {:.1}s\n", timer.elapsed()),
+        format!("Press {:?} to restart", bindings.restart),
+    ];
+    // Races lost to the rival board leave no exploded board to retry
+    let exploded = race.map_or(true, |r| r.progress(Player::One).exploded);
+    if *board_state == BoardState::Lost && exploded {
+        lines.push(format!("\nPress {:?} to retry", bindings.retry));
+    }
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite { color: Color::rgba(0., 0., 0., 0.6), ..Default::default() },
+                // Above the tiles and the pause cover
+                transform: Transform::from_xyz(0., 0., 11.),
+                ..Default::default()
+            })
+            .insert(EndScreen)
+            .insert(Name::new("End Screen"))
+            .with_children(|parent| {
+                parent.spawn_bundle(Text2dBundle {
+                    text: Text {
+                        sections: lines
+                            .into_iter()
+                            .map(|value| TextSection { value, style: style.clone() })
+                            .collect(),
+                        alignment: TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    },
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    ..Default::default()
+                });
+            });
+    });
+}
+
+/// Fits the end screen to the board, which may be resized
+pub fn layout_end_screen(
+    geometry: Res<BoardGeometry>,
+    mut screens: Query<(&mut Transform, &mut Sprite, &Children), With<EndScreen>>,
+    mut texts: Query<&mut Text>,
+) {
+    let size = geometry.bounds.size;
+    // The title is twice as big as the other lines
+    let font_size = size.x.min(size.y) / 16.;
+    for (mut transform, mut sprite, children) in screens.iter_mut() {
+        if sprite.custom_size == Some(size) {
+            continue;
+        }
+        sprite.custom_size = Some(size);
+        transform.translation = (size / 2.).extend(transform.translation.z);
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                for (index, section) in text.sections.iter_mut().enumerate() {
+                    section.style.font_size = if index == 0 { font_size * 2. } else { font_size };
+                }
+            }
+        }
+    }
+}
+
+/// Restarts the game when the restart key is pressed on the end screen, or retries the lost
+/// board with the retry key
+pub fn end_screen_input(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<InputBindings>,
+    board_state: Res<BoardState>,
+    screens: Query<(), With<EndScreen>>,
+    mut restart_board_ewr: EventWriter<RestartBoardEvent>,
+    mut retry_board_ewr: EventWriter<RetryBoardEvent>,
+) {
+    if screens.is_empty() {
+        return;
+    }
+    if keys.just_pressed(bindings.restart) {
+        info!("Restarting game");
+        restart_board_ewr.send(RestartBoardEvent);
+    } else if keys.just_pressed(bindings.retry) && *board_state == BoardState::Lost {
+        info!("Retrying board");
+        retry_board_ewr.send(RetryBoardEvent { restore_flags: true });
+    }
+}
@@ -0,0 +1,118 @@
+use crate::components::{BombPop, Particle};
+use crate::events::BombExplodedEvent;
+use crate::resources::{BoardGeometry, RevealSequence};
+use crate::Board;
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+/// Number of particles spawned by an explosion
+const PARTICLE_COUNT: usize = 40;
+/// Duration in seconds of a revealed bomb pop
+const BOMB_POP_DURATION: f32 = 0.2;
+/// Extra scale of a revealed bomb at the top of its pop
+const BOMB_POP_SCALE: f32 = 0.3;
+/// Particle velocity loss per second
+const PARTICLE_DRAG: f32 = 3.;
+
+pub fn spawn_explosion(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+) {
+    let mut rng = thread_rng();
+    for event in bomb_exploded_evr.iter() {
+        let center = geometry.tile_center(event.coords);
+        // Particle burst at the exploding tile, in world space so the board layout ignores it
+        for _ in 0..PARTICLE_COUNT {
+            let angle = rng.gen_range(0. ..std::f32::consts::TAU);
+            let speed = rng.gen_range(2. ..8.) * geometry.tile_size;
+            let color = match rng.gen_range(0..3) {
+                0 => Color::YELLOW,
+                1 => Color::ORANGE,
+                _ => Color::ORANGE_RED,
+            };
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::splat(geometry.tile_size / 5.)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(center.extend(5.)),
+                    ..Default::default()
+                })
+                .insert(Particle {
+                    velocity: Vec2::new(angle.cos(), angle.sin()) * speed,
+                    gravity: 0.,
+                    lifetime: Timer::from_seconds(rng.gen_range(0.4..0.8), false),
+                })
+                .insert(Name::new("Particle"));
+        }
+    }
+}
+
+pub fn update_particles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut particles: Query<(Entity, &mut Particle, &mut Transform, &mut Sprite)>,
+) {
+    let delta = time.delta_seconds();
+    for (entity, mut particle, mut transform, mut sprite) in particles.iter_mut() {
+        particle.lifetime.tick(time.delta());
+        if particle.lifetime.finished() {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        }
+        particle.velocity.y -= particle.gravity * delta;
+        transform.translation += (particle.velocity * delta).extend(0.);
+        particle.velocity *= (1. - PARTICLE_DRAG * delta).max(0.);
+        sprite.color.set_a(1. - particle.lifetime.percent());
+    }
+}
+
+/// Reveals the next bombs of the `RevealSequence`, each with a pop
+pub fn reveal_bombs(
+    mut commands: Commands,
+    time: Res<Time>,
+    board: Res<Board>,
+    sequence: Option<ResMut<RevealSequence>>,
+    parents: Query<&Parent>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    let mut sequence = match sequence {
+        None => return,
+        Some(s) => s,
+    };
+    for coords in sequence.tick(time.delta()) {
+        let cover = match board.covered_tiles.get(&coords) {
+            None => continue,
+            Some(e) => *e,
+        };
+        if let Ok(mut visibility) = visibilities.get_mut(cover) {
+            visibility.is_visible = false;
+        }
+        if let Ok(tile) = parents.get(cover) {
+            commands.entity(tile.0).insert(BombPop(Timer::from_seconds(BOMB_POP_DURATION, false)));
+        }
+    }
+    if sequence.is_finished() {
+        commands.remove_resource::<RevealSequence>();
+    }
+}
+
+pub fn pop_bombs(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tiles: Query<(Entity, &mut BombPop, &mut Transform)>,
+) {
+    for (entity, mut pop, mut transform) in tiles.iter_mut() {
+        if pop.0.tick(time.delta()).finished() {
+            transform.scale = Vec3::ONE;
+            commands.entity(entity).remove::<BombPop>();
+            continue;
+        }
+        let bump = (pop.0.percent() * std::f32::consts::PI).sin();
+        transform.scale = Vec3::new(1. + BOMB_POP_SCALE * bump, 1. + BOMB_POP_SCALE * bump, 1.);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::capture::BoardCapture;
+use crate::events::ExportBoardImageEvent;
+use crate::resources::{BoardAssets, GameTimer, Tile, VisibleTile};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task};
+use futures_lite::future;
+use image::{imageops, ImageResult, Rgba, RgbaImage};
+use std::path::PathBuf;
+
+/// Tile side in pixels
+const TILE_PX: u32 = 16;
+/// Glyph pixel scale
+const GLYPH_SCALE: u32 = 2;
+/// Margin around the header text in pixels
+const HEADER_MARGIN: u32 = 4;
+/// Bomb and detonated bomb colors
+const BOMB_COLOR: [u8; 4] = [20, 20, 20, 255];
+const DETONATED_COLOR: [u8; 4] = [140, 20, 20, 255];
+/// Flag color, the flag material being a texture
+pub(crate) const FLAG_COLOR: [u8; 4] = [220, 40, 40, 255];
+
+/// Board image being saved to a file on the IO task pool
+pub type ExportTask = Task<(PathBuf, ImageResult<()>)>;
+
+/// Board images being saved, see `export_board_image`
+#[derive(Default)]
+pub struct ExportTasks(pub Vec<ExportTask>);
+
+/// Exports the board to a PNG file on `ExportBoardImageEvent`, with the time and seed written
+/// above it. The board camera renders the board to an image, see `BoardCapture`, headless apps
+/// rasterizing it on the CPU from the visible tiles and the asset colors instead. The file is
+/// written on the IO task pool
+pub fn export_board_image(
+    board: Option<Res<Board>>,
+    board_assets: Res<BoardAssets>,
+    timer: Res<GameTimer>,
+    pool: Res<IoTaskPool>,
+    mut capture: Option<ResMut<BoardCapture>>,
+    mut tasks: ResMut<ExportTasks>,
+    mut export_evr: EventReader<ExportBoardImageEvent>,
+) {
+    for event in export_evr.iter() {
+        let board = match &board {
+            None => {
+                warn!("No board to export");
+                continue;
+            }
+            Some(b) => b,
+        };
+        let solution = event.solution && cfg!(feature = "debug");
+        if event.solution && !solution {
+            warn!("Board solutions are only exported with the debug feature");
+        }
+        let header = format!("TIME {:.1}S SEED {}", timer.elapsed(), board.tile_map.seed());
+        let background = rgba(board_assets.board_material.color);
+        match capture.as_mut() {
+            Some(capture) => capture.request(event.path.clone(), header, background, solution),
+            None => {
+                let image = render_board(board, &board_assets, solution);
+                tasks.0.push(save_image(&pool, event.path.clone(), image, header, background));
+            }
+        }
+    }
+}
+
+/// System logging the saved board images
+pub(crate) fn finish_exports(mut tasks: ResMut<ExportTasks>) {
+    for mut task in std::mem::take(&mut tasks.0) {
+        match future::block_on(future::poll_once(&mut task)) {
+            None => tasks.0.push(task),
+            Some((path, Ok(()))) => info!("Exported board to {}", path.display()),
+            Some((path, Err(e))) => error!("Failed to export board to {}: {}", path.display(), e),
+        }
+    }
+}
+
+/// Writes the `header` above the board `image` and saves it to `path` on the IO task pool
+pub(crate) fn save_image(
+    pool: &IoTaskPool,
+    path: PathBuf,
+    image: RgbaImage,
+    header: String,
+    background: [u8; 4],
+) -> ExportTask {
+    pool.spawn(async move {
+        let header_height = 5 * GLYPH_SCALE + 2 * HEADER_MARGIN;
+        let width = image.width().max(text_width(&header) + 2 * HEADER_MARGIN);
+        let mut output =
+            RgbaImage::from_pixel(width, header_height + image.height(), Rgba(background));
+        draw_text(&mut output, &header, HEADER_MARGIN, HEADER_MARGIN, [0, 0, 0, 255]);
+        imageops::replace(&mut output, &image, 0, header_height as i64);
+        let result = output.save(&path);
+        (path, result)
+    })
+}
+
+/// Rasterizes the board, bottom row last
+fn render_board(board: &Board, assets: &BoardAssets, solution: bool) -> RgbaImage {
+    let (width, height) = (board.tile_map.width() as u32, board.tile_map.height() as u32);
+    let mut image =
+        RgbaImage::from_pixel(width * TILE_PX, height * TILE_PX, rgba(assets.board_material.color));
+    for y in 0..height {
+        for x in 0..width {
+            let coords = Coordinates { x: x as u16, y: y as u16 };
+            let visible = match board.visible_tile(coords) {
+                VisibleTile::Covered { .. } if solution => solution_tile(board, coords),
+                visible => visible,
+            };
+            // Image rows go down, board rows go up
+            let (left, top) = (x * TILE_PX, (height - 1 - y) * TILE_PX);
+            draw_tile(&mut image, assets, visible, left, top);
+        }
+    }
+    image
+}
+
+/// Tile as it would be uncovered
+fn solution_tile(board: &Board, coords: Coordinates) -> VisibleTile {
+    match board.tile_map[coords.y as usize][coords.x as usize] {
+        Tile::Empty => VisibleTile::Empty,
+        Tile::BombNeighbor(count) => VisibleTile::Number(count),
+        Tile::Hole => VisibleTile::Hole,
+        _ => VisibleTile::Exploded,
+    }
+}
+
+fn draw_tile(image: &mut RgbaImage, assets: &BoardAssets, tile: VisibleTile, left: u32, top: u32) {
+    let (background, mark) = match tile {
+        VisibleTile::Hole => return,
+        VisibleTile::Covered { flags: 0 } => (assets.covered_tile_material.color, None),
+        VisibleTile::Covered { .. } => (assets.covered_tile_material.color, Some(FLAG_COLOR)),
+        VisibleTile::Exploded => (assets.tile_material.color, Some(BOMB_COLOR)),
+        VisibleTile::Detonated => (assets.tile_material.color, Some(DETONATED_COLOR)),
+        VisibleTile::Empty | VisibleTile::Number(_) => (assets.tile_material.color, None),
+    };
+    // One pixel gap between tiles
+    fill(image, left, top, TILE_PX - 1, TILE_PX - 1, rgba(background));
+    if let Some(color) = mark {
+        fill(image, left + TILE_PX / 4, top + TILE_PX / 4, TILE_PX / 2, TILE_PX / 2, color);
+    }
+    if let VisibleTile::Number(count) = tile {
+        // Same as `Board::render_ascii` for counts outside of `0..=9`
+        let glyph = match count {
+            0..=9 => char::from(b'0' + count as u8),
+            c if c > 9 => '+',
+            _ => '-',
+        };
+        let color = rgba(assets.bomb_counter_color(count));
+        let (glyph_width, glyph_height) = (3 * GLYPH_SCALE, 5 * GLYPH_SCALE);
+        let (x, y) = (left + (TILE_PX - glyph_width) / 2, top + (TILE_PX - glyph_height) / 2);
+        draw_text(image, &glyph.to_string(), x, y, color);
+    }
+}
+
+fn fill(image: &mut RgbaImage, left: u32, top: u32, width: u32, height: u32, color: [u8; 4]) {
+    for y in top..(top + height).min(image.height()) {
+        for x in left..(left + width).min(image.width()) {
+            image.put_pixel(x, y, Rgba(color));
+        }
+    }
+}
+
+pub(crate) fn rgba(color: Color) -> [u8; 4] {
+    color.as_rgba_f32().map(|c| (c.clamp(0., 1.) * 255.).round() as u8)
+}
+
+fn text_width(text: &str) -> u32 {
+    text.chars().count() as u32 * 4 * GLYPH_SCALE
+}
+
+fn draw_text(image: &mut RgbaImage, text: &str, left: u32, top: u32, color: [u8; 4]) {
+    for (i, c) in text.chars().enumerate() {
+        let x = left + i as u32 * 4 * GLYPH_SCALE;
+        for (row, bits) in glyph(c).iter().enumerate() {
+            for column in 0..3 {
+                if bits & (0b100 >> column) != 0 {
+                    let (px, py) = (x + column * GLYPH_SCALE, top + row as u32 * GLYPH_SCALE);
+                    fill(image, px, py, GLYPH_SCALE, GLYPH_SCALE, color);
+                }
+            }
+        }
+    }
+}
+
+/// 3x5 pixel glyphs, one row of 3 bits per line. Unknown characters are blank
+pub(crate) fn glyph(c: char) -> [u8; 5] {
+    match c {
+        '0' => [0b111, 0b101, 0b101, 0b101, 0b111],
+        '1' => [0b010, 0b110, 0b010, 0b010, 0b111],
+        '2' => [0b111, 0b001, 0b111, 0b100, 0b111],
+        '3' => [0b111, 0b001, 0b111, 0b001, 0b111],
+        '4' => [0b101, 0b101, 0b111, 0b001, 0b001],
+        '5' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        '6' => [0b111, 0b100, 0b111, 0b101, 0b111],
+        '7' => [0b111, 0b001, 0b001, 0b001, 0b001],
+        '8' => [0b111, 0b101, 0b111, 0b101, 0b111],
+        '9' => [0b111, 0b101, 0b111, 0b001, 0b111],
+        '+' => [0b000, 0b010, 0b111, 0b010, 0b000],
+        '-' => [0b000, 0b000, 0b111, 0b000, 0b000],
+        '.' => [0b000, 0b000, 0b000, 0b000, 0b010],
+        'D' => [0b110, 0b101, 0b101, 0b101, 0b110],
+        'E' => [0b111, 0b100, 0b110, 0b100, 0b111],
+        'I' => [0b111, 0b010, 0b010, 0b010, 0b111],
+        'M' => [0b101, 0b111, 0b111, 0b101, 0b101],
+        'S' => [0b111, 0b100, 0b111, 0b001, 0b111],
+        'T' => [0b111, 0b010, 0b010, 0b010, 0b010],
+        _ => [0; 5],
+    }
+}
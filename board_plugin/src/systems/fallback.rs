@@ -0,0 +1,64 @@
+use crate::embedded;
+use crate::events::MissingAssetEvent;
+use crate::resources::BoardAssets;
+use bevy::asset::{HandleId, LoadState};
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Replaces the board textures and font that failed to load with the embedded ones, instead of
+/// blank tiles. Textures without embedded counterpart fall back to plain white
+pub fn replace_missing_assets(
+    asset_server: Res<AssetServer>,
+    board_assets: Res<BoardAssets>,
+    images: Option<ResMut<Assets<Image>>>,
+    fonts: Option<ResMut<Assets<Font>>>,
+    mut replaced: Local<HashSet<HandleId>>,
+    mut missing_asset_ewr: EventWriter<MissingAssetEvent>,
+) {
+    // Headless apps render nothing
+    let (mut images, mut fonts) = match (images, fonts) {
+        (Some(images), Some(fonts)) => (images, fonts),
+        _ => return,
+    };
+    let mut failed = |id: HandleId| {
+        if replaced.contains(&id) || asset_server.get_load_state(id) != LoadState::Failed {
+            return false;
+        }
+        replaced.insert(id);
+        let path = asset_server
+            .get_handle_path(id)
+            .map(|p| p.path().display().to_string())
+            .unwrap_or_default();
+        warn!("Failed to load {}, using the embedded fallback", path);
+        missing_asset_ewr.send(MissingAssetEvent { path });
+        true
+    };
+    let font = &board_assets.bomb_counter_font;
+    if failed(font.id) {
+        fonts.set_untracked(font.id, embedded::font());
+    }
+    let materials = [
+        (&board_assets.board_material, None),
+        (&board_assets.tile_material, None),
+        (&board_assets.covered_tile_material, None),
+        (&board_assets.hovered_tile_material, None),
+        (&board_assets.pressed_tile_material, None),
+        (&board_assets.flag_material, Some(embedded::FLAG)),
+        (&board_assets.auto_flag_material, Some(embedded::FLAG)),
+        (&board_assets.bomb_material, Some(embedded::BOMB)),
+        (&board_assets.armored_bomb_material, Some(embedded::BOMB)),
+        (&board_assets.anti_mine_material, Some(embedded::BOMB)),
+        (&board_assets.mega_mine_material, Some(embedded::BOMB)),
+        (&board_assets.decoy_mine_material, Some(embedded::BOMB)),
+        (&board_assets.cursor_material, None),
+        (&board_assets.tutorial_material, None),
+    ];
+    for (material, png) in materials {
+        if failed(material.texture.id) {
+            images.set_untracked(
+                material.texture.id,
+                png.map_or_else(Image::default, embedded::image),
+            );
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::components::FogOverlay;
+use crate::events::TileUncoveredEvent;
+use crate::resources::{BoardGeometry, BoardOptions};
+use crate::Board;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Fog overlay color, darkening the cover under it
+const FOG_COLOR: Color = Color::rgba(0., 0., 0., 0.6);
+
+/// Darkens the covered tiles out of sight, once tiles are uncovered or covers are spawned
+pub fn update_fog(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut cover_count: Local<usize>,
+    mut fogs: Query<(Entity, &Parent, &mut Visibility), With<FogOverlay>>,
+) {
+    if board.fog_radius.is_none() {
+        return;
+    }
+    let uncovered = tile_uncovered_evr.iter().count() > 0;
+    if !uncovered && *cover_count == board.covered_tiles.len() {
+        return;
+    }
+    *cover_count = board.covered_tiles.len();
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    let mut overlays: HashMap<Entity, Entity> =
+        fogs.iter().map(|(entity, parent, _)| (parent.0, entity)).collect();
+    for (coords, cover) in board.covered_tiles.iter() {
+        let fogged = !board.in_sight(coords);
+        match overlays.remove(cover) {
+            Some(overlay) => {
+                if let Ok((_, _, mut visibility)) = fogs.get_mut(overlay) {
+                    visibility.is_visible = fogged;
+                }
+            }
+            None if fogged => {
+                commands.entity(*cover).with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                color: FOG_COLOR,
+                                custom_size: Some(Vec2::splat(geometry.tile_size - padding)),
+                                ..Default::default()
+                            },
+                            transform: Transform::from_xyz(0., 0., 1.),
+                            ..Default::default()
+                        })
+                        .insert(FogOverlay);
+                });
+            }
+            None => (),
+        }
+    }
+}
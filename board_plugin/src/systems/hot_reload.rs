@@ -0,0 +1,33 @@
+use crate::events::RestartBoardEvent;
+use crate::resources::{BoardOptions, EndlessProgress};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Regenerates the board when the map size, bomb count or tile padding are tuned through the
+/// inspector. Endless runs generate their levels from the progression instead
+pub fn hot_reload_options(
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    progress: Option<Res<EndlessProgress>>,
+    mut last: Local<Option<((u16, u16), u16, f32)>>,
+    mut restart_board_ewr: EventWriter<RestartBoardEvent>,
+) {
+    let options = match board_options {
+        Some(o) if o.is_changed() && progress.is_none() => o,
+        _ => return,
+    };
+    let params = (options.map_size, options.layout.bomb_count(), options.tile_padding);
+    let previous = match last.replace(params) {
+        Some(p) if p != params => p,
+        _ => return,
+    };
+    // The options may have been replaced along with the board, like when a game is started
+    let tile_map = &board.tile_map;
+    let board_matches = (tile_map.width(), tile_map.height()) == options.map_size
+        && tile_map.bomb_count() == options.layout.bomb_count();
+    if board_matches && previous.2 == options.tile_padding {
+        return;
+    }
+    info!("Board options changed, regenerating the board");
+    restart_board_ewr.send(RestartBoardEvent);
+}
@@ -0,0 +1,46 @@
+use crate::components::Pressed;
+use crate::resources::{BoardAssets, BoardGeometry, BoardWindow, HoveredTile, SpriteMaterial};
+use crate::systems::atlas::{paint_cover, CoverSpriteParts};
+use crate::systems::input::board_camera;
+use crate::Board;
+use bevy::prelude::*;
+
+pub fn hover_tiles(
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_assets: Res<BoardAssets>,
+    mut hovered: ResMut<HoveredTile>,
+    // Pressed covers keep their own material
+    mut covers: Query<CoverSpriteParts, Without<Pressed>>,
+) {
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let camera = board_camera(&cameras, &board_window);
+    // Only covered tiles get highlighted
+    let current = window
+        .cursor_position()
+        .and_then(|pos| geometry.mouse_position(window, camera, pos))
+        .filter(|coords| board.covered_tiles.contains_key(coords));
+    if current == hovered.0 {
+        return;
+    }
+    let mut apply = |coords, material: &SpriteMaterial| {
+        if let Some(entity) = board.covered_tiles.get(&coords) {
+            if let Ok(cover) = covers.get_mut(*entity) {
+                paint_cover(cover, material, board_assets.atlas.as_ref());
+            }
+        }
+    };
+    if let Some(previous) = hovered.0 {
+        apply(previous, &board_assets.covered_tile_material);
+    }
+    if let Some(coords) = current {
+        apply(coords, &board_assets.hovered_tile_material);
+    }
+    hovered.0 = current;
+}
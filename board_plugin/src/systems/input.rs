@@ -1,36 +1,367 @@
-use crate::Board;
-use bevy::input::{mouse::MouseButtonInput, ElementState};
+use crate::components::{ChordPreview, Pressed};
+use crate::events::{InputSource, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::resources::{
+    BoardAssets, BoardGeometry, BoardOptions, BoardState, BoardWindow, DragState, InputBindings,
+};
+use crate::systems::atlas::{paint_cover, CoverSpriteParts};
+use crate::{Board, Coordinates};
+use bevy::input::{
+    mouse::MouseButtonInput,
+    touch::{TouchInput, TouchPhase, Touches},
+    ElementState,
+};
 use bevy::prelude::*;
-use crate::events::TileTriggerEvent;
+use bevy::utils::HashMap;
 
+/// Duration in seconds of a touch press to mark a tile instead of uncovering it
+const LONG_PRESS_DURATION: f64 = 0.5;
+
+/// Last action time of the recently clicked tiles, for an input system to drop the repeats
+/// faster than `BoardOptions::input_debounce`
+#[derive(Debug, Default)]
+pub struct TileDebounce(HashMap<Coordinates, f64>);
+
+impl TileDebounce {
+    /// Records an action on `coords` at `now`, unless the previous one is less than `delay`
+    /// seconds old. Returns whether the action is allowed
+    pub fn allow(&mut self, coords: Coordinates, now: f64, delay: Option<f32>) -> bool {
+        let delay = match delay {
+            None => return true,
+            Some(d) => d as f64,
+        };
+        // Expired actions are dropped so the map stays small
+        self.0.retain(|_, time| now - *time < delay);
+        if self.0.contains_key(&coords) {
+            debug!("Debounced action on {}", coords);
+            return false;
+        }
+        self.0.insert(coords, now);
+        true
+    }
+}
+
+/// Retrieves the camera rendering the board, to map cursor positions through its projection
+pub(crate) fn board_camera<'a>(
+    cameras: &'a Query<(&Camera, &GlobalTransform)>,
+    board_window: &BoardWindow,
+) -> Option<(&'a Camera, &'a GlobalTransform)> {
+    cameras.iter().find(|(camera, _)| board_window.is_board_camera(camera))
+}
+
+/// Pans the board camera while the uncover button or a single finger is dragged out of the
+/// `BoardOptions::camera_drag` dead zone. Presses turned into drags are not clicks
+pub fn drag_camera(
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    board_options: Option<Res<BoardOptions>>,
+    bindings: Res<InputBindings>,
+    buttons: Res<Input<MouseButton>>,
+    touches: Res<Touches>,
+    mut drag: ResMut<DragState>,
+    mut cameras: Query<(&Camera, &mut Transform, &OrthographicProjection)>,
+) {
+    let options = match board_options.and_then(|o| o.camera_drag) {
+        None => {
+            *drag = DragState::default();
+            return;
+        }
+        Some(o) => o,
+    };
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    // Presses released within their frame are never drags
+    if buttons.just_pressed(bindings.uncover_button) || touches.iter_just_pressed().next().is_some()
+    {
+        drag.origin = None;
+        drag.dragging = false;
+    }
+    let pointer = if buttons.pressed(bindings.uncover_button) {
+        window.cursor_position()
+    } else {
+        // Touch positions have a top left origin, unlike the cursor position
+        let mut pressed = touches.iter();
+        match (pressed.next(), pressed.next()) {
+            (Some(touch), None) => {
+                Some(Vec2::new(touch.position().x, window.height() - touch.position().y))
+            }
+            _ => None,
+        }
+    };
+    let position = match pointer {
+        None => {
+            drag.origin = None;
+            return;
+        }
+        Some(p) => p,
+    };
+    let origin = *drag.origin.get_or_insert(position);
+    if !drag.dragging && position.distance(origin) > options.threshold {
+        trace!("Press dragged from {}", origin);
+        drag.dragging = true;
+    }
+    if drag.dragging {
+        let delta = position - drag.last;
+        for (camera, mut transform, projection) in cameras.iter_mut() {
+            if board_window.is_board_camera(camera) {
+                // Fitted boards zoom the camera out through its scale
+                let scale = projection.scale * transform.scale.truncate();
+                transform.translation -= (delta * scale).extend(0.);
+            }
+        }
+    }
+    drag.last = position;
+}
+
+/// Handles button presses, except for uncovering which is done on release
 pub fn input_handling(
     windows: Res<Windows>,
-    board: Res<Board>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    geometry: Res<BoardGeometry>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    bindings: Res<InputBindings>,
+    time: Res<Time>,
+    mut debounce: Local<TileDebounce>,
     mut button_evr: EventReader<MouseButtonInput>,
-    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
 ) {
-    let window = windows.get_primary().unwrap();
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let camera = board_camera(&cameras, &board_window);
+    let delay = board_options.and_then(|o| o.input_debounce);
+    let _span = info_span!("input_handling").entered();
 
     for event in button_evr.iter() {
         if let ElementState::Pressed = event.state {
             let position = window.cursor_position();
             if let Some(pos) = position {
                 trace!("Mouse button pressed: {:?} at {}", event.button, pos);
-                let tile_coordinates = board.mouse_position(window, pos);
+                let tile_coordinates = geometry.mouse_position(window, camera, pos);
                 if let Some(coordinates) = tile_coordinates {
-                    match event.button {
-                        MouseButton::Left => {
-                            info!("Trying to uncover tile on {}", coordinates);
-                            tile_trigger_ewr.send(TileTriggerEvent(coordinates));
-                        }
-                        MouseButton::Right => {
-                            info!("Trying to mark tile on {}", coordinates);
-                            // TODO: generate an event
-                        }
-                        _ => (),
+                    let now = time.seconds_since_startup();
+                    if event.button == bindings.flag_button
+                        && debounce.allow(coordinates, now, delay)
+                    {
+                        info!("Trying to mark tile on {}", coordinates);
+                        let source = InputSource::Mouse(event.button);
+                        tile_mark_ewr.send(TileMarkEvent(coordinates, source));
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Depresses the covered tile under the held uncover button, and uncovers it on release.
+/// Moving the cursor away moves or cancels the press, and camera drags cancel it
+pub fn pressed_tile_handling(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    (board, geometry): (Res<Board>, Res<BoardGeometry>),
+    board_state: Res<BoardState>,
+    board_assets: Res<BoardAssets>,
+    bindings: Res<InputBindings>,
+    board_options: Option<Res<BoardOptions>>,
+    buttons: Res<Input<MouseButton>>,
+    drag: Res<DragState>,
+    time: Res<Time>,
+    mut debounce: Local<TileDebounce>,
+    mut pressed: Query<(Entity, CoverSpriteParts), With<Pressed>>,
+    mut covers: Query<CoverSpriteParts, Without<Pressed>>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+) {
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let camera = board_camera(&cameras, &board_window);
+    let held = !drag.dragging && buttons.pressed(bindings.uncover_button);
+    let released = !drag.dragging && buttons.just_released(bindings.uncover_button);
+    let target = if held || released {
+        window
+            .cursor_position()
+            .and_then(|pos| geometry.mouse_position(window, camera, pos))
+            .and_then(|coords| board.tile_to_uncover(&coords).map(|entity| (coords, *entity)))
+    } else {
+        None
+    };
+    // The press is released or moved away
+    let atlas = board_assets.atlas.as_ref();
+    for (entity, cover) in pressed.iter_mut() {
+        let on_target = target.map(|(_, e)| e) == Some(entity);
+        if held && on_target {
+            continue;
+        }
+        commands.entity(entity).remove::<Pressed>();
+        paint_cover(cover, &board_assets.covered_tile_material, atlas);
+        if released && on_target {
+            let now = time.seconds_since_startup();
+            let delay = board_options.as_ref().and_then(|o| o.input_debounce);
+            let target = target.filter(|(c, _)| debounce.allow(*c, now, delay));
+            if let Some((coordinates, _)) = target {
+                info!("Trying to uncover tile on {}", coordinates);
+                let source = InputSource::Mouse(bindings.uncover_button);
+                tile_trigger_ewr.send(TileTriggerEvent(coordinates, source));
+            }
+        }
+    }
+    // The press starts or moves to a new tile
+    if let (true, Some((_, entity))) = (held, target) {
+        if let Ok(cover) = covers.get_mut(entity) {
+            commands.entity(entity).insert(Pressed);
+            paint_cover(cover, &board_assets.pressed_tile_material, atlas);
+        }
+    }
+}
+
+/// Highlights the tiles a chord would uncover while the chord button, or both uncover and flag
+/// buttons, are held on a numbered tile. The chord is performed on release
+pub fn chord_handling(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    (board, geometry): (Res<Board>, Res<BoardGeometry>),
+    board_state: Res<BoardState>,
+    board_assets: Res<BoardAssets>,
+    bindings: Res<InputBindings>,
+    board_options: Option<Res<BoardOptions>>,
+    buttons: Res<Input<MouseButton>>,
+    time: Res<Time>,
+    mut was_held: Local<Option<MouseButton>>,
+    mut debounce: Local<TileDebounce>,
+    mut previews: Query<(Entity, CoverSpriteParts), With<ChordPreview>>,
+    mut covers: Query<CoverSpriteParts, Without<ChordPreview>>,
+    mut tile_chord_ewr: EventWriter<TileChordEvent>,
+) {
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let camera = board_camera(&cameras, &board_window);
+    // The chord button, or the uncover button completing a chord with both buttons
+    let held_button = if !board.variant.allows_chording() {
+        None
+    } else if buttons.pressed(bindings.chord_button) {
+        Some(bindings.chord_button)
+    } else if bindings.chord_with_both_buttons
+        && buttons.pressed(bindings.uncover_button)
+        && buttons.pressed(bindings.flag_button)
+    {
+        Some(bindings.uncover_button)
+    } else {
+        None
+    };
+    let held = held_button.is_some();
+    let target = window
+        .cursor_position()
+        .and_then(|pos| geometry.mouse_position(window, camera, pos))
+        .filter(|coords| board.uncovered_bomb_count(*coords).is_some());
+    let preview = match (held, target) {
+        (true, Some(coords)) => board.adjacent_unmarked_tiles(coords),
+        _ => Vec::new(),
+    };
+    let atlas = board_assets.atlas.as_ref();
+    for (entity, cover) in previews.iter_mut() {
+        if !preview.contains(&entity) {
+            commands.entity(entity).remove::<ChordPreview>();
+            paint_cover(cover, &board_assets.covered_tile_material, atlas);
+        }
+    }
+    for entity in preview {
+        if let Ok(cover) = covers.get_mut(entity) {
+            commands.entity(entity).insert(ChordPreview);
+            paint_cover(cover, &board_assets.pressed_tile_material, atlas);
+        }
+    }
+    if let (Some(button), false) = (*was_held, held) {
+        let now = time.seconds_since_startup();
+        let delay = board_options.and_then(|o| o.input_debounce);
+        if let Some(coords) = target.filter(|c| debounce.allow(*c, now, delay)) {
+            info!("Trying to chord tile on {}", coords);
+            tile_chord_ewr.send(TileChordEvent(coords, InputSource::Mouse(button)));
+        }
+    }
+    *was_held = held_button;
+}
+
+/// Touch screens uncover tiles on tap and mark them on long press, camera drags doing neither
+pub fn touch_input_handling(
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    geometry: Res<BoardGeometry>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    time: Res<Time>,
+    drag: Res<DragState>,
+    mut touch_evr: EventReader<TouchInput>,
+    mut touch_starts: Local<HashMap<u64, f64>>,
+    mut debounce: Local<TileDebounce>,
+    mut tile_trigger_ewr: EventWriter<TileTriggerEvent>,
+    mut tile_mark_ewr: EventWriter<TileMarkEvent>,
+) {
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let camera = board_camera(&cameras, &board_window);
+    let delay = board_options.and_then(|o| o.input_debounce);
+    let _span = info_span!("touch_input_handling").entered();
+
+    for event in touch_evr.iter() {
+        match event.phase {
+            TouchPhase::Started => {
+                touch_starts.insert(event.id, time.seconds_since_startup());
+            }
+            TouchPhase::Ended => {
+                let start = match touch_starts.remove(&event.id) {
+                    None => continue,
+                    Some(s) => s,
+                };
+                if drag.dragging {
+                    continue;
+                }
+                // Touch positions have a top left origin, unlike the cursor position
+                let pos = Vec2::new(event.position.x, window.height() - event.position.y);
+                trace!("Touch released at {}", pos);
+                let now = time.seconds_since_startup();
+                let coordinates = geometry
+                    .mouse_position(window, camera, pos)
+                    .filter(|c| debounce.allow(*c, now, delay));
+                if let Some(coordinates) = coordinates {
+                    if now - start >= LONG_PRESS_DURATION {
+                        info!("Trying to mark tile on {}", coordinates);
+                        tile_mark_ewr.send(TileMarkEvent(coordinates, InputSource::Touch));
+                    } else {
+                        info!("Trying to uncover tile on {}", coordinates);
+                        tile_trigger_ewr.send(TileTriggerEvent(coordinates, InputSource::Touch));
                     }
                 }
             }
+            TouchPhase::Cancelled => {
+                touch_starts.remove(&event.id);
+            }
+            TouchPhase::Moved => (),
         }
     }
-}
\ No newline at end of file
+}
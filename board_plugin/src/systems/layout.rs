@@ -0,0 +1,119 @@
+use crate::resources::{BoardAnchor, BoardOptions, BoardPosition, BoardWindow, TileMap, TileSize};
+use crate::Coordinates;
+use bevy::prelude::*;
+
+/// Gap between the main board and the rival board of a versus race, in tiles
+pub const RIVAL_GAP: u16 = 1;
+
+/// We use the actual window size, which may differ from the descriptor (Ex: web canvas).
+/// The descriptor is only used by headless apps, without windows
+pub fn window_size(
+    window: &WindowDescriptor,
+    board_window: &BoardWindow,
+    windows: &Windows,
+) -> Vec2 {
+    match windows.get(board_window.window) {
+        None => Vec2::new(window.width, window.height),
+        Some(w) => Vec2::new(w.width(), w.height()),
+    }
+}
+
+/// Ratio between physical and logical pixels of the window
+pub fn scale_factor(
+    window: &WindowDescriptor,
+    board_window: &BoardWindow,
+    windows: &Windows,
+) -> f32 {
+    match windows.get(board_window.window) {
+        None => window.scale_factor_override.unwrap_or(1.) as f32,
+        Some(w) => w.scale_factor() as f32,
+    }
+}
+
+/// Tile size in logical pixels, adaptive sizes being rounded to whole physical pixels so
+/// tiles stay sharp on high-DPI displays
+pub fn build_tile_size(
+    window_size: Vec2,
+    scale_factor: f32,
+    options: &BoardOptions,
+    tile_map: &TileMap,
+) -> f32 {
+    match options.tile_size {
+        TileSize::Fixed(v) => v * options.ui_scale,
+        TileSize::Adaptive { min, max } => {
+            let size = adaptative_tile_size(
+                options.margins.available_size(window_size),
+                (min * options.ui_scale, max * options.ui_scale),
+                layout_tiles(options, tile_map),
+            );
+            (size * scale_factor).floor().max(1.) / scale_factor
+        }
+    }
+}
+
+/// Size in tiles of the boards area, a versus race placing the rival board to the right of
+/// the main one
+fn layout_tiles(options: &BoardOptions, tile_map: &TileMap) -> (u16, u16) {
+    let (width, height) = (tile_map.width(), tile_map.height());
+    match options.versus {
+        true => (width.saturating_mul(2).saturating_add(RIVAL_GAP), height),
+        false => (width, height),
+    }
+}
+
+/// Size of the boards area of a main board of `board_size`, see `layout_tiles`
+pub fn layout_size(options: &BoardOptions, board_size: Vec2, tile_size: f32) -> Vec2 {
+    match options.versus {
+        true => Vec2::new(board_size.x * 2. + tile_size * RIVAL_GAP as f32, board_size.y),
+        false => board_size,
+    }
+}
+
+/// Main board anchor position (bottom left), placing the boards area of `board_size`
+pub fn build_board_position(options: &BoardOptions, board_size: Vec2, window_size: Vec2) -> Vec3 {
+    match options.position {
+        BoardPosition::Centered { offset } => {
+            (options.margins.center() - board_size / 2.).extend(0.) + offset
+        }
+        BoardPosition::Custom(p) => p,
+        BoardPosition::Anchored { anchor, offset, margin } => {
+            // The camera is centered on the world origin
+            let margins = options.margins;
+            let min = -window_size / 2. + Vec2::new(margins.left, margins.bottom) + margin;
+            let max =
+                window_size / 2. - Vec2::new(margins.right, margins.top) - margin - board_size;
+            let center = margins.center() - board_size / 2.;
+            let (x, y) = match anchor {
+                BoardAnchor::TopLeft => (min.x, max.y),
+                BoardAnchor::Top => (center.x, max.y),
+                BoardAnchor::TopRight => (max.x, max.y),
+                BoardAnchor::Left => (min.x, center.y),
+                BoardAnchor::Right => (max.x, center.y),
+                BoardAnchor::BottomLeft => (min.x, min.y),
+                BoardAnchor::Bottom => (center.x, min.y),
+                BoardAnchor::BottomRight => (max.x, min.y),
+            };
+            Vec3::new(x, y, 0.) + offset
+        }
+    }
+}
+
+/// Computes a tile size that matches the window according to the tile map size
+fn adaptative_tile_size(
+    window_size: Vec2,
+    (min, max): (f32, f32),      // Tile size constraints
+    (width, height): (u16, u16), // Tile map dimensions
+) -> f32 {
+    let max_width = window_size.x / width as f32;
+    let max_heigth = window_size.y / height as f32;
+    max_width.min(max_heigth).clamp(min, max)
+}
+
+/// Tile local position in the board, the sprite pivot being centered
+pub fn tile_translation(coordinates: Coordinates, size: f32) -> Vec3 {
+    Vec3::new(
+        (coordinates.x as f32 * size) + (size / 2.),
+        (coordinates.y as f32 * size) + (size / 2.),
+        1.,
+    )
+}
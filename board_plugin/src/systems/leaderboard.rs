@@ -0,0 +1,22 @@
+use crate::events::SubmitScoreEvent;
+use crate::resources::Leaderboards;
+use bevy::prelude::*;
+
+pub fn submit_scores(
+    mut leaderboards: ResMut<Leaderboards>,
+    mut submit_score_evr: EventReader<SubmitScoreEvent>,
+) {
+    let mut submitted = false;
+    for event in submit_score_evr.iter() {
+        match leaderboards.submit(event.key, event.entry.clone()) {
+            None => info!("{} did not make the leaderboard", event.entry.name),
+            Some(rank) => info!("{} ranked #{} on the leaderboard", event.entry.name, rank + 1),
+        }
+        submitted = true;
+    }
+    if submitted {
+        if let Err(e) = leaderboards.save() {
+            error!("Failed to save leaderboards: {}", e);
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use crate::components::Detonated;
+use crate::resources::{BoardAssets, BoardGeometry};
+use bevy::prelude::*;
+
+/// Crosses out the newly detonated bombs
+pub fn cross_out_detonated(
+    mut commands: Commands,
+    geometry: Res<BoardGeometry>,
+    board_assets: Res<BoardAssets>,
+    detonated: Query<Entity, Added<Detonated>>,
+) {
+    for entity in detonated.iter() {
+        commands.entity(entity).with_children(|parent| {
+            parent
+                .spawn_bundle(Text2dBundle {
+                    text: Text {
+                        sections: vec![TextSection {
+                            value: "X".to_string(),
+                            style: TextStyle {
+                                color: Color::RED,
+                                font: board_assets.bomb_counter_font.clone(),
+                                font_size: geometry.tile_size,
+                            },
+                        }],
+                        alignment: TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    },
+                    transform: Transform::from_xyz(0., 0., 3.),
+                    ..Default::default()
+                })
+                .insert(Name::new("Detonation Cross"));
+        });
+    }
+}
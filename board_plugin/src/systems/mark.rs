@@ -0,0 +1,244 @@
+use crate::components::{AutoFlag, CoverShake, Easing, Flag, FlagAnimation, WinFlag};
+use crate::events::{
+    AutoFlagEvent, FlagTileEvent, TileFlaggedEvent, TileMarkEvent, TileUnflaggedEvent,
+    WrongFlagAttemptEvent,
+};
+use crate::resources::{
+    BoardAssets, BoardGeometry, BoardOptions, BoardState, RestoredFlags, TutorialAction,
+    TutorialScript,
+};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Duration in seconds of the flag drop animation
+const FLAG_DROP_DURATION: f32 = 0.3;
+/// Duration in seconds of the flag fade out animation
+const FLAG_FADE_DURATION: f32 = 0.2;
+/// Duration in seconds of the rejected flag cover shake
+const COVER_SHAKE_DURATION: f32 = 0.3;
+/// Number of back and forth movements of a cover shake
+const COVER_SHAKE_OSCILLATIONS: f32 = 4.;
+
+pub fn mark_tiles(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    geometry: Res<BoardGeometry>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    tutorial: Option<Res<TutorialScript>>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut flag_tile_evr: EventReader<FlagTileEvent>,
+    mut auto_flag_evr: EventReader<AutoFlagEvent>,
+    children: Query<&Children>,
+    flags: Query<(), With<Flag>>,
+    mut tile_flagged_ewr: EventWriter<TileFlaggedEvent>,
+    mut tile_unflagged_ewr: EventWriter<TileUnflaggedEvent>,
+    mut wrong_flag_attempt_ewr: EventWriter<WrongFlagAttemptEvent>,
+) {
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let padding = board_options.as_ref().map(|o| o.tile_padding).unwrap_or_default();
+    let mistake_proof = board_options.map_or(false, |o| o.mistake_proof_flags);
+    // Mark events toggle flags, while flag and auto flag events only flag unflagged tiles. The
+    // tutorial only blocks input
+    let marks: Vec<_> = tile_mark_evr
+        .iter()
+        .filter(|e| tutorial.as_ref().map_or(true, |t| t.allows(TutorialAction::Mark, e.0)))
+        .map(|e| (e.0, true, false))
+        .chain(flag_tile_evr.iter().map(|e| (e.0, false, false)))
+        .chain(auto_flag_evr.iter().map(|e| (e.0, false, true)))
+        .collect();
+    for (coords, toggle, auto) in marks {
+        if !toggle && board.flags_at(&coords) > 0 {
+            continue;
+        }
+        let adds_flag = board.flags_at(&coords) < board.variant.max_flags();
+        if mistake_proof && adds_flag && !board.tile_map.is_bomb_at(coords) {
+            if let Some(entity) =
+                board.covered_tiles.get(&coords).filter(|_| board.in_sight(&coords))
+            {
+                info!("Rejected flag on safe tile {}", coords);
+                commands
+                    .entity(*entity)
+                    .insert(CoverShake(Timer::from_seconds(COVER_SHAKE_DURATION, false)));
+                wrong_flag_attempt_ewr.send(WrongFlagAttemptEvent(coords));
+            }
+            continue;
+        }
+        if let Some((entity, mark)) = board.try_toggle_mark(&coords) {
+            if mark {
+                tile_flagged_ewr.send(TileFlaggedEvent(coords));
+                let height = geometry.tile_size / 2.;
+                let index = board.flags_at(&coords).saturating_sub(1);
+                let mut bundle = flag_bundle(&board_assets, geometry.tile_size, padding, index);
+                bundle.transform.translation.y = height;
+                if auto {
+                    bundle.sprite.color = board_assets.auto_flag_material.color;
+                    bundle.texture = board_assets.auto_flag_material.texture.clone();
+                }
+                commands.entity(entity).with_children(|parent| {
+                    let mut flag = parent.spawn_bundle(bundle);
+                    flag.insert(FlagAnimation::Drop {
+                        timer: Timer::from_seconds(FLAG_DROP_DURATION, false),
+                        height,
+                    })
+                    .insert(Flag)
+                    .insert(Name::new("Flag"));
+                    if auto {
+                        flag.insert(AutoFlag);
+                    }
+                });
+            } else {
+                tile_unflagged_ewr.send(TileUnflaggedEvent(coords));
+                let children = match children.get(entity) {
+                    Ok(c) => c,
+                    Err(e) => {
+                        error!("Failed to retrieve flag entity: {}", e);
+                        continue;
+                    }
+                };
+                for child in children.iter().filter(|c| flags.get(**c).is_ok()) {
+                    commands.entity(*child).insert(FlagAnimation::FadeOut(Timer::from_seconds(
+                        FLAG_FADE_DURATION,
+                        false,
+                    )));
+                }
+            }
+        }
+    }
+}
+
+/// Places the flags restored by `RetryBoardEvent` once the retried board is spawned
+pub fn restore_flags(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    board_state: Res<BoardState>,
+    restored: Option<Res<RestoredFlags>>,
+) {
+    let restored = match restored {
+        None => return,
+        Some(r) => r,
+    };
+    // Flagged tiles may not be spawned yet
+    if *board_state == BoardState::Loading {
+        return;
+    }
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    for (coords, count) in restored.0.iter() {
+        for _ in 0..*count {
+            if let Some((entity, true)) = board.try_toggle_mark(coords) {
+                let index = board.flags_at(coords) - 1;
+                let bundle = flag_bundle(&board_assets, geometry.tile_size, padding, index);
+                commands.entity(entity).with_children(|parent| {
+                    parent.spawn_bundle(bundle).insert(Flag).insert(Name::new("Flag"));
+                });
+            }
+        }
+    }
+    commands.remove_resource::<RestoredFlags>();
+}
+
+/// Drops the flags placed on the remaining covers when the board is completed, one cover after
+/// the other
+pub fn drop_win_flags(
+    mut commands: Commands,
+    time: Res<Time>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    mut covers: Query<(Entity, &mut WinFlag)>,
+) {
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    let height = geometry.tile_size / 2.;
+    for (entity, mut win_flag) in covers.iter_mut() {
+        if !win_flag.delay.tick(time.delta()).finished() {
+            continue;
+        }
+        commands.entity(entity).remove::<WinFlag>().with_children(|parent| {
+            for index in win_flag.flags.clone() {
+                let mut bundle = flag_bundle(&board_assets, geometry.tile_size, padding, index);
+                bundle.transform.translation.y = height;
+                parent
+                    .spawn_bundle(bundle)
+                    .insert(FlagAnimation::Drop {
+                        timer: Timer::from_seconds(FLAG_DROP_DURATION, false),
+                        height,
+                    })
+                    .insert(Flag)
+                    .insert(Name::new("Flag"));
+            }
+        });
+    }
+}
+
+/// Sprite of the `index`th flag of a tile cover
+pub(crate) fn flag_bundle(
+    board_assets: &BoardAssets,
+    tile_size: f32,
+    padding: f32,
+    index: u8,
+) -> SpriteBundle {
+    // Extra flags of armored bombs are stacked aside
+    let stacked = index as f32;
+    SpriteBundle {
+        texture: board_assets.flag_material.texture.clone(),
+        sprite: Sprite {
+            color: board_assets.flag_material.color,
+            custom_size: Some(Vec2::splat(tile_size - padding)),
+            ..Default::default()
+        },
+        transform: Transform::from_xyz(stacked * tile_size / 6., 0., 1. + stacked * 0.1),
+        ..Default::default()
+    }
+}
+
+pub fn animate_flags(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut flags: Query<(Entity, &mut FlagAnimation, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut animation, mut transform, mut sprite) in flags.iter_mut() {
+        match animation.as_mut() {
+            FlagAnimation::Drop { timer, height } => {
+                timer.tick(time.delta());
+                transform.translation.y = *height * (1. - Easing::BounceOut.apply(timer.percent()));
+                if timer.finished() {
+                    transform.translation.y = 0.;
+                    commands.entity(entity).remove::<FlagAnimation>();
+                }
+            }
+            FlagAnimation::FadeOut(timer) => {
+                timer.tick(time.delta());
+                sprite.color.set_a(1. - timer.percent());
+                if timer.finished() {
+                    commands.entity(entity).despawn_recursive();
+                }
+            }
+        }
+    }
+}
+
+/// Shakes the covers of rejected flags sideways
+pub fn shake_covers(
+    mut commands: Commands,
+    time: Res<Time>,
+    geometry: Res<BoardGeometry>,
+    mut covers: Query<(Entity, &mut CoverShake, &mut Transform)>,
+) {
+    for (entity, mut shake, mut transform) in covers.iter_mut() {
+        shake.0.tick(time.delta());
+        let percent = shake.0.percent();
+        let amplitude = geometry.tile_size / 10. * (1. - percent);
+        transform.translation.x =
+            amplitude * (percent * COVER_SHAKE_OSCILLATIONS * std::f32::consts::TAU).sin();
+        if shake.0.finished() {
+            transform.translation.x = 0.;
+            commands.entity(entity).remove::<CoverShake>();
+        }
+    }
+}
@@ -0,0 +1,212 @@
+use crate::components::{MiniMapView, MiniMapViewport};
+use crate::resources::{
+    BoardAssets, BoardGeometry, BoardOptions, BoardWindow, DragState, InputBindings, Tile,
+};
+use crate::systems::export::{rgba, FLAG_COLOR};
+use crate::systems::input::board_camera;
+use crate::{Board, Coordinates};
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::render::render_resource::{Extent3d, FilterMode, TextureDimension, TextureFormat};
+
+/// Mini-map depth in the board, over every tile visual
+const MINI_MAP_Z: f32 = 20.;
+/// Viewport outline color
+const VIEWPORT_COLOR: Color = Color::rgba(1., 1., 1., 0.35);
+
+/// Shows the mini-map in the top right corner of the window while the board overflows it, see
+/// `BoardOptions::mini_map`. Its image is redrawn whenever the board changes
+pub fn update_mini_map(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    images: Option<ResMut<Assets<Image>>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut maps: Query<
+        (Entity, &mut Transform, &mut Sprite, &mut Visibility, &Handle<Image>),
+        With<MiniMapView>,
+    >,
+    mut viewports: Query<
+        (&mut Transform, &mut Sprite),
+        (With<MiniMapViewport>, Without<MiniMapView>),
+    >,
+) {
+    let options = match board_options.and_then(|o| o.camera_drag.and(o.mini_map)) {
+        None => {
+            for (entity, ..) in maps.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            return;
+        }
+        Some(o) => o,
+    };
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let (_, mut transform, mut sprite, mut visibility, texture) = match maps.iter_mut().next() {
+        Some(map) => map,
+        None => {
+            spawn_mini_map(&mut commands, &board, &board_assets, images);
+            return;
+        }
+    };
+    let camera = board_camera(&cameras, &board_window);
+    let view_min = BoardGeometry::screen_to_world(window, camera, Vec2::ZERO);
+    let view_max =
+        BoardGeometry::screen_to_world(window, camera, Vec2::new(window.width(), window.height()));
+    let bounds = geometry.bounds;
+    let overflows = bounds.position.cmplt(view_min).any()
+        || (bounds.position + bounds.size).cmpgt(view_max).any();
+    if visibility.is_visible != overflows {
+        visibility.is_visible = overflows;
+    }
+    if !overflows {
+        return;
+    }
+    if board.is_changed() {
+        if let Some(image) = images.and_then(|i| i.into_inner().get_mut(texture)) {
+            *image = mini_map_image(&board, &board_assets);
+        }
+    }
+    // The mini-map keeps its size on screen whatever the camera zoom
+    let scale = (view_max.x - view_min.x) / window.width();
+    let tiles = Vec2::new(board.tile_map.width() as f32, board.tile_map.height() as f32);
+    let size = tiles * options.size * scale / tiles.max_element();
+    let center = view_max - Vec2::splat(options.margin * scale) - size / 2.;
+    let translation = (center - bounds.position).extend(MINI_MAP_Z);
+    if transform.translation != translation || sprite.custom_size != Some(size) {
+        transform.translation = translation;
+        sprite.custom_size = Some(size);
+    }
+    // The viewport outline is clipped to the mini-map
+    let factor = size / bounds.size;
+    let min = ((view_min - bounds.center()) * factor).max(-size / 2.);
+    let max = ((view_max - bounds.center()) * factor).min(size / 2.);
+    if let Some((mut transform, mut sprite)) = viewports.iter_mut().next() {
+        transform.translation = ((min + max) / 2.).extend(0.1);
+        sprite.custom_size = Some((max - min).max(Vec2::ZERO));
+    }
+}
+
+/// Moves the board camera to the mini-map position under the pressed uncover button. Presses
+/// starting on the mini-map are turned into camera drags, so they never reach the tiles below
+pub fn navigate_mini_map(
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    geometry: Res<BoardGeometry>,
+    bindings: Res<InputBindings>,
+    buttons: Res<Input<MouseButton>>,
+    mut drag: ResMut<DragState>,
+    mut navigating: Local<bool>,
+    maps: Query<(&GlobalTransform, &Sprite, &Visibility), With<MiniMapView>>,
+    mut cameras: Query<(&Camera, &mut Transform, &GlobalTransform)>,
+) {
+    if !buttons.pressed(bindings.uncover_button) {
+        *navigating = false;
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let (cursor, (map_transform, map_sprite, visibility)) =
+        match (window.cursor_position(), maps.iter().next()) {
+            (Some(c), Some(m)) => (c, m),
+            _ => return,
+        };
+    let map_size = match map_sprite.custom_size {
+        Some(s) if visibility.is_visible => s,
+        _ => return,
+    };
+    for (camera, mut transform, global_transform) in cameras.iter_mut() {
+        if !board_window.is_board_camera(camera) {
+            continue;
+        }
+        let world =
+            BoardGeometry::screen_to_world(window, Some((camera, global_transform)), cursor);
+        let local = (world - map_transform.translation.xy()) / map_size + Vec2::splat(0.5);
+        if buttons.just_pressed(bindings.uncover_button) {
+            *navigating = local.cmpge(Vec2::ZERO).all() && local.cmple(Vec2::ONE).all();
+        }
+        if !*navigating {
+            return;
+        }
+        let target =
+            geometry.bounds.position + local.clamp(Vec2::ZERO, Vec2::ONE) * geometry.bounds.size;
+        transform.translation.x = target.x;
+        transform.translation.y = target.y;
+        drag.origin = Some(cursor);
+        drag.last = cursor;
+        drag.dragging = true;
+    }
+}
+
+/// Spawns the mini-map, hidden until laid out
+fn spawn_mini_map(
+    commands: &mut Commands,
+    board: &Board,
+    board_assets: &BoardAssets,
+    images: Option<ResMut<Assets<Image>>>,
+) {
+    let texture = match images {
+        Some(mut images) => images.add(mini_map_image(board, board_assets)),
+        // Nothing is rendered headless
+        None => Default::default(),
+    };
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                texture,
+                visibility: Visibility { is_visible: false },
+                ..Default::default()
+            })
+            .insert(MiniMapView)
+            .insert(Name::new("Mini-map"))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite { color: VIEWPORT_COLOR, ..Default::default() },
+                        ..Default::default()
+                    })
+                    .insert(MiniMapViewport)
+                    .insert(Name::new("Mini-map Viewport"));
+            });
+    });
+}
+
+/// One pixel per tile image of the uncovered tiles and flags, holes being transparent
+fn mini_map_image(board: &Board, board_assets: &BoardAssets) -> Image {
+    let (width, height) = (board.tile_map.width(), board.tile_map.height());
+    let covered = rgba(board_assets.covered_tile_material.color);
+    let uncovered = rgba(board_assets.tile_material.color);
+    // Image rows go from the top, unlike tile coordinates
+    let data = (0..height)
+        .rev()
+        .flat_map(|y| (0..width).map(move |x| Coordinates { x, y }))
+        .flat_map(|c| {
+            if board.tile_map[c.y as usize][c.x as usize] == Tile::Hole {
+                [0; 4]
+            } else if board.marked_tiles.contains_key(&c) {
+                FLAG_COLOR
+            } else if board.is_covered(&c) {
+                covered
+            } else {
+                uncovered
+            }
+        })
+        .collect();
+    let mut image = Image::new(
+        Extent3d { width: width as u32, height: height as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    );
+    // Tiles stay sharp squares once scaled up
+    image.sampler_descriptor.mag_filter = FilterMode::Nearest;
+    image
+}
@@ -1,2 +1,53 @@
+pub mod accessibility;
+pub mod ascii;
+pub mod assist;
+pub mod atlas;
+pub mod board;
+pub mod camera;
+pub mod cascade;
+pub mod celebration;
+pub mod chunks;
+pub mod cursor;
+#[cfg(feature = "debug")]
+pub mod debug_overlay;
+pub mod end_screen;
+pub mod explosion;
+pub mod export;
+pub mod fallback;
+pub mod fog;
+#[cfg(feature = "debug")]
+pub mod hot_reload;
+pub mod hover;
 pub mod input;
-pub mod uncover;
\ No newline at end of file
+pub mod layout;
+pub mod leaderboard;
+pub mod lives;
+pub mod mark;
+pub mod mini_map;
+pub mod modifiers;
+pub mod moving_mines;
+pub mod night;
+pub mod pause;
+pub mod power_ups;
+pub mod progress;
+pub mod regions;
+pub mod remaining;
+pub mod replay;
+pub mod resize;
+pub mod run_criteria;
+pub mod settings;
+pub mod shake;
+pub mod snapshot;
+pub mod spawn;
+pub mod split_counts;
+pub mod stats;
+pub mod timer;
+pub mod tooltip;
+pub mod tutorial;
+pub mod tween;
+pub mod uncover;
+pub mod versus;
+pub mod view;
+#[cfg(target_arch = "wasm32")]
+pub mod web;
+pub mod window;
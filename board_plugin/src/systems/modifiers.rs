@@ -0,0 +1,67 @@
+use crate::components::TileModifier;
+use crate::resources::{BoardGeometry, BoardOptions};
+use crate::Board;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Tints the covers of the modifier tiles, cracked ice, found keys and opened locks losing
+/// theirs
+pub fn update_modifier_overlays(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    mut overlays: Query<(Entity, &Parent, &mut TileModifier, &mut Sprite)>,
+) {
+    if !board.is_changed() && !geometry.is_changed() {
+        return;
+    }
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    let size = Some(Vec2::splat(geometry.tile_size - padding));
+    let mut stale: HashMap<Entity, Entity> =
+        overlays.iter().map(|(entity, parent, _, _)| (parent.0, entity)).collect();
+    for (coords, modifier) in board.modifiers.tiles.iter() {
+        let cover = match board.covered_tiles.get(coords) {
+            None => continue,
+            Some(c) => *c,
+        };
+        match stale.remove(&cover).and_then(|o| overlays.get_mut(o).ok()) {
+            Some((_, _, mut overlay_modifier, mut sprite)) => {
+                if *overlay_modifier != *modifier {
+                    *overlay_modifier = *modifier;
+                }
+                sprite.color = overlay_color(*modifier);
+                sprite.custom_size = size;
+            }
+            None => {
+                commands.entity(cover).with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                color: overlay_color(*modifier),
+                                custom_size: size,
+                                ..Default::default()
+                            },
+                            transform: Transform::from_xyz(0., 0., 1.),
+                            ..Default::default()
+                        })
+                        .insert(*modifier)
+                        .insert(Name::new("Tile Modifier"));
+                });
+            }
+        }
+    }
+    for overlay in stale.values() {
+        commands.entity(*overlay).despawn_recursive();
+    }
+}
+
+/// Translucent tint of a modifier, drawn over the cover
+fn overlay_color(modifier: TileModifier) -> Color {
+    match modifier {
+        TileModifier::Ice => Color::rgba(0.7, 0.9, 1., 0.5),
+        TileModifier::Locked => Color::rgba(0.3, 0.2, 0.1, 0.6),
+        TileModifier::Key => Color::rgba(1., 0.85, 0.2, 0.4),
+        TileModifier::Teleport => Color::rgba(0.7, 0.3, 1., 0.4),
+    }
+}
@@ -0,0 +1,127 @@
+use crate::components::{Bomb, BombNeighbor, NightShade};
+use crate::events::{MineMovedEvent, TileUncoveredEvent};
+use crate::resources::{Board, BoardAssets, BoardGeometry, BoardOptions, BoardState, Tile};
+use crate::systems::spawn::insert_tile_contents;
+use crate::Coordinates;
+use bevy::prelude::*;
+use rand::{seq::SliceRandom, thread_rng};
+use std::sync::Arc;
+
+/// Random mine moves tried before a moving mines schedule gives up until its next move
+const MINE_MOVE_ATTEMPTS: usize = 16;
+
+/// System moving a random covered mine of a `BoardOptions::moving_mines` board to another
+/// covered tile once its schedule is due. Marked tiles are left alone, and moves changing
+/// whether an uncovered tile is empty are rejected, so the revealed tiles stay consistent
+pub fn move_mines(
+    mut commands: Commands,
+    mut board: ResMut<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    board_state: Res<BoardState>,
+    time: Res<Time>,
+    tiles: Query<(Entity, &Coordinates, Option<&Children>)>,
+    shades: Query<(), With<NightShade>>,
+    mut schedule: Local<(f32, u32)>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut mine_moved_ewr: EventWriter<MineMovedEvent>,
+) {
+    if board.is_added() {
+        *schedule = Default::default();
+    }
+    let uncovered = tile_uncovered_evr.iter().count() as u32;
+    let (padding, moving_mines) = match board_options.as_ref() {
+        Some(o) => (o.tile_padding, o.moving_mines),
+        None => return,
+    };
+    let moving_mines = match moving_mines {
+        Some(m) if *board_state == BoardState::Playing => m,
+        _ => return,
+    };
+    // Mines stay put until the first tile is uncovered
+    if board.uncovered_tiles() == 0 {
+        return;
+    }
+    schedule.0 += time.delta_seconds();
+    schedule.1 += uncovered;
+    let due = moving_mines.interval.map_or(false, |i| schedule.0 >= i)
+        || moving_mines.uncovers.map_or(false, |u| u > 0 && schedule.1 >= u);
+    if !due {
+        return;
+    }
+    *schedule = Default::default();
+    let (from, to, changed) = match pick_mine_move(&mut board) {
+        None => return,
+        Some(m) => m,
+    };
+    debug!("Mine moved from {} to {}", from, to);
+    let size = geometry.tile_size;
+    for (entity, coords, children) in tiles.iter() {
+        if !changed.contains(coords) {
+            continue;
+        }
+        // Only the bomb or counter is rebuilt, the cover and night shade stay
+        let cover = board.covered_tiles.get(coords);
+        for child in children.into_iter().flat_map(|c| c.iter()) {
+            if Some(child) != cover && !shades.contains(*child) {
+                commands.entity(*child).despawn_recursive();
+            }
+        }
+        let mut tile_entity = commands.entity(entity);
+        tile_entity.remove::<Bomb>().remove::<BombNeighbor>();
+        let tile = board.tile_map[coords.y as usize][coords.x as usize];
+        let kind = board.mine_kind(coords);
+        insert_tile_contents(&mut tile_entity, &tile, kind, size, padding, &board_assets);
+    }
+    mine_moved_ewr.send(MineMovedEvent { from, to });
+}
+
+/// Moves a random covered and unmarked plain mine of the `board` to a random covered tile,
+/// returning the move and the changed tiles
+fn pick_mine_move(board: &mut Board) -> Option<(Coordinates, Coordinates, Vec<Coordinates>)> {
+    let mut rng = thread_rng();
+    let movable = |board: &Board, coords: &Coordinates| {
+        board.is_covered(coords) && !board.marked_tiles.contains_key(coords)
+    };
+    let mines: Vec<Coordinates> = board
+        .tile_map
+        .bomb_coordinates()
+        .filter(|c| board.tile_map[c.y as usize][c.x as usize] == Tile::Bomb)
+        .filter(|c| movable(board, c))
+        .collect();
+    let destinations: Vec<Coordinates> = (0..board.tile_map.height())
+        .flat_map(|y| (0..board.tile_map.width()).map(move |x| Coordinates { x, y }))
+        .filter(|c| {
+            let tile = board.tile_map[c.y as usize][c.x as usize];
+            !tile.is_bomb() && tile != Tile::Hole && movable(board, c)
+        })
+        .collect();
+    for _ in 0..MINE_MOVE_ATTEMPTS {
+        let from = *mines.choose(&mut rng)?;
+        let to = *destinations.choose(&mut rng)?;
+        // Uncovered numbers must not change
+        let before: Vec<(Coordinates, Tile)> = board
+            .tile_map
+            .neighbors(from)
+            .chain(board.tile_map.neighbors(to))
+            .filter(|c| !board.is_covered(c))
+            .map(|c| (c, board.tile_map[c.y as usize][c.x as usize]))
+            .collect();
+        let changed = Arc::make_mut(&mut board.tile_map).move_bomb(from, to);
+        if changed.is_empty() {
+            continue;
+        }
+        let consistent =
+            before.iter().all(|(c, tile)| board.tile_map[c.y as usize][c.x as usize] == *tile);
+        if consistent {
+            // Special mines keep their kind
+            if let Some(kind) = board.mine_kinds.remove(&from) {
+                board.mine_kinds.insert(to, kind);
+            }
+            return Some((from, to, changed));
+        }
+        Arc::make_mut(&mut board.tile_map).move_bomb(to, from);
+    }
+    None
+}
@@ -0,0 +1,101 @@
+use crate::components::NightShade;
+use crate::events::NightModeEvent;
+use crate::resources::{BoardAssets, BoardGeometry, NightMode, Tile};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Night shades are drawn over every other tile visual
+const NIGHT_SHADE_Z: f32 = 5.;
+
+/// Applies the night mode events to the theme, in any state
+pub fn night_mode_event_handler(
+    mut board_assets: ResMut<BoardAssets>,
+    mut night_mode_evr: EventReader<NightModeEvent>,
+) {
+    if let Some(NightModeEvent(night_mode)) = night_mode_evr.iter().last() {
+        board_assets.night_mode = *night_mode;
+    }
+}
+
+/// Darkens the tiles in night mode according to their distance to the uncovered tiles, once
+/// tiles are uncovered or the theme changes. Shades are despawned when the night mode ends
+pub fn update_night_shades(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_assets: Res<BoardAssets>,
+    tiles: Query<(Entity, &Coordinates)>,
+    mut shades: Query<(Entity, &Parent, &mut Sprite), With<NightShade>>,
+) {
+    if !board.is_changed() && !board_assets.is_changed() {
+        return;
+    }
+    let night_mode = match board_assets.night_mode {
+        None => {
+            for (entity, _, _) in shades.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            return;
+        }
+        Some(n) => n,
+    };
+    let mut overlays: HashMap<Entity, Entity> =
+        shades.iter().map(|(entity, parent, _)| (parent.0, entity)).collect();
+    for (tile, coords) in tiles.iter() {
+        let mut color = night_mode.darkness;
+        color.set_a(color.a() * (1. - light_level(&board, &night_mode, coords)));
+        match overlays.remove(&tile).and_then(|o| shades.get_mut(o).ok()) {
+            Some((_, _, mut sprite)) => {
+                // Unchanged shades are left untouched so they aren't extracted again
+                if sprite.color != color {
+                    sprite.color = color;
+                }
+            }
+            None => {
+                commands.entity(tile).with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite {
+                                color,
+                                custom_size: Some(Vec2::splat(geometry.tile_size)),
+                                ..Default::default()
+                            },
+                            transform: Transform::from_xyz(0., 0., NIGHT_SHADE_Z),
+                            ..Default::default()
+                        })
+                        .insert(NightShade)
+                        .insert(Name::new("Night Shade"));
+                });
+            }
+        }
+    }
+}
+
+/// Light at `coords`, from 0 out of the light radius of any uncovered tile to 1 on uncovered
+/// tiles
+fn light_level(board: &Board, night_mode: &NightMode, coords: &Coordinates) -> f32 {
+    let radius = night_mode.light_radius.max(0.);
+    if !board.is_covered(coords) {
+        return 1.;
+    }
+    let reach = radius.ceil() as u16;
+    let (x_min, y_min) = (coords.x.saturating_sub(reach), coords.y.saturating_sub(reach));
+    let x_max = coords.x.saturating_add(reach).min(board.tile_map.width() - 1);
+    let y_max = coords.y.saturating_add(reach).min(board.tile_map.height() - 1);
+    let mut light: f32 = 0.;
+    for y in y_min..=y_max {
+        for x in x_min..=x_max {
+            let c = Coordinates { x, y };
+            // Holes don't give light
+            if board.is_covered(&c) || board.tile_map[y as usize][x as usize] == Tile::Hole {
+                continue;
+            }
+            let distance = Vec2::new(x as f32 - coords.x as f32, y as f32 - coords.y as f32);
+            light = light.max(1. - distance.length() / radius);
+        }
+    }
+    // The light fades out smoothly
+    let light = light.clamp(0., 1.);
+    light * light * (3. - 2. * light)
+}
@@ -0,0 +1,67 @@
+use crate::events::{BoardCompletedEvent, BombExplodedEvent, PauseEvent, TimeExpiredEvent};
+use crate::resources::{BoardState, LostBoard, RevealSequence};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+
+/// Delay in seconds between two bomb reveals on loss
+const BOMB_REVEAL_DELAY: f32 = 0.08;
+
+pub fn pause_event_handler(
+    board: Res<Board>,
+    mut board_state: ResMut<BoardState>,
+    mut pause_evr: EventReader<PauseEvent>,
+    mut visibilities: Query<&mut Visibility>,
+) {
+    for event in pause_evr.iter() {
+        let paused = match (event, *board_state) {
+            (PauseEvent::Pause | PauseEvent::Toggle, BoardState::Playing) => true,
+            (PauseEvent::Resume | PauseEvent::Toggle, BoardState::Paused) => false,
+            _ => continue,
+        };
+        info!("{} game", if paused { "Pausing" } else { "Resuming" });
+        *board_state = if paused { BoardState::Paused } else { BoardState::Playing };
+        if let Some(entity) = board.pause_cover {
+            if let Ok(mut visibility) = visibilities.get_mut(entity) {
+                visibility.is_visible = paused;
+            }
+        }
+    }
+}
+
+pub fn end_game(
+    mut commands: Commands,
+    board: Res<Board>,
+    mut board_state: ResMut<BoardState>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+    mut time_expired_evr: EventReader<TimeExpiredEvent>,
+) {
+    let expired = time_expired_evr.iter().count() > 0;
+    let exploded = bomb_exploded_evr.iter().next().map(|e| e.coords);
+    if exploded.is_some() || expired {
+        if *board_state != BoardState::Lost {
+            commands.insert_resource(reveal_sequence(&board, exploded));
+            let flags = board.marked_tiles.iter().map(|(c, f)| (*c, *f)).collect();
+            commands.insert_resource(LostBoard { seed: board.tile_map.seed(), flags });
+        }
+        *board_state = BoardState::Lost;
+    } else if board_completed_evr.iter().count() > 0 && *board_state != BoardState::Lost {
+        *board_state = BoardState::Won;
+    }
+}
+
+/// Covered bombs ordered from the exploded one outward, or from the board center when the time
+/// expired
+fn reveal_sequence(board: &Board, exploded: Option<Coordinates>) -> RevealSequence {
+    let (width, height) = (board.tile_map.width(), board.tile_map.height());
+    let origin = exploded.unwrap_or(Coordinates { x: width / 2, y: height / 2 });
+    let distance = |coords: &Coordinates| {
+        let (dx, dy) = (coords.x as i32 - origin.x as i32, coords.y as i32 - origin.y as i32);
+        dx * dx + dy * dy
+    };
+    let mut bombs: Vec<Coordinates> =
+        board.covered_tiles.keys().copied().filter(|c| board.tile_map.is_bomb_at(*c)).collect();
+    // Ties are broken by position for a stable order
+    bombs.sort_by_key(|c| (distance(c), c.y, c.x));
+    RevealSequence::new(bombs, BOMB_REVEAL_DELAY)
+}
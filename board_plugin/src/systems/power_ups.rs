@@ -0,0 +1,159 @@
+use crate::components::Particle;
+use crate::events::{
+    FlagTileEvent, PowerUpGrantedEvent, PowerUpUsedEvent, UncoverEvent, UsePowerUpEvent,
+};
+use crate::resources::{BoardAssets, BoardGeometry, BoardState, PowerUp, PowerUps};
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+
+/// Number of sparkles spawned on a power-up target tile
+const SPARKLE_COUNT: usize = 12;
+/// Board background tint while the timers are frozen
+const FROZEN_TINT: Color = Color::rgb(0.6, 0.8, 1.);
+
+/// Grants a random power-up every grant interval
+pub fn grant_power_ups(
+    time: Res<Time>,
+    board_state: Res<BoardState>,
+    power_ups: Option<ResMut<PowerUps>>,
+    mut granted_ewr: EventWriter<PowerUpGrantedEvent>,
+) {
+    let mut power_ups = match power_ups {
+        None => return,
+        Some(p) => p,
+    };
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let mut rng = thread_rng();
+    for _ in 0..power_ups.tick(time.delta()) {
+        let power_up = PowerUp::ALL[rng.gen_range(0..PowerUp::ALL.len())];
+        info!("Power-up granted: {:?}", power_up);
+        power_ups.grant(power_up);
+        granted_ewr.send(PowerUpGrantedEvent(power_up));
+    }
+}
+
+/// Uses the requested power-ups. A charge is only spent if the power-up has a target
+pub fn use_power_ups(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    power_ups: Option<ResMut<PowerUps>>,
+    mut use_power_up_evr: EventReader<UsePowerUpEvent>,
+    mut uncover_ewr: EventWriter<UncoverEvent>,
+    mut flag_tile_ewr: EventWriter<FlagTileEvent>,
+    mut used_ewr: EventWriter<PowerUpUsedEvent>,
+) {
+    let mut power_ups = match power_ups {
+        None => return,
+        Some(p) => p,
+    };
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let mut rng = thread_rng();
+    for UsePowerUpEvent(power_up) in use_power_up_evr.iter() {
+        if power_ups.charges(*power_up) == 0 {
+            continue;
+        }
+        let coords = match power_up {
+            PowerUp::RevealSafeTile => {
+                let safe = covered_tiles(&board)
+                    .filter(|c| !board.tile_map.is_bomb_at(*c) && board.flags_at(c) == 0);
+                match safe.collect::<Vec<_>>().choose(&mut rng) {
+                    None => continue,
+                    Some(coords) => {
+                        uncover_ewr.send(UncoverEvent(*coords));
+                        Some(*coords)
+                    }
+                }
+            }
+            PowerUp::DefuseMine => {
+                let bombs = covered_tiles(&board)
+                    .filter(|c| board.tile_map.is_bomb_at(*c) && board.flags_at(c) == 0);
+                match bombs.collect::<Vec<_>>().choose(&mut rng) {
+                    None => continue,
+                    Some(coords) => {
+                        flag_tile_ewr.send(FlagTileEvent(*coords));
+                        Some(*coords)
+                    }
+                }
+            }
+            PowerUp::FreezeTimer => {
+                power_ups.freeze();
+                None
+            }
+        };
+        power_ups.take(*power_up);
+        info!("Power-up used: {:?}", power_up);
+        used_ewr.send(PowerUpUsedEvent { power_up: *power_up, coords });
+    }
+}
+
+/// Covered tiles, including the ones of unspawned chunks
+fn covered_tiles(board: &Board) -> impl Iterator<Item = Coordinates> + '_ {
+    let unspawned = board.chunks.iter().flat_map(|c| c.covered.iter());
+    board.covered_tiles.keys().chain(unspawned).copied()
+}
+
+/// Sparkles over the power-up target tiles
+pub fn spawn_power_up_effects(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    mut used_evr: EventReader<PowerUpUsedEvent>,
+) {
+    let mut rng = thread_rng();
+    for coords in used_evr.iter().filter_map(|e| e.coords) {
+        let center = geometry.tile_center(coords);
+        for _ in 0..SPARKLE_COUNT {
+            let angle = rng.gen_range(0. ..std::f32::consts::TAU);
+            let speed = rng.gen_range(1. ..3.) * geometry.tile_size;
+            commands
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color: Color::WHITE,
+                        custom_size: Some(Vec2::splat(geometry.tile_size / 8.)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_translation(center.extend(5.)),
+                    ..Default::default()
+                })
+                .insert(Particle {
+                    velocity: Vec2::new(angle.cos(), angle.sin()) * speed,
+                    gravity: 0.,
+                    lifetime: Timer::from_seconds(rng.gen_range(0.3..0.6), false),
+                })
+                .insert(Name::new("Sparkle"));
+        }
+    }
+}
+
+/// Tints the board background while the timers are frozen
+pub fn display_freeze(
+    board: Res<Board>,
+    board_assets: Res<BoardAssets>,
+    power_ups: Option<Res<PowerUps>>,
+    mut was_frozen: Local<bool>,
+    mut sprites: Query<&mut Sprite>,
+) {
+    let frozen = power_ups.map_or(false, |p| p.is_frozen());
+    if frozen == *was_frozen {
+        return;
+    }
+    *was_frozen = frozen;
+    if let Ok(mut sprite) = sprites.get_mut(board.background) {
+        let color = board_assets.board_material.color;
+        sprite.color = match frozen {
+            true => Color::rgba(
+                color.r() * FROZEN_TINT.r(),
+                color.g() * FROZEN_TINT.g(),
+                color.b() * FROZEN_TINT.b(),
+                color.a(),
+            ),
+            false => color,
+        };
+    }
+}
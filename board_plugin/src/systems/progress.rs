@@ -0,0 +1,106 @@
+use crate::components::{ProgressBarFill, ProgressBarView};
+use crate::events::TileUncoveredEvent;
+use crate::resources::{BoardGeometry, BoardOptions, BoardWindow};
+use crate::systems::input::board_camera;
+use crate::Board;
+use bevy::prelude::*;
+
+/// Progress bar depth in the board, over every tile visual
+const PROGRESS_BAR_Z: f32 = 20.;
+/// Bar background and fill colors
+const BACKGROUND_COLOR: Color = Color::rgba(0., 0., 0., 0.5);
+const FILL_COLOR: Color = Color::rgb(0.3, 0.8, 0.4);
+
+/// Shows the uncovered share of the safe tiles in a bar at the top of the window, see
+/// `BoardOptions::progress_bar`. The bar fills up on uncover events
+pub fn update_progress_bar(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    mut bars: Query<(Entity, &mut Transform, &mut Sprite), With<ProgressBarView>>,
+    mut fills: Query<
+        (&mut Transform, &mut Sprite, ChangeTrackers<ProgressBarFill>),
+        Without<ProgressBarView>,
+    >,
+) {
+    let options = match board_options.and_then(|o| o.progress_bar) {
+        None => {
+            for (entity, ..) in bars.iter() {
+                commands.entity(entity).despawn_recursive();
+            }
+            return;
+        }
+        Some(o) => o,
+    };
+    let (_, mut bar_transform, mut bar_sprite) = match bars.iter_mut().next() {
+        Some(bar) => bar,
+        None => {
+            spawn_progress_bar(&mut commands, &board);
+            return;
+        }
+    };
+    // The bar keeps its size on screen whatever the camera position and zoom
+    if let Some(window) = windows.get(board_window.window) {
+        let camera = board_camera(&cameras, &board_window);
+        let view_min = BoardGeometry::screen_to_world(window, camera, Vec2::ZERO);
+        let view_max = BoardGeometry::screen_to_world(
+            window,
+            camera,
+            Vec2::new(window.width(), window.height()),
+        );
+        let scale = (view_max.x - view_min.x) / window.width();
+        let size = Vec2::new(
+            (view_max.x - view_min.x - options.margin * scale * 2.).max(0.),
+            options.height * scale,
+        );
+        let center = Vec2::new(
+            (view_min.x + view_max.x) / 2.,
+            view_max.y - options.margin * scale - size.y / 2.,
+        );
+        let translation = (center - geometry.bounds.position).extend(PROGRESS_BAR_Z);
+        if bar_transform.translation != translation || bar_sprite.custom_size != Some(size) {
+            bar_transform.translation = translation;
+            bar_sprite.custom_size = Some(size);
+        }
+    }
+    let uncovered = tile_uncovered_evr.iter().count() > 0;
+    let width = bar_sprite.custom_size.map_or(0., |s| s.x);
+    for (mut transform, mut sprite, tracker) in fills.iter_mut() {
+        if !uncovered && !tracker.is_added() && sprite.custom_size == bar_sprite.custom_size {
+            continue;
+        }
+        // The fill grows from the left end of the bar
+        let progress = board.progress();
+        transform.scale.x = progress;
+        transform.translation.x = -width * (1. - progress) / 2.;
+        sprite.custom_size = bar_sprite.custom_size;
+    }
+}
+
+/// Spawns the progress bar, laid out next frame
+fn spawn_progress_bar(commands: &mut Commands, board: &Board) {
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn_bundle(SpriteBundle {
+                sprite: Sprite { color: BACKGROUND_COLOR, ..Default::default() },
+                ..Default::default()
+            })
+            .insert(ProgressBarView)
+            .insert(Name::new("Progress Bar"))
+            .with_children(|parent| {
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite { color: FILL_COLOR, ..Default::default() },
+                        transform: Transform::from_xyz(0., 0., 0.1),
+                        ..Default::default()
+                    })
+                    .insert(ProgressBarFill)
+                    .insert(Name::new("Progress Bar Fill"));
+            });
+    });
+}
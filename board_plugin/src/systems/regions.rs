@@ -0,0 +1,121 @@
+use crate::components::{BombNeighbor, Coordinates, RegionTint};
+use crate::resources::{BoardAssets, BoardGeometry, BoardOptions, ColorRegions};
+use crate::Board;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+/// Opacity of the region color drawn over the covers
+const COVER_TINT_ALPHA: f32 = 0.35;
+
+/// Paints the tiles of a `ColorRegions` variant board in their region color, the covers getting
+/// a translucent tint, and splits the numbers into one count per region in the region colors.
+/// Texture atlas counters are single sprites, they keep showing the total
+pub fn paint_color_regions(
+    mut commands: Commands,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    mut tiles: Query<
+        (&Coordinates, Option<&mut Sprite>, Option<&mut TextureAtlasSprite>),
+        Changed<Coordinates>,
+    >,
+    numbers: Query<(&Coordinates, &Children), Changed<BombNeighbor>>,
+    mut texts: Query<&mut Text>,
+    tints: Query<(Entity, &Parent), With<RegionTint>>,
+    mut tint_sprites: Query<&mut Sprite, (With<RegionTint>, Without<Coordinates>)>,
+) {
+    let regions = match &board.regions {
+        None => return,
+        Some(r) => r,
+    };
+    let padding = board_options.map(|o| o.tile_padding).unwrap_or_default();
+    let color = |region: u8| region_color(&board_assets, region);
+    for (coords, sprite, atlas_sprite) in tiles.iter_mut() {
+        if let Some(mut sprite) = sprite {
+            sprite.color = color(regions.region_at(*coords));
+        }
+        if let Some(mut sprite) = atlas_sprite {
+            sprite.color = color(regions.region_at(*coords));
+        }
+    }
+    for (coords, children) in numbers.iter() {
+        let sections = region_sections(regions, &board, *coords, &color);
+        for child in children.iter() {
+            let mut text = match texts.get_mut(*child) {
+                Err(_) => continue,
+                Ok(t) => t,
+            };
+            // Counters are spawned at the tile size, several counts sharing its width
+            let font_size = (geometry.tile_size - padding) / sections.len().max(1) as f32;
+            if let Some(style) = text.sections.first().map(|s| s.style.clone()) {
+                text.sections = sections
+                    .iter()
+                    .map(|(value, color)| TextSection {
+                        value: value.clone(),
+                        style: TextStyle { color: *color, font_size, ..style.clone() },
+                    })
+                    .collect();
+            }
+        }
+    }
+    if !board.is_changed() && !geometry.is_changed() {
+        return;
+    }
+    let size = Some(Vec2::splat(geometry.tile_size - padding));
+    let painted: HashMap<Entity, Entity> =
+        tints.iter().map(|(entity, parent)| (parent.0, entity)).collect();
+    for (coords, cover) in board.covered_tiles.iter() {
+        match painted.get(cover) {
+            Some(tint) if geometry.is_changed() => {
+                if let Ok(mut sprite) = tint_sprites.get_mut(*tint) {
+                    sprite.custom_size = size;
+                }
+            }
+            Some(_) => (),
+            None => {
+                let mut tint = color(regions.region_at(*coords));
+                tint.set_a(COVER_TINT_ALPHA);
+                commands.entity(*cover).with_children(|parent| {
+                    parent
+                        .spawn_bundle(SpriteBundle {
+                            sprite: Sprite { color: tint, custom_size: size, ..Default::default() },
+                            transform: Transform::from_xyz(0., 0., 0.5),
+                            ..Default::default()
+                        })
+                        .insert(RegionTint)
+                        .insert(Name::new("Region Tint"));
+                });
+            }
+        }
+    }
+}
+
+/// Color of a region, the colors repeating when there are more regions
+fn region_color(board_assets: &BoardAssets, region: u8) -> Color {
+    let colors = &board_assets.region_colors;
+    match colors.len() {
+        0 => Color::GRAY,
+        len => colors[region as usize % len],
+    }
+}
+
+/// Text sections of the number at `coords`, one count per region with adjacent mines, spaced
+fn region_sections(
+    regions: &ColorRegions,
+    board: &Board,
+    coords: Coordinates,
+    color: impl Fn(u8) -> Color,
+) -> Vec<(String, Color)> {
+    let counts = regions.bomb_counts(&board.tile_map, coords);
+    let mut sections: Vec<_> = counts
+        .iter()
+        .enumerate()
+        .filter(|(_, count)| **count > 0)
+        .map(|(region, count)| (format!("{} ", count), color(region as u8)))
+        .collect();
+    if let Some((value, _)) = sections.last_mut() {
+        value.pop();
+    }
+    sections
+}
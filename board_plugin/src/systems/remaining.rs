@@ -0,0 +1,70 @@
+use crate::components::{BombNeighbor, Coordinates};
+use crate::resources::{BoardAssets, BoardOptions};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Color of the numbers whose mines are all flagged
+const SATISFIED_COLOR: Color = Color::GRAY;
+/// Color of the numbers with more adjacent flags than mines
+const OVERFLAGGED_COLOR: Color = Color::RED;
+
+/// Shows the mines left to flag around numbers instead of their count, with
+/// `BoardOptions::remaining_numbers`. Numbers are updated whenever the board changes, and
+/// restored when the option is disabled. Number badges keep showing the count, and color region
+/// boards and split counts are left alone
+pub fn show_remaining_numbers(
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    mut enabled: Local<bool>,
+    tiles: Query<(&Coordinates, &BombNeighbor, &Children)>,
+    mut texts: Query<&mut Text>,
+    mut atlas_sprites: Query<&mut TextureAtlasSprite>,
+) {
+    if board.regions.is_some() || board_options.as_ref().map_or(false, |o| o.split_counts) {
+        return;
+    }
+    let remaining = board_options.map_or(false, |o| o.remaining_numbers);
+    let toggled = remaining != *enabled;
+    *enabled = remaining;
+    if !toggled && !(remaining && board.is_changed()) {
+        return;
+    }
+    for (coords, neighbor, children) in tiles.iter() {
+        let (value, color) = if remaining {
+            let left = neighbor.count as isize - board.adjacent_marked_count(*coords) as isize;
+            let color = match left {
+                0 => SATISFIED_COLOR,
+                l if l < 0 => OVERFLAGGED_COLOR,
+                l => board_assets.bomb_counter_color(l as i8),
+            };
+            (left as i8, color)
+        } else {
+            (neighbor.count, board_assets.bomb_counter_color(neighbor.count))
+        };
+        for child in children.iter() {
+            if let (Some(atlas), Ok(mut sprite)) =
+                (&board_assets.atlas, atlas_sprites.get_mut(*child))
+            {
+                let index = atlas.number_index(value);
+                if sprite.index != index || sprite.color != color {
+                    sprite.index = index;
+                    sprite.color = color;
+                }
+                continue;
+            }
+            let mut text = match texts.get_mut(*child) {
+                Err(_) => continue,
+                Ok(t) => t,
+            };
+            // Unchanged texts are left untouched so they aren't laid out again
+            let value = value.to_string();
+            let section = &text.sections[0];
+            if section.value != value || section.style.color != color {
+                let section = &mut text.sections[0];
+                section.value = value;
+                section.style.color = color;
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use crate::components::GhostTile;
+use crate::events::{BoardGeneratedEvent, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::resources::{BoardGeometry, GameTimer, Ghost, ReplayAction, ReplayRecorder};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Ghost overlay color
+const GHOST_COLOR: Color = Color::rgba(0.6, 0.8, 1., 0.35);
+
+pub fn record_replay(
+    timer: Res<GameTimer>,
+    mut recorder: ResMut<ReplayRecorder>,
+    mut board_generated_evr: EventReader<BoardGeneratedEvent>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    if let Some(event) = board_generated_evr.iter().last() {
+        recorder.start(event);
+    }
+    let time = timer.elapsed();
+    for event in tile_trigger_evr.iter() {
+        recorder.record(time, ReplayAction::Uncover(event.0));
+    }
+    for event in tile_mark_evr.iter() {
+        recorder.record(time, ReplayAction::Mark(event.0));
+    }
+    for event in tile_chord_evr.iter() {
+        recorder.record(time, ReplayAction::Chord(event.0));
+    }
+}
+
+/// Plays the ghost replay along the game timer, and spawns its overlay
+pub fn advance_ghost(
+    mut commands: Commands,
+    board: Res<Board>,
+    timer: Res<GameTimer>,
+    ghost: Option<ResMut<Ghost>>,
+    mut board_generated_evr: EventReader<BoardGeneratedEvent>,
+) {
+    let mut ghost = match ghost {
+        None => return,
+        Some(g) => g,
+    };
+    if board_generated_evr.iter().count() > 0 {
+        ghost.reset();
+    }
+    if !ghost.replay().matches(&board.tile_map) || ghost.is_finished() {
+        return;
+    }
+    for coords in ghost.advance(timer.elapsed(), &board.tile_map) {
+        commands.entity(board.entity).with_children(|parent| {
+            parent
+                .spawn_bundle(SpriteBundle {
+                    sprite: Sprite { color: GHOST_COLOR, ..Default::default() },
+                    ..Default::default()
+                })
+                .insert(GhostTile(coords))
+                .insert(Name::new(format!("Ghost ({}, {})", coords.x, coords.y)));
+        });
+    }
+}
+
+/// Lays the ghost overlay out on the board tiles
+pub fn layout_ghost(
+    geometry: Res<BoardGeometry>,
+    mut ghost_tiles: Query<(&GhostTile, &mut Transform, &mut Sprite)>,
+) {
+    let size = geometry.tile_size;
+    for (ghost_tile, mut transform, mut sprite) in ghost_tiles.iter_mut() {
+        let coords = ghost_tile.0;
+        transform.translation =
+            Vec3::new(coords.x as f32 * size + size / 2., coords.y as f32 * size + size / 2., 4.);
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::components::NumberBadge;
+use crate::resources::{Board, BoardGeometry, BoardOptions, BoardWindow, VersusRace};
+use crate::systems::layout::{
+    build_board_position, build_tile_size, layout_size, tile_translation, RIVAL_GAP,
+};
+use crate::systems::spawn::badge_transform;
+use crate::{Bounds2, Coordinates};
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::window::{WindowResized, WindowScaleFactorChanged};
+
+/// System fitting the board to the window when it is resized or moved to a display with
+/// another scale factor
+// We lay out the hierarchy again instead of scaling it, since scaled text gets blurry
+pub fn resize_board(
+    board: Res<Board>,
+    mut geometry: ResMut<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_window: Res<BoardWindow>,
+    windows: Res<Windows>,
+    mut window_resized_evr: EventReader<WindowResized>,
+    mut scale_factor_evr: EventReader<WindowScaleFactorChanged>,
+    race: Option<Res<VersusRace>>,
+    tiles: Query<(Entity, &Coordinates)>,
+    children: Query<&Children>,
+    badges: Query<(), With<NumberBadge>>,
+    mut transforms: Query<&mut Transform>,
+    mut sprites: Query<&mut Sprite>,
+    mut atlas_sprites: Query<&mut TextureAtlasSprite>,
+    mut texts: Query<&mut Text>,
+) {
+    let resized = window_resized_evr.iter().filter(|e| e.id == board_window.window).count();
+    let rescaled = scale_factor_evr.iter().filter(|e| e.id == board_window.window).count();
+    if resized + rescaled == 0 {
+        return;
+    }
+    let window = match windows.get(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let options = match board_options {
+        None => BoardOptions::default(),
+        Some(o) => o.clone(),
+    };
+    let window_size = Vec2::new(window.width(), window.height());
+    let scale_factor = window.scale_factor() as f32;
+    let tile_size = build_tile_size(window_size, scale_factor, &options, &board.tile_map);
+    let board_size = Vec2::new(
+        board.tile_map.width() as f32 * tile_size,
+        board.tile_map.height() as f32 * tile_size,
+    );
+    let layout_size = layout_size(&options, board_size, tile_size);
+    let board_position = build_board_position(&options, layout_size, window_size);
+    debug!("Resizing board with a tile size of {}", tile_size);
+
+    if let Ok(mut transform) = transforms.get_mut(board.entity) {
+        transform.translation = board_position;
+    }
+    // The rival board keeps its tiles, scaled to the new tile size
+    if let Some(rival) = race.as_ref().map(|r| &r.rival) {
+        if let Ok(mut transform) = transforms.get_mut(rival.board.entity) {
+            let scale = tile_size / rival.tile_size;
+            transform.translation.x = board_size.x + tile_size * RIVAL_GAP as f32;
+            transform.scale = Vec3::new(scale, scale, 1.);
+        }
+    }
+    // The background and pause cover fill the whole board
+    for entity in std::iter::once(board.background).chain(board.pause_cover) {
+        if let Ok(mut sprite) = sprites.get_mut(entity) {
+            sprite.custom_size = Some(board_size);
+        }
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.translation = (board_size / 2.).extend(transform.translation.z);
+        }
+    }
+    for (entity, coordinates) in tiles.iter() {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            transform.translation = tile_translation(*coordinates, tile_size);
+        }
+        resize_tile_hierarchy(
+            entity,
+            tile_size - options.tile_padding,
+            &children,
+            &badges,
+            &mut transforms,
+            &mut sprites,
+            &mut atlas_sprites,
+            &mut texts,
+        );
+    }
+    *geometry = BoardGeometry {
+        bounds: Bounds2 { position: board_position.xy(), size: board_size },
+        tile_size,
+        view: geometry.view,
+    };
+}
+
+/// Resizes the sprites and texts of a tile and its descendants (cover, bomb, flag..).
+/// Number badges are laid out for a unit tile, so they are scaled instead
+fn resize_tile_hierarchy(
+    entity: Entity,
+    size: f32,
+    children: &Query<&Children>,
+    badges: &Query<(), With<NumberBadge>>,
+    transforms: &mut Query<&mut Transform>,
+    sprites: &mut Query<&mut Sprite>,
+    atlas_sprites: &mut Query<&mut TextureAtlasSprite>,
+    texts: &mut Query<&mut Text>,
+) {
+    if badges.contains(entity) {
+        if let Ok(mut transform) = transforms.get_mut(entity) {
+            *transform = badge_transform(size);
+        }
+        return;
+    }
+    if let Ok(mut sprite) = sprites.get_mut(entity) {
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+    if let Ok(mut sprite) = atlas_sprites.get_mut(entity) {
+        sprite.custom_size = Some(Vec2::splat(size));
+    }
+    if let Ok(mut text) = texts.get_mut(entity) {
+        for section in text.sections.iter_mut() {
+            section.style.font_size = size;
+        }
+    }
+    if let Ok(entity_children) = children.get(entity) {
+        for child in entity_children.iter() {
+            resize_tile_hierarchy(
+                *child,
+                size,
+                children,
+                badges,
+                transforms,
+                sprites,
+                atlas_sprites,
+                texts,
+            );
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use crate::Board;
+use bevy::ecs::schedule::ShouldRun;
+use bevy::prelude::*;
+
+/// Keeps the board systems inert while no board exists, before the first one is spawned or
+/// after it is cleaned up. Piped from the running state criteria
+pub fn board_exists(In(should_run): In<ShouldRun>, board: Option<Res<Board>>) -> ShouldRun {
+    if board.is_some() {
+        return should_run;
+    }
+    match should_run {
+        ShouldRun::Yes => ShouldRun::No,
+        ShouldRun::YesAndCheckAgain => ShouldRun::NoAndCheckAgain,
+        no => no,
+    }
+}
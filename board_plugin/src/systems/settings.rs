@@ -0,0 +1,35 @@
+use crate::resources::{BoardAssets, InputBindings, Settings};
+use bevy::app::AppExit;
+use bevy::prelude::*;
+
+/// Applies the settings bindings and palette whenever they change. At first, only the ones
+/// differing from the defaults replace those of the host app, so default settings keep its
+/// customized `InputBindings` and `BoardAssets`
+pub fn apply_settings(
+    settings: Res<Settings>,
+    mut bindings: ResMut<InputBindings>,
+    mut board_assets: ResMut<BoardAssets>,
+    mut applied: Local<bool>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+    let initial = !std::mem::replace(&mut *applied, true);
+    let defaults = Settings::default();
+    if !initial || settings.bindings != defaults.bindings {
+        *bindings = settings.bindings.clone();
+    }
+    if !initial || settings.palette != defaults.palette {
+        board_assets.set_palette(settings.palette);
+    }
+}
+
+/// Saves the settings when the app exits
+pub fn save_settings(settings: Res<Settings>, mut app_exit_evr: EventReader<AppExit>) {
+    if app_exit_evr.iter().count() == 0 {
+        return;
+    }
+    if let Err(e) = settings.save() {
+        error!("Failed to save settings: {}", e);
+    }
+}
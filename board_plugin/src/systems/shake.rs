@@ -0,0 +1,54 @@
+use crate::events::BombExplodedEvent;
+use crate::resources::{BoardOptions, BoardWindow};
+use bevy::prelude::*;
+use rand::{thread_rng, Rng};
+
+/// Running camera shake
+pub struct Shake {
+    timer: Timer,
+    amplitude: f32,
+    /// Offset currently applied to the camera
+    offset: Vec3,
+}
+
+pub fn shake_camera(
+    time: Res<Time>,
+    board_options: Option<Res<BoardOptions>>,
+    board_window: Res<BoardWindow>,
+    mut shake: Local<Option<Shake>>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+    mut cameras: Query<(&Camera, &mut Transform)>,
+) {
+    if bomb_exploded_evr.iter().count() > 0 {
+        if let Some(options) = board_options.and_then(|o| o.camera_shake) {
+            let offset = shake.as_ref().map(|s| s.offset).unwrap_or_default();
+            *shake = Some(Shake {
+                timer: Timer::from_seconds(options.duration, false),
+                amplitude: options.amplitude,
+                offset,
+            });
+        }
+    }
+    let current = match shake.as_mut() {
+        None => return,
+        Some(s) => s,
+    };
+    current.timer.tick(time.delta());
+    // The shake fades out, ending with no offset so the camera is restored
+    let new_offset = if current.timer.finished() {
+        Vec3::ZERO
+    } else {
+        let strength = current.amplitude * (1. - current.timer.percent());
+        let mut rng = thread_rng();
+        Vec3::new(rng.gen_range(-1. ..1.), rng.gen_range(-1. ..1.), 0.) * strength
+    };
+    for (camera, mut transform) in cameras.iter_mut() {
+        if board_window.is_board_camera(camera) {
+            transform.translation += new_offset - current.offset;
+        }
+    }
+    current.offset = new_offset;
+    if current.timer.finished() {
+        *shake = None;
+    }
+}
@@ -0,0 +1,25 @@
+use crate::events::BoardChangedEvent;
+use crate::resources::BoardSnapshot;
+use crate::Board;
+use bevy::prelude::*;
+
+/// Sends the visible tiles changed since the last board change
+pub fn send_board_changes(
+    board: Res<Board>,
+    mut previous: Local<Option<(Entity, BoardSnapshot)>>,
+    mut board_changed_ewr: EventWriter<BoardChangedEvent>,
+) {
+    if !board.is_changed() {
+        return;
+    }
+    let snapshot = board.snapshot();
+    let changes = match previous.as_ref() {
+        Some((entity, previous)) if *entity == board.entity => snapshot.diff(previous),
+        // New boards are sent whole
+        _ => snapshot.diff(&BoardSnapshot { width: 0, height: 0, tiles: Vec::new() }),
+    };
+    if !changes.is_empty() {
+        board_changed_ewr.send(BoardChangedEvent { changes });
+    }
+    *previous = Some((board.entity, snapshot));
+}
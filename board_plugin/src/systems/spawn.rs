@@ -0,0 +1,846 @@
+use crate::components::{
+    Bomb, BombNeighbor, BombPop, BombSprite, CoverSprite, Cursor, Detonated, NumberBadge,
+    NumberText, TileRoot, TutorialHighlight, TutorialMessage,
+};
+use crate::events::BoardGeneratedEvent;
+use crate::resources::{
+    Board, BoardAssets, BoardGeometry, BoardLoading, BoardModifiers, BoardOptions, BoardState,
+    BoardVariant, ColorRegions, Countdown, Lives, MineKind, PowerUps, RivalBoard, SpriteMaterial,
+    Tile, TileChunks, TileMap, VersusRace,
+};
+use crate::systems::layout::{
+    build_board_position, build_tile_size, layout_size, tile_translation, RIVAL_GAP,
+};
+use crate::{BoardRoot, Bounds2, Coordinates, Uncover};
+use bevy::ecs::system::EntityCommands;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+use std::sync::Arc;
+
+/// Number badge center, relative to the tile center, in tile sizes
+const BADGE_OFFSET: Vec2 = Vec2::new(0.3, -0.3);
+/// Spacing between number badge dots, in tile sizes
+const BADGE_DOT_SPACING: f32 = 0.1;
+/// Number badge dot size, in tile sizes
+const BADGE_DOT_SIZE: f32 = 0.07;
+
+/// Spawns the board entities of the generated `tile_map` and inserts the board resources
+pub fn spawn_board(
+    commands: &mut Commands,
+    options: &BoardOptions,
+    tile_map: TileMap,
+    mut pooled: Vec<Entity>,
+    board_assets: &BoardAssets,
+    window_size: Vec2,
+    scale_factor: f32,
+    board_generated_ewr: &mut EventWriter<BoardGeneratedEvent>,
+) {
+    let (width, height) = options.map_size;
+    let _span =
+        info_span!("spawn_board", width, height, chunked = options.chunk_size.is_some()).entered();
+    let tile_size = build_tile_size(window_size, scale_factor, options, &tile_map);
+    let board_size =
+        Vec2::new(tile_map.width() as f32 * tile_size, tile_map.height() as f32 * tile_size);
+    let layout_size = layout_size(options, board_size, tile_size);
+    let board_position = build_board_position(options, layout_size, window_size);
+    // Shared with the rival board of a versus race
+    let tile_map = Arc::new(tile_map);
+    let mut covered_tiles = match options.chunk_size {
+        None => HashMap::with_capacity((width * height).into()),
+        Some(_) => HashMap::new(),
+    };
+    let chunks =
+        options.chunk_size.map(|size| TileChunks::new(size, &tile_map, options.safe_start));
+    let mut loading = BoardLoading::new(tile_map.tile_count());
+    let mine_kinds = options.mine_kinds.assign(&tile_map);
+    let regions = match options.variant {
+        BoardVariant::ColorRegions { regions } => Some(ColorRegions::generate(&tile_map, regions)),
+        _ => None,
+    };
+
+    let mut pause_cover = None;
+    let mut background = None;
+    let mut rival = None;
+
+    let board_entity = commands
+        .spawn()
+        .insert(Name::new("Board"))
+        .insert(BoardRoot)
+        .insert(Transform::from_translation(board_position))
+        .insert(GlobalTransform::default())
+        .with_children(|parent| {
+            background = Some(spawn_background(board_size, &board_assets.board_material, parent));
+            spawn_cursor(&board_assets.cursor_material, parent);
+            spawn_tutorial_highlight(board_assets, parent);
+            if options.hide_on_pause {
+                pause_cover = Some(spawn_pause_cover(board_size, parent));
+            }
+            if options.versus {
+                rival = Some(spawn_rival_board(
+                    parent,
+                    &tile_map,
+                    options,
+                    board_assets,
+                    board_size,
+                    tile_size,
+                ));
+            }
+        })
+        .id();
+    match options.chunk_size {
+        // Chunked boards spawn their tiles around the camera view later on
+        Some(_) => loading.spawned = loading.total,
+        None => {
+            spawn_board_tiles(
+                commands,
+                board_entity,
+                &tile_map,
+                tile_size,
+                options.tile_padding,
+                board_assets,
+                &mine_kinds,
+                &mut pooled,
+                &mut covered_tiles,
+                &mut loading,
+                options.spawn_budget.map_or(usize::MAX, |budget| budget.max(1) as usize),
+            );
+            // The previous board was bigger, or the next tiles are spawned in later frames
+            for entity in pooled {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+    board_generated_ewr.send(BoardGeneratedEvent {
+        width: tile_map.width(),
+        height: tile_map.height(),
+        bomb_count: tile_map.bomb_count(),
+        seed: tile_map.seed(),
+    });
+    let async_cascade =
+        options.async_cascade.map_or(false, |min| tile_map.tile_count() >= min as usize);
+    commands.insert_resource(BoardGeometry {
+        bounds: Bounds2 { position: board_position.xy(), size: board_size },
+        tile_size,
+        view: options.view,
+    });
+    match rival {
+        None => commands.remove_resource::<VersusRace>(),
+        Some(mut rival) => {
+            // The rival board starts from the same safe start opening
+            if let Some(start) = options.safe_start.then(|| tile_map.safe_start()).flatten() {
+                for cover in rival.uncover(start).0 {
+                    commands.entity(cover).despawn_recursive();
+                }
+            }
+            commands.insert_resource(VersusRace::new(rival));
+        }
+    }
+    commands.insert_resource(Board {
+        tile_map,
+        covered_tiles,
+        marked_tiles: HashMap::new(),
+        variant: options.variant,
+        detonated_tiles: Vec::new(),
+        entity: board_entity,
+        background: background.expect("Board background was not spawned"),
+        pause_cover,
+        chunks,
+        fog_radius: options.fog_radius,
+        cascade: options.cascade,
+        async_cascade,
+        openings: Vec::new(),
+        modifiers: BoardModifiers::new(&options.tile_modifiers),
+        mine_kinds,
+        regions,
+    });
+    let state = if loading.is_done() { BoardState::Playing } else { BoardState::Loading };
+    commands.insert_resource(state);
+    commands.insert_resource(Lives::new(options.lives));
+    match options.time_limit {
+        None => commands.remove_resource::<Countdown>(),
+        Some(limit) => commands.insert_resource(Countdown::new(limit)),
+    }
+    match &options.power_ups {
+        None => commands.remove_resource::<PowerUps>(),
+        Some(power_ups) => commands.insert_resource(PowerUps::new(power_ups)),
+    }
+    if options.safe_start && loading.is_done() {
+        if let Some(entity) = loading.safe_start {
+            commands.entity(entity).insert(Uncover);
+        }
+    }
+    commands.insert_resource(loading);
+}
+
+fn spawn_background(
+    board_size: Vec2,
+    material: &SpriteMaterial,
+    parent: &mut ChildBuilder,
+) -> Entity {
+    // We spawn the board background sprite at the center of the board,
+    // since the sprite pivot is centered
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: material.color,
+                custom_size: Some(board_size),
+                ..Default::default()
+            },
+            texture: material.texture.clone(),
+            transform: Transform::from_xyz(board_size.x / 2., board_size.y / 2., 0.),
+            ..Default::default()
+        })
+        .insert(Name::new("Background"))
+        .id()
+}
+
+/// Spawns the rival board of a versus race under the main board, to its right. Its tiles
+/// only hold sprites, so the main board systems leave them alone
+fn spawn_rival_board(
+    parent: &mut ChildBuilder,
+    tile_map: &Arc<TileMap>,
+    options: &BoardOptions,
+    board_assets: &BoardAssets,
+    board_size: Vec2,
+    tile_size: f32,
+) -> RivalBoard {
+    let size = tile_size - options.tile_padding;
+    let mut covered_tiles = HashMap::with_capacity(tile_map.tile_count());
+    let cursor = Coordinates { x: tile_map.width() / 2, y: tile_map.height() / 2 };
+    let mut cursor_entity = None;
+    let mut background = None;
+    let entity = parent
+        .spawn()
+        .insert(Name::new("Rival Board"))
+        .insert(Transform::from_xyz(board_size.x + tile_size * RIVAL_GAP as f32, 0., 0.))
+        .insert(GlobalTransform::default())
+        .with_children(|parent| {
+            background = Some(spawn_background(board_size, &board_assets.board_material, parent));
+            for (coordinates, tile) in tile_map.tiles() {
+                let mut tile_entity = parent.spawn();
+                let translation = tile_translation(coordinates, tile_size);
+                insert_sprite(
+                    &mut tile_entity,
+                    &board_assets.tile_material,
+                    board_assets,
+                    size,
+                    Transform::from_translation(translation),
+                );
+                tile_entity.with_children(|parent| {
+                    match tile {
+                        Tile::BombNeighbor(count) => {
+                            spawn_bomb_count(parent, count, board_assets, size, 1.);
+                        }
+                        tile if tile.is_bomb() => {
+                            insert_sprite(
+                                &mut parent.spawn(),
+                                &board_assets.bomb_material,
+                                board_assets,
+                                size,
+                                Transform::from_xyz(0., 0., 1.),
+                            );
+                        }
+                        _ => (),
+                    }
+                    let mut cover = parent.spawn();
+                    insert_sprite(
+                        &mut cover,
+                        &board_assets.covered_tile_material,
+                        board_assets,
+                        size,
+                        Transform::from_xyz(0., 0., 2.),
+                    );
+                    covered_tiles.insert(coordinates, cover.id());
+                });
+            }
+            let material = &board_assets.cursor_material;
+            let mut translation = tile_translation(cursor, tile_size);
+            translation.z = 5.;
+            cursor_entity = Some(
+                parent
+                    .spawn_bundle(SpriteBundle {
+                        sprite: Sprite {
+                            color: material.color,
+                            custom_size: Some(Vec2::splat(tile_size)),
+                            ..Default::default()
+                        },
+                        texture: material.texture.clone(),
+                        transform: Transform::from_translation(translation),
+                        ..Default::default()
+                    })
+                    .insert(Name::new("Rival Cursor"))
+                    .id(),
+            );
+        })
+        .id();
+    let board = Board {
+        tile_map: Arc::clone(tile_map),
+        covered_tiles,
+        marked_tiles: HashMap::new(),
+        variant: options.variant,
+        detonated_tiles: Vec::new(),
+        entity,
+        background: background.expect("Rival background was not spawned"),
+        pause_cover: None,
+        chunks: None,
+        fog_radius: None,
+        cascade: options.cascade,
+        async_cascade: false,
+        openings: Vec::new(),
+        modifiers: BoardModifiers::default(),
+        mine_kinds: HashMap::new(),
+        regions: None,
+    };
+    RivalBoard {
+        board,
+        cursor,
+        cursor_entity: cursor_entity.expect("Rival cursor was not spawned"),
+        tile_size,
+    }
+}
+
+fn spawn_cursor(material: &SpriteMaterial, parent: &mut ChildBuilder) {
+    // The cursor is placed and sized by its display system
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite { color: material.color, ..Default::default() },
+            texture: material.texture.clone(),
+            transform: Transform::from_xyz(0., 0., 5.),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(Cursor)
+        .insert(Name::new("Keyboard Cursor"));
+}
+
+fn spawn_tutorial_highlight(board_assets: &BoardAssets, parent: &mut ChildBuilder) {
+    // The highlight and its message are placed and sized by their display system
+    let material = &board_assets.tutorial_material;
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite { color: material.color, ..Default::default() },
+            texture: material.texture.clone(),
+            transform: Transform::from_xyz(0., 0., 6.),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(TutorialHighlight)
+        .insert(Name::new("Tutorial Highlight"))
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(Text2dBundle {
+                    text: Text::with_section(
+                        "",
+                        TextStyle {
+                            color: Color::WHITE,
+                            font: board_assets.bomb_counter_font.clone(),
+                            font_size: 1.,
+                        },
+                        TextAlignment {
+                            vertical: VerticalAlign::Center,
+                            horizontal: HorizontalAlign::Center,
+                        },
+                    ),
+                    transform: Transform::from_xyz(0., 0., 1.),
+                    visibility: Visibility { is_visible: false },
+                    ..Default::default()
+                })
+                .insert(TutorialMessage)
+                .insert(Name::new("Tutorial Message"));
+        });
+}
+
+fn spawn_pause_cover(board_size: Vec2, parent: &mut ChildBuilder) -> Entity {
+    // The cover is drawn above every tile and hidden until the board is paused
+    parent
+        .spawn_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: Color::DARK_GRAY,
+                custom_size: Some(board_size),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(board_size.x / 2., board_size.y / 2., 10.),
+            visibility: Visibility { is_visible: false },
+            ..Default::default()
+        })
+        .insert(Name::new("Pause Cover"))
+        .id()
+}
+
+/// Spawns a loading text over the current board while the next one is generated, despawned
+/// with the board
+pub fn spawn_loading_indicator(
+    commands: &mut Commands,
+    board: &Board,
+    geometry: &BoardGeometry,
+    board_assets: &BoardAssets,
+) {
+    let center = geometry.bounds.size / 2.;
+    commands.entity(board.entity).with_children(|parent| {
+        parent
+            .spawn_bundle(Text2dBundle {
+                text: Text::with_section(
+                    "Generating...",
+                    TextStyle {
+                        color: Color::WHITE,
+                        font: board_assets.bomb_counter_font.clone(),
+                        font_size: geometry.tile_size,
+                    },
+                    TextAlignment {
+                        vertical: VerticalAlign::Center,
+                        horizontal: HorizontalAlign::Center,
+                    },
+                ),
+                // Above the tiles and the pause cover
+                transform: Transform::from_xyz(center.x, center.y, 11.),
+                ..Default::default()
+            })
+            .insert(Name::new("Loading Indicator"));
+    });
+}
+
+// TODO: Refactor this to builder
+/// Spawns the tiles from the bottom left `min` to the top right `max` exclusive coordinates
+pub fn spawn_tiles(
+    parent: &mut ChildBuilder,
+    tile_map: &TileMap,
+    (min, max): (Coordinates, Coordinates),
+    size: f32,
+    padding: f32,
+    board_assets: &BoardAssets,
+    mine_kinds: &HashMap<Coordinates, MineKind>,
+    is_covered: impl Fn(&Coordinates) -> bool,
+    detonated: &[Coordinates],
+    covered_tiles: &mut HashMap<Coordinates, Entity>,
+    safe_start_entity: &mut Option<Entity>,
+) {
+    // Tiles
+    for y in min.y..max.y {
+        for x in min.x..max.x {
+            let tile = &tile_map[y as usize][x as usize];
+            // Holes are left out of the board shape
+            if *tile == Tile::Hole {
+                continue;
+            }
+            let coordinates = Coordinates { x, y };
+            build_tile(
+                &mut parent.spawn(),
+                tile,
+                mine_kinds.get(&coordinates).copied().unwrap_or_default(),
+                coordinates,
+                size,
+                padding,
+                board_assets,
+                is_covered(&coordinates),
+                detonated.contains(&coordinates),
+                covered_tiles,
+                safe_start_entity,
+            );
+        }
+    }
+}
+
+/// Spawns the next `budget` tiles of a board that isn't chunked under `board_entity`, in
+/// row-major order from where `loading` stopped. The `pooled` tile entities of the previous
+/// board are reconfigured before new ones get spawned, sparing restarts of large boards most
+/// of the spawning. The tiles of a call are parented in a single batch, `Commands` not
+/// batching the spawning of entities with children
+pub fn spawn_board_tiles(
+    commands: &mut Commands,
+    board_entity: Entity,
+    tile_map: &TileMap,
+    size: f32,
+    padding: f32,
+    board_assets: &BoardAssets,
+    mine_kinds: &HashMap<Coordinates, MineKind>,
+    pooled: &mut Vec<Entity>,
+    covered_tiles: &mut HashMap<Coordinates, Entity>,
+    loading: &mut BoardLoading,
+    budget: usize,
+) {
+    let available = pooled.len();
+    let width = tile_map.width() as usize;
+    let end = width * tile_map.height() as usize;
+    let mut tiles = Vec::with_capacity(budget.min(loading.total - loading.spawned));
+    while loading.next < end && tiles.len() < budget {
+        let (x, y) = (loading.next % width, loading.next / width);
+        loading.next += 1;
+        let tile = &tile_map[y][x];
+        // Holes are left out of the board shape
+        if *tile == Tile::Hole {
+            continue;
+        }
+        let mut tile_entity = match pooled.pop() {
+            Some(entity) => commands.entity(entity),
+            None => commands.spawn(),
+        };
+        let coordinates = Coordinates { x: x as u16, y: y as u16 };
+        build_tile(
+            &mut tile_entity,
+            tile,
+            mine_kinds.get(&coordinates).copied().unwrap_or_default(),
+            coordinates,
+            size,
+            padding,
+            board_assets,
+            true,
+            false,
+            covered_tiles,
+            &mut loading.safe_start,
+        );
+        tiles.push(tile_entity.id());
+    }
+    loading.spawned += tiles.len();
+    commands.entity(board_entity).push_children(&tiles);
+    trace!(reused = available.min(tiles.len()), "Spawned {} board tiles", tiles.len());
+}
+
+/// Detaches the tile entities from the `board` about to be despawned, stripping their
+/// children, layout and sprite components so `spawn_board_tiles` can reconfigure them. The
+/// sprites are removed as the next board may be drawn with or without the tile atlas
+pub fn release_tiles(
+    commands: &mut Commands,
+    board: &Board,
+    tiles: &Query<(Entity, &Parent, Option<&Children>), With<Coordinates>>,
+) -> Vec<Entity> {
+    // Chunked boards spawn and despawn their tiles on their own
+    if board.chunks.is_some() {
+        return Vec::new();
+    }
+    let mut pooled = Vec::new();
+    for (entity, parent, children) in tiles.iter() {
+        if parent.0 != board.entity {
+            continue;
+        }
+        for child in children.into_iter().flat_map(|c| c.iter()) {
+            commands.entity(*child).despawn_recursive();
+        }
+        commands
+            .entity(entity)
+            .remove::<Bomb>()
+            .remove::<BombNeighbor>()
+            .remove::<Detonated>()
+            .remove::<BombPop>()
+            .remove::<Sprite>()
+            .remove::<Handle<Image>>()
+            .remove::<TextureAtlasSprite>()
+            .remove::<Handle<TextureAtlas>>();
+        pooled.push(entity);
+    }
+    commands.entity(board.entity).remove_children(&pooled);
+    pooled
+}
+
+/// Inserts the components and children of the `tile` at `coordinates` into `tile_entity`
+fn build_tile(
+    tile_entity: &mut EntityCommands,
+    tile: &Tile,
+    mine_kind: MineKind,
+    coordinates: Coordinates,
+    size: f32,
+    padding: f32,
+    board_assets: &BoardAssets,
+    covered: bool,
+    detonated: bool,
+    covered_tiles: &mut HashMap<Coordinates, Entity>,
+    safe_start_entity: &mut Option<Entity>,
+) {
+    // Refactor to struct VisualTile
+    insert_tile(
+        tile_entity,
+        padding,
+        size,
+        coordinates,
+        board_assets,
+        covered,
+        covered_tiles,
+        safe_start_entity,
+        tile,
+    );
+    if detonated {
+        tile_entity.insert(Detonated);
+    }
+    insert_tile_contents(tile_entity, tile, mine_kind, size, padding, board_assets);
+}
+
+/// Inserts the bomb or bomb counter of the `tile` into `tile_entity`
+pub fn insert_tile_contents(
+    tile_entity: &mut EntityCommands,
+    tile: &Tile,
+    mine_kind: MineKind,
+    size: f32,
+    padding: f32,
+    board_assets: &BoardAssets,
+) {
+    match tile {
+        Tile::Bomb => {
+            let material = match mine_kind {
+                MineKind::Standard => &board_assets.bomb_material,
+                MineKind::Mega => &board_assets.mega_mine_material,
+                MineKind::Decoy => &board_assets.decoy_mine_material,
+            };
+            insert_bomb(tile_entity, material, board_assets, padding, size);
+        }
+        Tile::ArmoredBomb(armor) => {
+            let material = &board_assets.armored_bomb_material;
+            insert_bomb(tile_entity, material, board_assets, padding, size);
+            // The armor is displayed over the bomb
+            tile_entity.with_children(|parent| {
+                spawn_bomb_count(parent, *armor as i8, board_assets, size - padding, 2.);
+            });
+        }
+        Tile::AntiMine => {
+            let material = &board_assets.anti_mine_material;
+            insert_bomb(tile_entity, material, board_assets, padding, size);
+        }
+        Tile::BombNeighbor(count) => {
+            insert_bomb_neighbor(tile_entity, board_assets, *count, size, padding);
+        }
+        Tile::Empty | Tile::Hole => (),
+    }
+}
+
+//noinspection RsTypeCheck
+fn insert_bomb(
+    tile_entity: &mut EntityCommands,
+    bomb_material: &SpriteMaterial,
+    board_assets: &BoardAssets,
+    padding: f32,
+    size: f32,
+) {
+    // If the tile is a bomb we add the matching component and a sprite child
+    tile_entity.insert(Bomb);
+    tile_entity.with_children(|parent| {
+        let mut sprite = parent.spawn();
+        insert_sprite(
+            &mut sprite,
+            bomb_material,
+            board_assets,
+            size - padding,
+            Transform::from_xyz(0., 0., 1.),
+        );
+        sprite.insert(BombSprite);
+    });
+}
+
+//noinspection RsTypeCheck
+fn insert_bomb_neighbor(
+    tile_entity: &mut EntityCommands,
+    board_assets: &BoardAssets,
+    count: i8,
+    size: f32,
+    padding: f32,
+) {
+    // If the tile is a bomb neighbour we add the matching component and a text child
+    tile_entity.insert(BombNeighbor { count });
+    tile_entity.with_children(|parent| {
+        spawn_bomb_count(parent, count, board_assets, size - padding, 1.);
+        if board_assets.number_badges && count != 0 {
+            spawn_number_badge(parent, board_assets, count, size - padding);
+        }
+    });
+}
+
+/// Spawns a dice-like dot pattern of `count` in a corner of the tile, so counters can be told
+/// apart without relying on their color
+fn spawn_number_badge(parent: &mut ChildBuilder, board_assets: &BoardAssets, count: i8, size: f32) {
+    let color = board_assets.bomb_counter_color(count);
+    parent
+        .spawn()
+        .insert(NumberBadge)
+        .insert(badge_transform(size))
+        .insert(GlobalTransform::default())
+        .with_children(|parent| {
+            for (x, y) in badge_dots(count.unsigned_abs()) {
+                parent.spawn_bundle(SpriteBundle {
+                    sprite: Sprite {
+                        color,
+                        custom_size: Some(Vec2::splat(BADGE_DOT_SIZE)),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(
+                        *x as f32 * BADGE_DOT_SPACING,
+                        *y as f32 * BADGE_DOT_SPACING,
+                        0.,
+                    ),
+                    ..Default::default()
+                });
+            }
+        });
+}
+
+/// Number badge transform for a tile of `size`
+pub fn badge_transform(size: f32) -> Transform {
+    Transform::from_translation((BADGE_OFFSET * size).extend(1.))
+        .with_scale(Vec3::new(size, size, 1.))
+}
+
+/// Dot positions of a number badge on a 3x3 grid, counts above 9 showing as 9
+fn badge_dots(count: u8) -> &'static [(i8, i8)] {
+    match count.min(9) {
+        0 => &[],
+        1 => &[(0, 0)],
+        2 => &[(-1, 1), (1, -1)],
+        3 => &[(-1, 1), (0, 0), (1, -1)],
+        4 => &[(-1, 1), (1, 1), (-1, -1), (1, -1)],
+        5 => &[(-1, 1), (1, 1), (0, 0), (-1, -1), (1, -1)],
+        6 => &[(-1, 1), (1, 1), (-1, 0), (1, 0), (-1, -1), (1, -1)],
+        7 => &[(-1, 1), (1, 1), (-1, 0), (0, 0), (1, 0), (-1, -1), (1, -1)],
+        8 => &[(-1, 1), (0, 1), (1, 1), (-1, 0), (1, 0), (-1, -1), (0, -1), (1, -1)],
+        _ => &[(-1, 1), (0, 1), (1, 1), (-1, 0), (0, 0), (1, 0), (-1, -1), (0, -1), (1, -1)],
+    }
+}
+
+fn insert_tile(
+    tile_entity: &mut EntityCommands,
+    padding: f32,
+    size: f32,
+    coordinates: Coordinates,
+    board_assets: &BoardAssets,
+    covered: bool,
+    covered_tiles: &mut HashMap<Coordinates, Entity>,
+    safe_start_entity: &mut Option<Entity>,
+    tile: &Tile,
+) {
+    insert_sprite(
+        tile_entity,
+        &board_assets.tile_material,
+        board_assets,
+        size - padding,
+        Transform::from_translation(tile_translation(coordinates, size)),
+    );
+    tile_entity.insert(coordinates).insert(TileRoot);
+    // Names are only displayed by the inspector, and formatting one per tile is costly on
+    // huge boards
+    #[cfg(feature = "debug")]
+    tile_entity.insert(Name::new(format!("Tile {}", coordinates)));
+    if !covered {
+        return;
+    }
+    insert_cover(
+        tile_entity,
+        covered_tiles,
+        board_assets,
+        padding,
+        size,
+        coordinates,
+        safe_start_entity,
+        tile,
+    );
+}
+
+fn insert_cover(
+    tile_entity: &mut EntityCommands,
+    covered_tiles: &mut HashMap<Coordinates, Entity>,
+    board_assets: &BoardAssets,
+    padding: f32,
+    size: f32,
+    coordinates: Coordinates,
+    safe_start_entity: &mut Option<Entity>,
+    tile: &Tile,
+) {
+    tile_entity.with_children(|parent| {
+        let mut cover = parent.spawn();
+        insert_sprite(
+            &mut cover,
+            &board_assets.covered_tile_material,
+            board_assets,
+            size - padding,
+            Transform::from_xyz(0., 0., 2.),
+        );
+        let entity = cover.insert(Name::new("Tile Cover")).insert(CoverSprite).id();
+        covered_tiles.insert(coordinates, entity);
+        if safe_start_entity.is_none() && *tile == Tile::Empty {
+            *safe_start_entity = Some(entity);
+        }
+    });
+}
+
+/// Inserts the sprite of a tile visual, drawn from the tile atlas when it is built
+fn insert_sprite(
+    entity: &mut EntityCommands,
+    material: &SpriteMaterial,
+    board_assets: &BoardAssets,
+    size: f32,
+    transform: Transform,
+) {
+    let atlas = board_assets.atlas.as_ref();
+    match atlas.and_then(|a| Some((a, a.texture_index(&material.texture)?))) {
+        Some((atlas, index)) => entity.insert_bundle(SpriteSheetBundle {
+            sprite: TextureAtlasSprite {
+                color: material.color,
+                index,
+                custom_size: Some(Vec2::splat(size)),
+                ..Default::default()
+            },
+            texture_atlas: atlas.atlas.clone(),
+            transform,
+            ..Default::default()
+        }),
+        None => entity.insert_bundle(SpriteBundle {
+            sprite: Sprite {
+                color: material.color,
+                custom_size: Some(Vec2::splat(size)),
+                ..Default::default()
+            },
+            texture: material.texture.clone(),
+            transform,
+            ..Default::default()
+        }),
+    };
+}
+
+/// Spawns a bomb counter at the `z` depth, as a text or drawn from the tile atlas
+fn spawn_bomb_count(
+    parent: &mut ChildBuilder,
+    count: i8,
+    board_assets: &BoardAssets,
+    size: f32,
+    z: f32,
+) {
+    match &board_assets.atlas {
+        Some(atlas) => {
+            parent
+                .spawn_bundle(SpriteSheetBundle {
+                    sprite: TextureAtlasSprite {
+                        color: board_assets.bomb_counter_color(count),
+                        index: atlas.number_index(count),
+                        custom_size: Some(Vec2::splat(size)),
+                        ..Default::default()
+                    },
+                    texture_atlas: atlas.atlas.clone(),
+                    transform: Transform::from_xyz(0., 0., z),
+                    ..Default::default()
+                })
+                .insert(NumberText);
+        }
+        None => {
+            let mut bundle = bomb_count_text_bundle(count, board_assets, size);
+            bundle.transform.translation.z = z;
+            parent.spawn_bundle(bundle).insert(NumberText);
+        }
+    }
+}
+
+/// Generates the bomb counter text 2D Bundle for a given value
+fn bomb_count_text_bundle(count: i8, board_assets: &BoardAssets, size: f32) -> Text2dBundle {
+    // We retrieve the text and the correct color
+    let (text, color) = (count.to_string(), board_assets.bomb_counter_color(count));
+    let font = board_assets.bomb_counter_font.clone();
+    // We generate a text bundle
+    Text2dBundle {
+        text: Text {
+            sections: vec![TextSection {
+                value: text,
+                style: TextStyle { color, font, font_size: size },
+            }],
+            alignment: TextAlignment {
+                vertical: VerticalAlign::Center,
+                horizontal: HorizontalAlign::Center,
+            },
+        },
+        transform: Transform::from_xyz(0., 0., 1.),
+        ..Default::default()
+    }
+}
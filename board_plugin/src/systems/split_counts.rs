@@ -0,0 +1,64 @@
+use crate::components::{BombNeighbor, Coordinates};
+use crate::resources::{BoardAssets, BoardGeometry, BoardOptions};
+use crate::Board;
+use bevy::prelude::*;
+
+/// Shows the orthogonal and diagonal mine counts of the numbers one above the other, at half
+/// size, with `BoardOptions::split_counts`. Numbers are restored when the option is disabled.
+/// Color region boards keep their region counts, and texture atlas counters their total
+pub fn show_split_counts(
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    mut enabled: Local<bool>,
+    numbers: Query<(&Coordinates, &BombNeighbor, &Children, ChangeTrackers<BombNeighbor>)>,
+    mut texts: Query<&mut Text>,
+) {
+    if board.regions.is_some() {
+        return;
+    }
+    let (split, padding) = board_options.map_or((false, 0.), |o| (o.split_counts, o.tile_padding));
+    let toggled = split != *enabled;
+    *enabled = split;
+    if !split && !toggled {
+        return;
+    }
+    // Counters are spawned at the tile size
+    let size = geometry.tile_size - padding;
+    for (coords, neighbor, children, tracker) in numbers.iter() {
+        // New numbers are spawned with their total
+        if !toggled && !tracker.is_changed() {
+            continue;
+        }
+        let section = |value: String, count, font_size| {
+            (value, board_assets.bomb_counter_color(count), font_size)
+        };
+        let sections = if split {
+            let (orthogonal, diagonal) = board.tile_map.split_bomb_count(*coords);
+            vec![
+                section(format!("{}\n", orthogonal), orthogonal, size / 2.),
+                section(diagonal.to_string(), diagonal, size / 2.),
+            ]
+        } else {
+            vec![section(neighbor.count.to_string(), neighbor.count, size)]
+        };
+        for child in children.iter() {
+            let mut text = match texts.get_mut(*child) {
+                Err(_) => continue,
+                Ok(t) => t,
+            };
+            let font = match text.sections.first() {
+                None => continue,
+                Some(s) => s.style.font.clone(),
+            };
+            text.sections = sections
+                .iter()
+                .map(|(value, color, font_size)| TextSection {
+                    value: value.clone(),
+                    style: TextStyle { font: font.clone(), font_size: *font_size, color: *color },
+                })
+                .collect();
+        }
+    }
+}
@@ -0,0 +1,64 @@
+use crate::events::{
+    BoardCompletedEvent, BombExplodedEvent, TileChordEvent, TileTriggerEvent, TimeExpiredEvent,
+};
+use crate::resources::{BoardOptions, GameRecord, GameStats, GameTimer};
+use crate::Board;
+use bevy::prelude::*;
+
+pub fn start_game_stats(mut stats: ResMut<GameStats>) {
+    stats.start_game();
+}
+
+pub fn count_clicks(
+    mut stats: ResMut<GameStats>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    for _ in tile_trigger_evr.iter() {
+        stats.add_click();
+    }
+    // A chord is a single click
+    for _ in tile_chord_evr.iter() {
+        stats.add_click();
+    }
+}
+
+pub fn record_game_end(
+    mut stats: ResMut<GameStats>,
+    mut timer: ResMut<GameTimer>,
+    board: Res<Board>,
+    board_options: Option<Res<BoardOptions>>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+    mut time_expired_evr: EventReader<TimeExpiredEvent>,
+) {
+    let exploded = bomb_exploded_evr.iter().count() > 0;
+    let expired = time_expired_evr.iter().count() > 0;
+    let completed = board_completed_evr.iter().last().copied();
+    if !exploded && !expired && completed.is_none() {
+        return;
+    }
+    let won = !exploded && !expired;
+    // A board only counts once, the timer being stopped on its first ending
+    if !timer.is_running() {
+        return;
+    }
+    timer.stop();
+    let (difficulty, daily) = match board_options {
+        None => (BoardOptions::default().difficulty(), None),
+        Some(o) => (o.difficulty(), o.daily),
+    };
+    let uncovered = board.uncovered_tiles() as u32;
+    let record = GameRecord {
+        difficulty,
+        won,
+        time: timer.elapsed(),
+        // The exploded bomb is not a cleared tile
+        tiles_cleared: if exploded { uncovered.saturating_sub(1) } else { uncovered },
+        clicks: stats.current_clicks(),
+        bbbv: completed.map(|e| e.bbbv).unwrap_or_else(|| board.bbbv()),
+        daily,
+    };
+    info!("Game finished: {:?}", record);
+    stats.record(record);
+}
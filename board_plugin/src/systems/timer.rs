@@ -0,0 +1,75 @@
+use crate::events::{TileChordEvent, TileTriggerEvent, TimeExpiredEvent};
+use crate::resources::{BoardOptions, BoardState, Countdown, GameTimer, PowerUps};
+use crate::Board;
+use bevy::prelude::*;
+
+pub fn start_timer(mut timer: ResMut<GameTimer>) {
+    timer.start();
+}
+
+pub fn tick_timer(
+    time: Res<Time>,
+    board_state: Res<BoardState>,
+    power_ups: Option<Res<PowerUps>>,
+    mut timer: ResMut<GameTimer>,
+) {
+    if *board_state != BoardState::Playing || power_ups.map_or(false, |p| p.is_frozen()) {
+        return;
+    }
+    timer.tick(time.delta_seconds());
+}
+
+/// Adds the `BoardOptions::time_penalties` of wrong chords and flagged tile clicks, checked
+/// before they get ignored by the uncover handlers
+pub fn apply_time_penalties(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    mut timer: ResMut<GameTimer>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+) {
+    let penalties = board_options.and_then(|o| o.time_penalties);
+    let penalties = match penalties {
+        Some(p) if *board_state == BoardState::Playing => p,
+        _ => return,
+    };
+    for coords in tile_trigger_evr.iter().map(|e| e.0) {
+        if board.is_covered(&coords) && board.flags_at(&coords) > 0 {
+            debug!("Flagged tile {} clicked, {}s penalty", coords, penalties.flagged_click);
+            timer.add_penalty(penalties.flagged_click);
+        }
+    }
+    if !board.variant.allows_chording() {
+        return;
+    }
+    for coords in tile_chord_evr.iter().map(|e| e.0) {
+        let wrong = board
+            .uncovered_bomb_count(coords)
+            .map_or(false, |c| board.adjacent_marked_count(coords) as isize != c as isize);
+        if wrong {
+            debug!("Wrong chord on {}, {}s penalty", coords, penalties.wrong_chord);
+            timer.add_penalty(penalties.wrong_chord);
+        }
+    }
+}
+
+pub fn tick_countdown(
+    time: Res<Time>,
+    board_state: Res<BoardState>,
+    power_ups: Option<Res<PowerUps>>,
+    countdown: Option<ResMut<Countdown>>,
+    mut time_expired_ewr: EventWriter<TimeExpiredEvent>,
+) {
+    let mut countdown = match countdown {
+        None => return,
+        Some(c) => c,
+    };
+    if *board_state != BoardState::Playing || power_ups.map_or(false, |p| p.is_frozen()) {
+        return;
+    }
+    if countdown.tick(time.delta_seconds()) {
+        info!("Time expired");
+        time_expired_ewr.send(TimeExpiredEvent);
+    }
+}
@@ -0,0 +1,77 @@
+use crate::components::Tooltip;
+use crate::resources::{BoardAssets, BoardGeometry, BoardOptions, BoardWindow};
+use crate::systems::input::board_camera;
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+
+/// Delay in seconds before a tooltip shows over a hovered numbered tile
+const TOOLTIP_DELAY: f64 = 1.;
+
+/// Shows a tooltip with the adjacent mine and flag counts over the numbered tile hovered for
+/// `TOOLTIP_DELAY`, if enabled in `BoardOptions::tooltips`
+pub fn tile_tooltip(
+    mut commands: Commands,
+    windows: Res<Windows>,
+    board_window: Res<BoardWindow>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    time: Res<Time>,
+    mut hovered: Local<Option<(Coordinates, f64)>>,
+    tooltips: Query<(Entity, &Tooltip)>,
+) {
+    let enabled = board_options.map_or(false, |o| o.tooltips);
+    let target = windows.get(board_window.window).filter(|_| enabled).and_then(|window| {
+        let camera = board_camera(&cameras, &board_window);
+        window
+            .cursor_position()
+            .and_then(|pos| geometry.mouse_position(window, camera, pos))
+            .filter(|coords| board.uncovered_bomb_count(*coords).is_some())
+    });
+    let now = time.seconds_since_startup();
+    let since = match (*hovered, target) {
+        (Some((coords, since)), Some(target)) if coords == target => since,
+        _ => now,
+    };
+    *hovered = target.map(|coords| (coords, since));
+    // Tooltips are refreshed on every change of the hovered tile or of its flags
+    let shown = target.filter(|_| now - since >= TOOLTIP_DELAY);
+    for (entity, tooltip) in tooltips.iter() {
+        if Some(tooltip.0) != shown || board.is_changed() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+    let coords = match shown {
+        Some(c) if board.is_changed() || tooltips.iter().all(|(_, t)| t.0 != c) => c,
+        _ => return,
+    };
+    let count = board.uncovered_bomb_count(coords).unwrap_or_default() as isize;
+    let flags = board.adjacent_marked_count(coords) as isize;
+    let size = geometry.tile_size;
+    // Above the tile, in board space
+    let position = geometry.tile_center(coords) - geometry.bounds.position + Vec2::new(0., size);
+    let tooltip = commands
+        .spawn_bundle(Text2dBundle {
+            text: Text::with_section(
+                format!("Mines: {} Flags: {} Left: {}", count, flags, count - flags),
+                TextStyle {
+                    color: Color::WHITE,
+                    font: board_assets.bomb_counter_font.clone(),
+                    font_size: size / 3.,
+                },
+                TextAlignment {
+                    vertical: VerticalAlign::Bottom,
+                    horizontal: HorizontalAlign::Center,
+                },
+            ),
+            // Above the tiles and the cursor
+            transform: Transform::from_translation(position.extend(8.)),
+            ..Default::default()
+        })
+        .insert(Tooltip(coords))
+        .insert(Name::new("Tooltip"))
+        .id();
+    commands.entity(board.entity).add_child(tooltip);
+}
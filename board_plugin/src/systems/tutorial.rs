@@ -0,0 +1,80 @@
+use crate::components::{TutorialHighlight, TutorialMessage};
+use crate::events::{
+    TileChordEvent, TileMarkEvent, TileTriggerEvent, TutorialCompletedEvent,
+    TutorialStepCompletedEvent,
+};
+use crate::resources::{BoardGeometry, TutorialAction, TutorialScript};
+use bevy::prelude::*;
+
+/// Moves to the next tutorial step when the current one is performed. Runs after the trigger
+/// handlers, which only let the current step input through
+pub fn advance_tutorial(
+    script: Option<ResMut<TutorialScript>>,
+    mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut tile_mark_evr: EventReader<TileMarkEvent>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut step_completed_ewr: EventWriter<TutorialStepCompletedEvent>,
+    mut tutorial_completed_ewr: EventWriter<TutorialCompletedEvent>,
+) {
+    let actions: Vec<_> = tile_trigger_evr
+        .iter()
+        .map(|e| (TutorialAction::Uncover, e.0))
+        .chain(tile_mark_evr.iter().map(|e| (TutorialAction::Mark, e.0)))
+        .chain(tile_chord_evr.iter().map(|e| (TutorialAction::Chord, e.0)))
+        .collect();
+    let mut script = match script {
+        None => return,
+        Some(s) => s,
+    };
+    for (action, coords) in actions {
+        if script.is_finished() || !script.allows(action, coords) {
+            continue;
+        }
+        let index = script.advance();
+        info!("Tutorial step {} completed", index);
+        step_completed_ewr.send(TutorialStepCompletedEvent { index });
+        if script.is_finished() {
+            info!("Tutorial completed");
+            tutorial_completed_ewr.send(TutorialCompletedEvent);
+        }
+    }
+}
+
+/// Highlights the tile of the current tutorial step, with its instruction above
+pub fn display_tutorial(
+    geometry: Res<BoardGeometry>,
+    script: Option<Res<TutorialScript>>,
+    mut highlights: Query<
+        (&mut Transform, &mut Sprite, &mut Visibility),
+        (With<TutorialHighlight>, Without<TutorialMessage>),
+    >,
+    mut messages: Query<(&mut Transform, &mut Text, &mut Visibility), With<TutorialMessage>>,
+) {
+    let step = script.as_ref().and_then(|s| s.current_step());
+    let size = geometry.tile_size;
+    for (mut transform, mut sprite, mut visibility) in highlights.iter_mut() {
+        visibility.is_visible = step.is_some();
+        if let Some(step) = step {
+            transform.translation.x = step.coords.x as f32 * size + size / 2.;
+            transform.translation.y = step.coords.y as f32 * size + size / 2.;
+            sprite.custom_size = Some(Vec2::splat(size));
+        }
+    }
+    // The message is a child of the highlight
+    for (mut transform, mut text, mut visibility) in messages.iter_mut() {
+        visibility.is_visible = step.is_some();
+        if let Some(step) = step {
+            transform.translation.y = size;
+            // Texts are laid out again on every change
+            if text.sections.first().map(|s| (&s.value, s.style.font_size))
+                == Some((&step.message, size / 2.))
+            {
+                continue;
+            }
+            for section in text.sections.iter_mut() {
+                section.value = step.message.clone();
+                section.style.font_size = size / 2.;
+            }
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use crate::components::{BombNeighbor, Coordinates, Easing, NumberBadge, Tween, TweenTarget};
+use crate::events::TileUncoveredEvent;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+
+/// Duration in seconds of the number pop-in
+const NUMBER_POP_DURATION: f32 = 0.25;
+
+/// Plays the tweens, removing them once finished
+pub fn animate_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut tweens: Query<(Entity, &mut Tween, &mut Transform)>,
+) {
+    for (entity, mut tween, mut transform) in tweens.iter_mut() {
+        tween.timer.tick(time.delta());
+        tween.apply(&mut transform);
+        if tween.timer.finished() {
+            commands.entity(entity).remove::<Tween>();
+        }
+    }
+}
+
+/// Scales the numbers of the uncovered tiles up from nothing
+pub fn pop_in_numbers(
+    mut commands: Commands,
+    mut tile_uncovered_evr: EventReader<TileUncoveredEvent>,
+    tiles: Query<(&Coordinates, &Children), With<BombNeighbor>>,
+    mut numbers: Query<
+        &mut Transform,
+        (Or<(With<Text>, With<TextureAtlasSprite>)>, Without<NumberBadge>),
+    >,
+) {
+    let uncovered: HashSet<_> = tile_uncovered_evr.iter().map(|e| e.coords).collect();
+    if uncovered.is_empty() {
+        return;
+    }
+    for (coords, children) in tiles.iter() {
+        if !uncovered.contains(coords) {
+            continue;
+        }
+        for child in children.iter() {
+            if let Ok(mut transform) = numbers.get_mut(*child) {
+                transform.scale = Vec3::ZERO;
+                let target = TweenTarget::Scale { from: Vec3::ZERO, to: Vec3::ONE };
+                commands.entity(*child).insert(Tween::new(
+                    NUMBER_POP_DURATION,
+                    target,
+                    Easing::BackOut,
+                ));
+            }
+        }
+    }
+}
@@ -1,51 +1,260 @@
-use crate::{events::TileTriggerEvent, Board, Bomb, BombNeighbor, Coordinates, Uncover};
+use crate::{
+    events::{
+        BoardCompletedEvent, BombExplodedEvent, LifeLostEvent, TileChordEvent, TileTriggerEvent,
+        TileUncoveredEvent, UncoverEvent, UncoverTileEvent,
+    },
+    resources::{
+        BoardOptions, BoardState, GameStats, GameTimer, Lives, MineKind, Tile, TutorialAction,
+        TutorialScript,
+    },
+    Board, CascadeInProgress, Coordinates, Detonated, Uncover, WinFlag,
+};
 use bevy::prelude::*;
 
+/// Delay in seconds between two covers flagged on win
+const WIN_FLAG_DELAY: f32 = 0.05;
+
 pub fn trigger_event_handler(
-    mut commands: Commands,
     board: Res<Board>,
+    board_state: Res<BoardState>,
+    tutorial: Option<Res<TutorialScript>>,
     mut tile_trigger_evr: EventReader<TileTriggerEvent>,
+    mut uncover_tile_evr: EventReader<UncoverTileEvent>,
+    mut uncover_ewr: EventWriter<UncoverEvent>,
+    cascades: Query<(), With<CascadeInProgress>>,
+    mut deferred: Local<Vec<Coordinates>>,
+) {
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    let _span = info_span!("trigger_event_handler").entered();
+    // Input and programmatic triggers are handled the same, except for the tutorial blocking
+    // input
+    let triggers = tile_trigger_evr
+        .iter()
+        .map(|e| e.0)
+        .filter(|c| tutorial.as_ref().map_or(true, |t| t.allows(TutorialAction::Uncover, *c)))
+        .chain(uncover_tile_evr.iter().map(|e| e.0));
+    // Triggers wait for the openings being computed, as they may uncover their tile
+    if board.is_added() {
+        deferred.clear();
+    }
+    deferred.extend(triggers);
+    if !cascades.is_empty() {
+        return;
+    }
+    for coords in deferred.drain(..) {
+        if board.tile_to_uncover(&coords).is_some() {
+            uncover_ewr.send(UncoverEvent(coords));
+        }
+    }
+}
+
+pub fn chord_event_handler(
+    board: Res<Board>,
+    board_state: Res<BoardState>,
+    tutorial: Option<Res<TutorialScript>>,
+    mut tile_chord_evr: EventReader<TileChordEvent>,
+    mut uncover_ewr: EventWriter<UncoverEvent>,
+    cascades: Query<(), With<CascadeInProgress>>,
+    mut deferred: Local<Vec<Coordinates>>,
 ) {
-    for trigger_event in tile_trigger_evr.iter() {
-        if let Some(entity) = board.tile_to_uncover(&trigger_event.0) {
-            commands.entity(*entity).insert(Uncover);
+    if *board_state != BoardState::Playing {
+        return;
+    }
+    if !board.variant.allows_chording() {
+        return;
+    }
+    let _span = info_span!("chord_event_handler").entered();
+    // Chords wait for the openings being computed, their flag count being checked afterwards
+    if board.is_added() {
+        deferred.clear();
+    }
+    deferred.extend(tile_chord_evr.iter().map(|e| e.0));
+    if !cascades.is_empty() {
+        return;
+    }
+    for coords in deferred.drain(..) {
+        if !tutorial.as_ref().map_or(true, |t| t.allows(TutorialAction::Chord, coords)) {
+            continue;
+        }
+        for neighbor in board.chord_targets(coords) {
+            uncover_ewr.send(UncoverEvent(neighbor));
         }
     }
 }
 
+/// Uncovers the tiles requested this frame by the trigger handlers, and the covers with the
+/// `Uncover` component
 pub fn uncover_tiles(
     mut commands: Commands,
     mut board: ResMut<Board>,
-    children: Query<(Entity, &Parent), With<Uncover>>,
-    parents: Query<(&Coordinates, Option<&Bomb>, Option<&BombNeighbor>)>,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    stats: Res<GameStats>,
+    timer: Res<GameTimer>,
+    mut lives: ResMut<Lives>,
+    children: Query<&Parent, With<Uncover>>,
+    parents: Query<&Parent>,
+    tiles: Query<&Coordinates>,
+    mut uncover_evr: EventReader<UncoverEvent>,
+    mut board_completed_ewr: EventWriter<BoardCompletedEvent>,
+    mut bomb_exploded_ewr: EventWriter<BombExplodedEvent>,
+    mut tile_uncovered_ewr: EventWriter<TileUncoveredEvent>,
+    mut life_lost_ewr: EventWriter<LifeLostEvent>,
+    mut cascade_frames: Local<u32>,
 ) {
-    // We iterate through tile covers to uncover
-    for (entity, parent) in children.iter() {
-        // we destroy the tile cover entity
-        commands.entity(entity).despawn_recursive();
-        let (coords, bomb, bomb_counter) = match parents.get(parent.0) {
-            Ok(v) => v,
-            Err(e) => {
-                error!("{:?}", e);
-                continue;
+    // The cascade resumes with the game
+    if *board_state == BoardState::Paused {
+        return;
+    }
+    // Openings computed on the async compute pool are uncovered with their origin. The board is
+    // only borrowed mutably when there are some, not to flag it as changed every frame
+    let openings =
+        if board.openings.is_empty() { Vec::new() } else { std::mem::take(&mut board.openings) };
+    let targets: Vec<(Coordinates, Option<Vec<Coordinates>>)> = uncover_evr
+        .iter()
+        .map(|e| e.0)
+        .chain(children.iter().filter_map(|parent| tiles.get(parent.0).ok().copied()))
+        .map(|c| (c, None))
+        .chain(openings.into_iter().map(|(c, o)| (c, Some(o))))
+        .collect();
+    if targets.is_empty() {
+        *cascade_frames = 0;
+        return;
+    }
+    // Animated cascades spread over frames, their depth is the number of consecutive frames
+    *cascade_frames += 1;
+    let _span = info_span!("uncover_tiles", targets = targets.len()).entered();
+    let covered = board.covered_count();
+    let (animated, flag_on_win, decoy_bonus) = board_options.map_or((false, true, 0), |o| {
+        (o.animated_cascade, o.flag_on_win, o.mine_kinds.decoy_bonus)
+    });
+    // We iterate through tiles to uncover
+    for (coords, opening) in targets.iter() {
+        // The tile may have been uncovered by another cascade this frame
+        if !board.is_covered(coords) {
+            debug!("Tried to uncover an already uncovered tile");
+            continue;
+        }
+        // Ice cracks on the first attempt, and locked tiles wait for their keys
+        if board.modifiers.blocks(coords) {
+            debug!("Tile {} resisted uncovering", coords);
+            if let Some(cover) = board.covered_tiles.get(coords) {
+                commands.entity(*cover).remove::<Uncover>();
+            }
+            continue;
+        }
+        let bomb = board.tile_map.is_bomb_at(*coords);
+        let kind = board.mine_kind(coords);
+        // A bomb costs a life, the game only ending with the last one
+        let detonated = bomb && lives.remaining() > 1;
+        if detonated {
+            board.detonated_tiles.push(*coords);
+            let remaining = lives.lose();
+            info!("Life lost, {} remaining", remaining);
+            // The tile entity is the parent of its cover, unspawned tiles get crossed out when
+            // their chunk spawns
+            let tile = board.covered_tiles.get(coords).and_then(|c| parents.get(*c).ok());
+            if let Some(parent) = tile {
+                commands.entity(parent.0).insert(Detonated);
             }
+            life_lost_ewr.send(LifeLostEvent { coords: *coords, remaining, kind });
+            // Mega mines blast their safe neighbors open
+            if kind == MineKind::Mega {
+                let safe = board.tile_map.neighbors(*coords).filter(|c| {
+                    !board.tile_map.is_bomb_at(*c) && board.tile_to_uncover(c).is_some()
+                });
+                for c in safe.collect::<Vec<_>>() {
+                    if let Some(cover) = board.covered_tiles.get(&c) {
+                        commands.entity(*cover).insert(Uncover);
+                    }
+                }
+            }
+        }
+        let empty = board.tile_map[coords.y as usize][coords.x as usize] == Tile::Empty;
+        let uncovered = if let Some(opening) = opening {
+            board.uncover_opening(opening)
+        } else if board.async_cascade && empty && !animated {
+            // The opening is uncovered as a whole once computed, see `compute_cascades`
+            commands.entity(board.entity).with_children(|parent| {
+                parent.spawn().insert(CascadeInProgress::new(*coords));
+            });
+            continue;
+        } else if animated {
+            // The cascade spreads by adding the `Uncover` component to adjacent tiles, which
+            // will then be removed next frame
+            let uncovered: Vec<_> =
+                board.try_uncover_tile(coords).map(|e| (*coords, Some(e))).into_iter().collect();
+            if empty {
+                let neighbors = board.tile_map.neighbors(*coords).filter(|c| board.cascades_to(c));
+                for c in neighbors.collect::<Vec<_>>() {
+                    match board.covered_tiles.get(&c) {
+                        Some(entity) => {
+                            commands.entity(*entity).insert(Uncover);
+                        }
+                        // Tiles of unspawned chunks have no cover to animate
+                        None => {
+                            for (c, _) in board.uncover_from(c) {
+                                let tile = board.tile_map[c.y as usize][c.x as usize];
+                                tile_uncovered_ewr.send(TileUncoveredEvent { coords: c, tile });
+                            }
+                        }
+                    }
+                }
+            }
+            uncovered
+        } else {
+            // The whole opening is uncovered at once
+            board.uncover_from(*coords)
         };
-        // We remove the entity from the board covered tile map
-        match board.try_uncover_tile(coords) {
-            None => debug!("Tried to uncover an already uncovered tile"),
-            Some(e) => debug!("Uncovered tile {} (entity: {:?})", coords, e),
+        for (c, cover) in uncovered {
+            debug!("Uncovered tile {} (entity: {:?})", c, cover);
+            if let Some(cover) = cover {
+                commands.entity(cover).despawn_recursive();
+            }
+            let tile = board.tile_map[c.y as usize][c.x as usize];
+            tile_uncovered_ewr.send(TileUncoveredEvent { coords: c, tile });
+            // Keys open the locks once all found, teleports uncover their pair next frame
+            if let Some(pair) = board.modifiers.uncovered(&c) {
+                if let Some(cover) = board.covered_tiles.get(&pair) {
+                    commands.entity(*cover).insert(Uncover);
+                }
+            }
+        }
+        if detonated {
+            continue;
         }
-        if bomb.is_some() {
+        if bomb {
+            lives.lose();
             info!("Boom !");
-            // TODO: Add explosion event
-        }
-        // If the tile is empty..
-        else if bomb_counter.is_none() {
-            // .. We propagate the uncovering by adding the `Uncover` component to adjacent tiles
-            // which will then be removed next frame
-            for entity in board.adjacent_covered_tiles(*coords) {
-                commands.entity(entity).insert(Uncover);
+            bomb_exploded_ewr.send(BombExplodedEvent { coords: *coords, kind });
+            // The game is over, the other targets of this frame stay covered
+            break;
+        }
+        if board.is_completed() {
+            info!("Board completed");
+            // Decoys are counted before the remaining covers get flagged
+            let bonus = board.flagged_decoys() * decoy_bonus;
+            if flag_on_win {
+                for (i, (_, cover, flags)) in board.flag_remaining().into_iter().enumerate() {
+                    let delay = Timer::from_seconds(i as f32 * WIN_FLAG_DELAY, false);
+                    commands.entity(cover).insert(WinFlag { delay, flags });
+                }
             }
+            board_completed_ewr.send(BoardCompletedEvent {
+                time: timer.elapsed(),
+                penalty: timer.penalty(),
+                bbbv: board.bbbv(),
+                difficulty_score: board.difficulty_score(),
+                clicks: stats.current_clicks(),
+                bonus,
+            });
         }
     }
+    trace!(
+        tiles_uncovered = covered.saturating_sub(board.covered_count()),
+        cascade_frames = *cascade_frames,
+        "Uncovered tiles this frame"
+    );
 }
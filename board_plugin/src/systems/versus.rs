@@ -0,0 +1,134 @@
+use crate::components::Flag;
+use crate::events::{BombExplodedEvent, RaceWonEvent};
+use crate::resources::{BoardAssets, BoardOptions, BoardState, InputBindings, Player, VersusRace};
+use crate::systems::mark::flag_bundle;
+use crate::{Board, Coordinates};
+use bevy::prelude::*;
+
+/// Gamepad buttons uncovering, flagging and chording the tile under the rival cursor
+const GAMEPAD_UNCOVER: GamepadButtonType = GamepadButtonType::South;
+const GAMEPAD_FLAG: GamepadButtonType = GamepadButtonType::West;
+const GAMEPAD_CHORD: GamepadButtonType = GamepadButtonType::East;
+
+/// Player two input of a versus race: the keyboard cursor keys or the buttons of any gamepad
+/// play the rival board, the main board being left to the mouse. Input waits for the main board
+/// to be playing, and stops once the race is won
+pub fn rival_input_handling(
+    mut commands: Commands,
+    board_state: Res<BoardState>,
+    board_options: Option<Res<BoardOptions>>,
+    board_assets: Res<BoardAssets>,
+    bindings: Res<InputBindings>,
+    keys: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    buttons: Res<Input<GamepadButton>>,
+    race: Option<ResMut<VersusRace>>,
+    mut transforms: Query<&mut Transform>,
+    children: Query<&Children>,
+    flags: Query<(), With<Flag>>,
+) {
+    let mut race = match race {
+        None => return,
+        Some(r) => r,
+    };
+    if *board_state != BoardState::Playing || race.winner().is_some() {
+        return;
+    }
+    let pressed = |key: KeyCode, button: GamepadButtonType| {
+        keys.just_pressed(key)
+            || gamepads.iter().any(|gamepad| buttons.just_pressed(GamepadButton(*gamepad, button)))
+    };
+    let rival = &mut race.rival;
+    let (width, height) = (rival.board.tile_map.width(), rival.board.tile_map.height());
+    let delta: (i8, i8) = [
+        (bindings.cursor_up, GamepadButtonType::DPadUp, (0, 1)),
+        (bindings.cursor_down, GamepadButtonType::DPadDown, (0, -1)),
+        (bindings.cursor_left, GamepadButtonType::DPadLeft, (-1, 0)),
+        (bindings.cursor_right, GamepadButtonType::DPadRight, (1, 0)),
+    ]
+    .iter()
+    .filter(|(key, button, _)| pressed(*key, *button))
+    .fold((0, 0), |(x, y), (_, _, (dx, dy))| (x + dx, y + dy));
+    if delta != (0, 0) {
+        // The cursor stays in bounds
+        let Coordinates { x, y } = rival.cursor;
+        rival.cursor = Coordinates {
+            x: (x as i32 + delta.0 as i32).clamp(0, width as i32 - 1) as u16,
+            y: (y as i32 + delta.1 as i32).clamp(0, height as i32 - 1) as u16,
+        };
+        if let Ok(mut transform) = transforms.get_mut(rival.cursor_entity) {
+            let size = rival.tile_size;
+            transform.translation.x = rival.cursor.x as f32 * size + size / 2.;
+            transform.translation.y = rival.cursor.y as f32 * size + size / 2.;
+        }
+    }
+    let coords = rival.cursor;
+    if pressed(bindings.cursor_flag, GAMEPAD_FLAG) {
+        if let Some((cover, mark)) = rival.board.try_toggle_mark(&coords) {
+            info!("Player two marks tile on {}", coords);
+            if mark {
+                let padding = board_options.map_or(0., |o| o.tile_padding);
+                let bundle = flag_bundle(&board_assets, rival.tile_size, padding, 0);
+                commands.entity(cover).with_children(|parent| {
+                    parent.spawn_bundle(bundle).insert(Flag).insert(Name::new("Flag"));
+                });
+            } else if let Ok(cover_children) = children.get(cover) {
+                for flag in cover_children.iter().filter(|c| flags.get(**c).is_ok()) {
+                    commands.entity(*flag).despawn_recursive();
+                }
+            }
+        }
+    }
+    let (covers, exploded) = if pressed(bindings.cursor_uncover, GAMEPAD_UNCOVER) {
+        info!("Player two uncovers tile on {}", coords);
+        rival.uncover(coords)
+    } else if pressed(bindings.cursor_chord, GAMEPAD_CHORD) {
+        info!("Player two chords tile on {}", coords);
+        rival.chord(coords)
+    } else {
+        return;
+    };
+    for cover in covers {
+        commands.entity(cover).despawn_recursive();
+    }
+    let uncovered = rival.uncovered_tiles();
+    let mut progress = race.progress(Player::Two);
+    progress.exploded |= exploded;
+    // The exploded bomb is not a cleared tile
+    progress.uncovered = uncovered.saturating_sub(progress.exploded as usize);
+    race.set_progress(Player::Two, progress);
+}
+
+/// Tracks the main board progress of a versus race and sends `RaceWonEvent` once a player
+/// completed their board or exploded. A race decided on the rival board ends the main board,
+/// won or lost by player one
+pub fn update_race(
+    board: Res<Board>,
+    mut board_state: ResMut<BoardState>,
+    race: Option<ResMut<VersusRace>>,
+    mut bomb_exploded_evr: EventReader<BombExplodedEvent>,
+    mut race_won_ewr: EventWriter<RaceWonEvent>,
+) {
+    let mut race = match race {
+        None => return,
+        Some(r) => r,
+    };
+    let mut progress = race.progress(Player::One);
+    progress.exploded |= bomb_exploded_evr.iter().count() > 0;
+    progress.uncovered = board.uncovered_tiles().saturating_sub(progress.exploded as usize);
+    race.set_progress(Player::One, progress);
+    let winner = match race.decide() {
+        None => return,
+        Some(w) => w,
+    };
+    info!("Player {:?} won the race", winner);
+    race_won_ewr.send(RaceWonEvent { player: winner });
+    // Main board endings are handled by `end_game`
+    let decided_by_rival = !progress.exploded && !progress.is_completed();
+    if decided_by_rival && !board_state.is_over() {
+        *board_state = match winner {
+            Player::One => BoardState::Won,
+            Player::Two => BoardState::Lost,
+        };
+    }
+}
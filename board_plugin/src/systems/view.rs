@@ -0,0 +1,22 @@
+use crate::resources::BoardGeometry;
+use crate::Board;
+use bevy::prelude::*;
+
+/// Rotates and mirrors the board entity as set in `BoardGeometry::view`, whenever the geometry
+/// changes
+pub fn apply_board_view(
+    board: Res<Board>,
+    geometry: Res<BoardGeometry>,
+    mut transforms: Query<&mut Transform>,
+) {
+    if !geometry.is_changed() {
+        return;
+    }
+    if let Ok(mut transform) = transforms.get_mut(board.entity) {
+        let view = geometry.view_transform(transform.translation.z);
+        // Unrotated boards are left untouched so they aren't propagated again
+        if *transform != view {
+            *transform = view;
+        }
+    }
+}
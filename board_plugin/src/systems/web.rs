@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// Prevents the browser context menu from opening on right click, which is used to mark tiles
+pub fn disable_context_menu() {
+    let document = match web_sys::window().and_then(|w| w.document()) {
+        None => {
+            warn!("No document found, the context menu can't be disabled");
+            return;
+        }
+        Some(d) => d,
+    };
+    let prevent_default = Closure::wrap(
+        Box::new(|event: web_sys::Event| event.prevent_default()) as Box<dyn FnMut(_)>
+    );
+    document.set_oncontextmenu(Some(prevent_default.as_ref().unchecked_ref()));
+    // The listener must live as long as the page
+    prevent_default.forget();
+}
@@ -0,0 +1,26 @@
+use crate::resources::{BoardWindow, InputBindings};
+use bevy::prelude::*;
+use bevy::window::WindowMode;
+
+/// Toggles the board window between windowed and borderless fullscreen. The board is refitted
+/// by the window resize handling
+pub fn toggle_fullscreen(
+    keys: Res<Input<KeyCode>>,
+    bindings: Res<InputBindings>,
+    board_window: Res<BoardWindow>,
+    mut windows: ResMut<Windows>,
+) {
+    if !keys.just_pressed(bindings.fullscreen) {
+        return;
+    }
+    let window = match windows.get_mut(board_window.window) {
+        None => return,
+        Some(w) => w,
+    };
+    let mode = match window.mode() {
+        WindowMode::Windowed => WindowMode::BorderlessFullscreen,
+        _ => WindowMode::Windowed,
+    };
+    info!("Switching window to {:?}", mode);
+    window.set_mode(mode);
+}
@@ -0,0 +1,180 @@
+//! Headless harness running the board plugin without windowing nor rendering, so board logic
+//! can be covered by integration tests:
+//!
+//! ```ignore
+//! let mut board = HeadlessBoard::new(options);
+//! board.trigger(Coordinates { x: 4, y: 4 });
+//! assert_eq!(board.state(), BoardState::Won);
+//! ```
+use crate::components::{CascadeInProgress, Uncover};
+use crate::events::{InputSource, TileChordEvent, TileMarkEvent, TileTriggerEvent};
+use crate::resources::{
+    Board, BoardAssets, BoardLoading, BoardOptions, BoardState, Leaderboards, PendingBoard,
+    Settings, SpriteMaterial,
+};
+use crate::systems::export::ExportTasks;
+use crate::{BoardPlugin, Coordinates};
+use bevy::asset::AssetPlugin;
+use bevy::ecs::event::Events;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::window::WindowPlugin;
+
+/// Maximum number of frames waited for an uncover cascade to settle
+const MAX_CASCADE_FRAMES: usize = 10_000;
+
+/// Application state of the headless harness
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+pub enum HeadlessState {
+    Playing,
+}
+
+/// Board plugin running in a minimal headless `App`
+pub struct HeadlessBoard {
+    pub app: App,
+}
+
+impl HeadlessBoard {
+    /// Builds the app and generates a board with `options`
+    pub fn new(options: BoardOptions) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_plugin(WindowPlugin::default())
+            .add_plugin(InputPlugin)
+            .insert_resource(WindowDescriptor::default())
+            .insert_resource(options)
+            // Nothing is loaded from or saved to the disk
+            .insert_resource(Leaderboards::default())
+            .insert_resource(Settings::default())
+            .insert_resource(Self::assets())
+            .add_state(HeadlessState::Playing)
+            .add_plugin(BoardPlugin { running_state: HeadlessState::Playing });
+        let mut harness = Self { app };
+        harness.settle();
+        harness
+    }
+
+    /// Untextured assets, nothing gets rendered anyway
+    fn assets() -> BoardAssets {
+        BoardAssets {
+            label: "Headless".to_string(),
+            board_material: Default::default(),
+            tile_material: Default::default(),
+            covered_tile_material: Default::default(),
+            hovered_tile_material: Default::default(),
+            pressed_tile_material: Default::default(),
+            cover_style: Default::default(),
+            bomb_counter_font: Default::default(),
+            bomb_counter_colors: BoardAssets::default_colors(),
+            negative_bomb_counter_colors: BoardAssets::default_negative_colors(),
+            number_badges: false,
+            flag_material: Default::default(),
+            auto_flag_material: SpriteMaterial::default(),
+            bomb_material: Default::default(),
+            armored_bomb_material: SpriteMaterial::default(),
+            anti_mine_material: SpriteMaterial::default(),
+            mega_mine_material: SpriteMaterial::default(),
+            decoy_mine_material: SpriteMaterial::default(),
+            region_colors: BoardAssets::default_region_colors(),
+            cursor_material: SpriteMaterial::default(),
+            tutorial_material: SpriteMaterial::default(),
+            night_mode: None,
+            atlas: None,
+        }
+    }
+
+    /// Runs a single frame
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Runs frames until no uncover cascade is pending, animated or computed on the async compute
+    /// pool, nor boards left to generate, tiles to spawn or board images to save
+    pub fn settle(&mut self) {
+        for _ in 0..MAX_CASCADE_FRAMES {
+            self.app.update();
+            if self.app.world.contains_resource::<BoardLoading>()
+                || self.app.world.contains_resource::<PendingBoard>()
+                || !self.app.world.resource::<ExportTasks>().0.is_empty()
+            {
+                continue;
+            }
+            let mut pending =
+                self.app.world.query_filtered::<(), Or<(With<Uncover>, With<CascadeInProgress>)>>();
+            if pending.iter(&self.app.world).next().is_none() {
+                return;
+            }
+        }
+        panic!("Uncover cascade did not settle in {} frames", MAX_CASCADE_FRAMES);
+    }
+
+    /// Inserts or replaces a resource, like a `TutorialScript`
+    pub fn insert_resource<R: Send + Sync + 'static>(&mut self, resource: R) {
+        self.app.world.insert_resource(resource);
+    }
+
+    /// Sends an event, as an input system would
+    pub fn send<E: Send + Sync + 'static>(&mut self, event: E) {
+        self.app.world.resource_mut::<Events<E>>().send(event);
+    }
+
+    /// Uncovers a tile and waits for the cascade
+    pub fn trigger(&mut self, coords: Coordinates) {
+        self.send(TileTriggerEvent(coords, InputSource::Ai));
+        self.settle();
+    }
+
+    /// Marks or unmarks a tile
+    pub fn mark(&mut self, coords: Coordinates) {
+        self.send(TileMarkEvent(coords, InputSource::Ai));
+        self.settle();
+    }
+
+    /// Chords a numbered tile and waits for the cascade
+    pub fn chord(&mut self, coords: Coordinates) {
+        self.send(TileChordEvent(coords, InputSource::Ai));
+        self.settle();
+    }
+
+    /// Removes the board resource, leaving the plugin without board as before the first one is
+    /// spawned
+    pub fn remove_board(&mut self) {
+        self.app.world.remove_resource::<Board>();
+    }
+
+    /// Current board
+    pub fn board(&self) -> &Board {
+        self.app.world.resource::<Board>()
+    }
+
+    /// Retrieves a resource, like `PowerUps`
+    pub fn resource<R: Send + Sync + 'static>(&self) -> &R {
+        self.app.world.resource::<R>()
+    }
+
+    /// Current board state
+    pub fn state(&self) -> BoardState {
+        *self.app.world.resource::<BoardState>()
+    }
+
+    /// Is the tile still covered
+    pub fn is_covered(&self, coords: Coordinates) -> bool {
+        self.board().is_covered(&coords)
+    }
+
+    /// Number of flags on the tile
+    pub fn flags_at(&self, coords: Coordinates) -> u8 {
+        self.board().flags_at(&coords)
+    }
+
+    /// Number of covered tiles
+    pub fn covered_count(&self) -> usize {
+        self.board().covered_count()
+    }
+
+    /// The board as seen by the player, see `Board::render_ascii`
+    pub fn render_ascii(&self) -> String {
+        self.board().render_ascii()
+    }
+}
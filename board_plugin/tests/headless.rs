@@ -0,0 +1,969 @@
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::ElementState;
+use bevy::prelude::{
+    Children, Entity, KeyCode, Name, Parent, Sprite, Text, TextureAtlasSprite, Transform, Vec3,
+    With,
+};
+use board_plugin::events::{
+    ExportBoardImageEvent, FlagTileEvent, InputSource, NightModeEvent, RestartBoardEvent,
+    RetryBoardEvent, TileTriggerEvent, UncoverTileEvent, UsePowerUpEvent,
+};
+use board_plugin::resources::{
+    Accessibility, AccessibilityBackend, Announcement, BoardGeometry, BoardLayout, BoardLoading,
+    BoardOptions, BoardOptionsBuilder, BoardOptionsError, BoardState, BoardVariant, Cascade,
+    Celebration, GameTimer, MineKind, MineKinds, MovingMines, NightMode, Player, PowerUp,
+    PowerUpOptions, PowerUps, ProgressBar, RevealSequence, Tile, TileMap, TileMapTextError,
+    TileModifiers, TileSize, TimePenalties, TutorialAction, TutorialScript, TutorialStep,
+    VersusRace, VisibleTile,
+};
+use board_plugin::testing::HeadlessBoard;
+use board_plugin::{
+    Adjacency, BoardRoot, BombSprite, Coordinates, CoverSprite, Easing, NumberText, TileRoot,
+    Tween, TweenTarget,
+};
+use std::sync::{Arc, Mutex};
+
+/// Seeded board options with bombs at the given positions
+fn board_options(map_size: (u16, u16), bombs: &[(u16, u16)]) -> BoardOptions {
+    BoardOptions {
+        map_size,
+        layout: BoardLayout::Explicit(
+            bombs.iter().map(|(x, y)| Coordinates { x: *x, y: *y }).collect(),
+        ),
+        seed: Some(0),
+        ..Default::default()
+    }
+}
+
+/// Seeded board with bombs at the given positions
+fn board(map_size: (u16, u16), bombs: &[(u16, u16)]) -> HeadlessBoard {
+    HeadlessBoard::new(board_options(map_size, bombs))
+}
+
+#[test]
+fn board_starts_covered() {
+    let board = board((5, 5), &[(0, 0)]);
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(board.covered_count(), 25);
+}
+
+#[test]
+fn empty_tile_cascades_to_completion() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.covered_count(), 1, "\n{}", board.render_ascii());
+    assert!(board.is_covered(Coordinates { x: 0, y: 0 }));
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn animated_cascade_reaches_completion() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        map_size: (5, 5),
+        layout: BoardLayout::Explicit(vec![Coordinates { x: 0, y: 0 }]),
+        seed: Some(0),
+        animated_cascade: true,
+        ..Default::default()
+    });
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.covered_count(), 1, "\n{}", board.render_ascii());
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn numbered_tile_does_not_cascade() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    board.trigger(Coordinates { x: 1, y: 1 });
+    assert_eq!(board.covered_count(), 24);
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn bomb_loses_the_game() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    board.trigger(Coordinates { x: 0, y: 0 });
+    assert_eq!(board.state(), BoardState::Lost);
+}
+
+#[test]
+fn marked_tile_is_not_uncovered() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    let bomb = Coordinates { x: 0, y: 0 };
+    board.mark(bomb);
+    assert_eq!(board.flags_at(bomb), 1);
+    board.trigger(bomb);
+    assert!(board.is_covered(bomb));
+    assert_eq!(board.state(), BoardState::Playing);
+    board.mark(bomb);
+    assert_eq!(board.flags_at(bomb), 0);
+}
+
+#[test]
+fn chord_uncovers_unmarked_neighbors() {
+    let mut board = board((3, 3), &[(0, 0)]);
+    let center = Coordinates { x: 1, y: 1 };
+    board.trigger(center);
+    assert_eq!(board.covered_count(), 8);
+    // Chording without the matching flags does nothing
+    board.chord(center);
+    assert_eq!(board.covered_count(), 8);
+    board.mark(Coordinates { x: 0, y: 0 });
+    board.chord(center);
+    assert_eq!(board.covered_count(), 1);
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn flag_event_never_unflags() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    let bomb = Coordinates { x: 0, y: 0 };
+    board.send(FlagTileEvent(bomb));
+    board.settle();
+    board.send(FlagTileEvent(bomb));
+    board.settle();
+    assert_eq!(board.flags_at(bomb), 1);
+    // Flagged tiles are left covered
+    board.send(UncoverTileEvent(bomb));
+    board.settle();
+    assert!(board.is_covered(bomb));
+    board.send(UncoverTileEvent(Coordinates { x: 4, y: 4 }));
+    board.settle();
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn tutorial_blocks_other_input() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    let bomb = Coordinates { x: 0, y: 0 };
+    board.insert_resource(TutorialScript::new(vec![TutorialStep {
+        coords: bomb,
+        action: TutorialAction::Mark,
+        message: "Flag this".to_string(),
+    }]));
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.covered_count(), 25);
+    board.mark(bomb);
+    assert_eq!(board.flags_at(bomb), 1);
+    // The tutorial is over
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+/// Accessibility backend keeping the announcements
+#[derive(Default, Clone)]
+struct RecordingBackend(Arc<Mutex<Vec<Announcement>>>);
+
+impl AccessibilityBackend for RecordingBackend {
+    fn announce(&mut self, announcement: &Announcement) {
+        self.0.lock().unwrap().push(announcement.clone());
+    }
+}
+
+#[test]
+fn accessibility_announces_flags_and_openings() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    let backend = RecordingBackend::default();
+    board.insert_resource(Accessibility::new(backend.clone()));
+    let bomb = Coordinates { x: 0, y: 0 };
+    board.mark(bomb);
+    board.trigger(Coordinates { x: 4, y: 4 });
+    let announcements = backend.0.lock().unwrap();
+    assert_eq!(
+        announcements[0],
+        Announcement::FlagChanged { coords: bomb, flagged: true, remaining_mines: 0 }
+    );
+    assert_eq!(announcements[1], Announcement::TilesUncovered { count: 24 });
+    assert!(matches!(announcements[2], Announcement::BoardCompleted { .. }));
+}
+
+#[test]
+fn power_ups_target_covered_tiles() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        map_size: (5, 5),
+        layout: BoardLayout::Explicit(vec![Coordinates { x: 0, y: 0 }]),
+        seed: Some(0),
+        power_ups: Some(PowerUpOptions {
+            starting: vec![PowerUp::DefuseMine, PowerUp::RevealSafeTile],
+            grant_interval: None,
+            ..Default::default()
+        }),
+        ..Default::default()
+    });
+    board.send(UsePowerUpEvent(PowerUp::DefuseMine));
+    board.settle();
+    assert_eq!(board.flags_at(Coordinates { x: 0, y: 0 }), 1);
+    board.send(UsePowerUpEvent(PowerUp::RevealSafeTile));
+    board.settle();
+    assert!(board.covered_count() < 25);
+    // Used charges are gone
+    board.send(UsePowerUpEvent(PowerUp::DefuseMine));
+    board.settle();
+    assert_eq!(board.resource::<PowerUps>().charges(PowerUp::DefuseMine), 0);
+    assert_eq!(board.resource::<PowerUps>().charges(PowerUp::FreezeTimer), 0);
+}
+
+#[test]
+fn fog_blocks_tiles_out_of_sight() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        fog_radius: Some(1),
+        ..board_options((9, 1), &[(4, 0)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    assert_eq!(board.covered_count(), 5, "\n{}", board.render_ascii());
+    board.mark(Coordinates { x: 6, y: 0 });
+    assert_eq!(board.flags_at(Coordinates { x: 6, y: 0 }), 0);
+    board.trigger(Coordinates { x: 8, y: 0 });
+    assert!(board.is_covered(Coordinates { x: 8, y: 0 }));
+    board.mark(Coordinates { x: 4, y: 0 });
+    assert_eq!(board.flags_at(Coordinates { x: 4, y: 0 }), 1);
+}
+
+#[test]
+fn restart_replaces_lost_board() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        builtin_end_screens: true,
+        ..board_options((5, 5), &[(0, 0)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    assert_eq!(board.state(), BoardState::Lost);
+    board.send(RestartBoardEvent);
+    board.settle();
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(board.covered_count(), 25);
+}
+
+#[test]
+fn coordinates_neighbors_and_indices() {
+    let origin = Coordinates::new(0, 0);
+    assert_eq!(origin.neighbors(Adjacency::Square).count(), 3);
+    assert_eq!(origin.neighbors(Adjacency::Orthogonal).count(), 2);
+    assert_eq!(origin.offset(-1, 0), None);
+    let coords = Coordinates::new(3, 2);
+    assert_eq!(Coordinates::from_index(coords.to_index(5), 5), coords);
+    assert_eq!(coords.checked_sub(Coordinates::new(4, 0)), None);
+}
+
+#[test]
+fn auto_flag_flags_proven_mines() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        auto_flag: true,
+        ..board_options((5, 1), &[(2, 0), (4, 0)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    board.update();
+    assert_eq!(board.flags_at(Coordinates { x: 2, y: 0 }), 1, "\n{}", board.render_ascii());
+    assert_eq!(board.flags_at(Coordinates { x: 4, y: 0 }), 0);
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn auto_open_uncovers_satisfied_numbers() {
+    let mut board =
+        HeadlessBoard::new(BoardOptions { auto_open: true, ..board_options((3, 2), &[(0, 0)]) });
+    board.trigger(Coordinates { x: 2, y: 1 });
+    assert_eq!(board.covered_count(), 2, "\n{}", board.render_ascii());
+    board.mark(Coordinates { x: 0, y: 0 });
+    board.update();
+    assert!(!board.is_covered(Coordinates { x: 0, y: 1 }), "\n{}", board.render_ascii());
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn mistake_proof_flags_reject_safe_tiles() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        mistake_proof_flags: true,
+        ..board_options((3, 1), &[(0, 0)])
+    });
+    board.mark(Coordinates { x: 2, y: 0 });
+    assert_eq!(board.flags_at(Coordinates { x: 2, y: 0 }), 0);
+    board.mark(Coordinates { x: 0, y: 0 });
+    assert_eq!(board.flags_at(Coordinates { x: 0, y: 0 }), 1);
+}
+
+#[test]
+fn empty_only_cascade_keeps_numbers_covered() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        cascade: Cascade::EmptyOnly,
+        ..board_options((5, 1), &[(4, 0)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    assert_eq!(board.covered_count(), 2, "\n{}", board.render_ascii());
+    assert!(board.is_covered(Coordinates { x: 3, y: 0 }));
+}
+
+#[test]
+fn snapshot_diff_lists_uncovered_tiles() {
+    let mut board = board((3, 1), &[(0, 0)]);
+    let before = board.board().snapshot();
+    assert_eq!(before.get(Coordinates { x: 2, y: 0 }), Some(VisibleTile::Covered { flags: 0 }));
+    board.trigger(Coordinates { x: 2, y: 0 });
+    let after = board.board().snapshot();
+    assert_eq!(
+        after.diff(&before),
+        vec![
+            (Coordinates { x: 1, y: 0 }, VisibleTile::Number(1)),
+            (Coordinates { x: 2, y: 0 }, VisibleTile::Empty)
+        ]
+    );
+}
+
+#[test]
+fn export_writes_board_image() {
+    let mut board = board((3, 2), &[(0, 0)]);
+    board.trigger(Coordinates { x: 2, y: 1 });
+    let path = std::env::temp_dir().join("minesweeper_export_test.png");
+    board.send(ExportBoardImageEvent { path: path.clone(), solution: false });
+    // The image is saved on the IO task pool
+    board.settle();
+    let image = image::open(&path).expect("exported image").into_rgba8();
+    std::fs::remove_file(&path).ok();
+    // Two rows of 16 pixel tiles under the header
+    assert_eq!(image.height(), 18 + 2 * 16);
+}
+
+#[test]
+fn loss_reveals_bombs_from_the_exploded_one() {
+    let mut board = board((5, 5), &[(0, 0), (4, 4), (1, 0)]);
+    // Both bombs are clicked on the same frame, only the first one explodes
+    board.send(TileTriggerEvent(Coordinates { x: 0, y: 0 }, InputSource::Ai));
+    board.send(TileTriggerEvent(Coordinates { x: 4, y: 4 }, InputSource::Ai));
+    board.update();
+    // The sequence is read on its first frame, before any reveal delay elapsed
+    for _ in 0..10 {
+        if board.app.world.contains_resource::<RevealSequence>() {
+            break;
+        }
+        board.update();
+    }
+    assert_eq!(board.state(), BoardState::Lost);
+    assert!(board.is_covered(Coordinates { x: 4, y: 4 }));
+    let pending: Vec<_> = board.resource::<RevealSequence>().pending.iter().copied().collect();
+    assert_eq!(pending, [Coordinates { x: 1, y: 0 }, Coordinates { x: 4, y: 4 }]);
+}
+
+#[test]
+fn completion_confetti_is_dismissed_by_any_key() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        celebration: Some(Celebration { confetti_count: 10, duration: 5., color_wave: true }),
+        ..board_options((3, 1), &[(0, 0)])
+    });
+    let confetti = |board: &mut HeadlessBoard| {
+        let mut names = board.app.world.query::<&Name>();
+        names.iter(&board.app.world).filter(|n| n.as_str() == "Confetti").count()
+    };
+    board.trigger(Coordinates { x: 2, y: 0 });
+    assert_eq!(board.state(), BoardState::Won);
+    assert_eq!(confetti(&mut board), 10);
+    board.send(KeyboardInput {
+        scan_code: 0,
+        key_code: Some(KeyCode::Space),
+        state: ElementState::Pressed,
+    });
+    board.update();
+    assert_eq!(confetti(&mut board), 0);
+}
+
+#[test]
+fn misclicks_add_time_penalties() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        time_penalties: Some(TimePenalties { wrong_chord: 5., flagged_click: 2. }),
+        ..board_options((5, 1), &[(0, 0)])
+    });
+    board.trigger(Coordinates { x: 1, y: 0 });
+    // The 1 has no flag around it
+    board.chord(Coordinates { x: 1, y: 0 });
+    assert_eq!(board.resource::<GameTimer>().penalty(), 5.);
+    board.mark(Coordinates { x: 2, y: 0 });
+    board.trigger(Coordinates { x: 2, y: 0 });
+    assert_eq!(board.resource::<GameTimer>().penalty(), 7.);
+    assert!(board.resource::<GameTimer>().elapsed() >= 7.);
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn retry_replays_lost_board_with_its_flags() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        map_size: (5, 5),
+        layout: BoardLayout::Random { count: 5 },
+        ..Default::default()
+    });
+    let bombs = |board: &HeadlessBoard| {
+        let tiles = (0..5).flat_map(|y| (0..5).map(move |x| Coordinates { x, y }));
+        tiles.filter(|c| board.board().tile_map.is_bomb_at(*c)).collect::<Vec<_>>()
+    };
+    let layout = bombs(&board);
+    board.mark(layout[0]);
+    board.trigger(layout[1]);
+    assert_eq!(board.state(), BoardState::Lost);
+    board.send(RetryBoardEvent { restore_flags: true });
+    board.settle();
+    board.update();
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(bombs(&board), layout);
+    assert_eq!(board.flags_at(layout[0]), 1);
+    assert_eq!(board.resource::<BoardOptions>().seed, None);
+}
+
+#[test]
+fn win_flags_remaining_mines() {
+    let mut board = board((5, 5), &[(0, 0), (4, 4)]);
+    board.mark(Coordinates { x: 4, y: 4 });
+    board.trigger(Coordinates { x: 2, y: 2 });
+    assert_eq!(board.state(), BoardState::Won);
+    assert_eq!(board.flags_at(Coordinates { x: 0, y: 0 }), 1);
+    assert_eq!(board.flags_at(Coordinates { x: 4, y: 4 }), 1);
+}
+
+#[test]
+fn remaining_numbers_subtract_flags() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        remaining_numbers: true,
+        ..board_options((4, 1), &[(0, 0), (3, 0)])
+    });
+    board.trigger(Coordinates { x: 1, y: 0 });
+    let number = |board: &mut HeadlessBoard| {
+        let mut tiles = board.app.world.query::<(&Coordinates, &Children)>();
+        let (_, children) = tiles
+            .iter(&board.app.world)
+            .find(|(c, _)| **c == Coordinates { x: 1, y: 0 })
+            .expect("numbered tile");
+        let text = children.iter().find_map(|c| board.app.world.get::<Text>(*c)).expect("text");
+        text.sections[0].value.clone()
+    };
+    assert_eq!(number(&mut board), "1");
+    board.mark(Coordinates { x: 0, y: 0 });
+    assert_eq!(number(&mut board), "0");
+}
+
+#[test]
+fn async_cascade_reaches_completion() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        async_cascade: Some(0),
+        ..board_options((5, 5), &[(0, 0)])
+    });
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.covered_count(), 1, "\n{}", board.render_ascii());
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn async_cascade_stops_at_ice() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        async_cascade: Some(0),
+        tile_modifiers: TileModifiers { ice: vec![(2, 0), (2, 1), (2, 2)], ..Default::default() },
+        ..board_options((5, 3), &[(4, 2)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    assert!(!board.is_covered(Coordinates { x: 1, y: 2 }), "\n{}", board.render_ascii());
+    assert!(board.is_covered(Coordinates { x: 2, y: 0 }));
+    assert!(board.is_covered(Coordinates { x: 3, y: 0 }));
+}
+
+#[test]
+fn first_board_can_be_generated_in_background() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        background_generation: Some(0),
+        ..board_options((5, 5), &[(0, 0)])
+    });
+    assert_eq!(board.state(), BoardState::Playing);
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn background_generation_keeps_board_until_ready() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        background_generation: Some(0),
+        ..board_options((5, 5), &[(0, 0)])
+    });
+    board.trigger(Coordinates { x: 0, y: 0 });
+    board.send(RestartBoardEvent);
+    board.update();
+    assert_eq!(board.state(), BoardState::Lost);
+    for _ in 0..10_000 {
+        if board.state() == BoardState::Playing {
+            break;
+        }
+        board.update();
+    }
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(board.covered_count(), 25);
+}
+
+#[test]
+fn restart_reuses_tile_entities() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    let tiles = |board: &mut HeadlessBoard| {
+        let mut tiles = board.app.world.query_filtered::<Entity, With<Coordinates>>();
+        let mut tiles: Vec<_> = tiles.iter(&board.app.world).collect();
+        tiles.sort();
+        tiles
+    };
+    let before = tiles(&mut board);
+    board.trigger(Coordinates { x: 0, y: 0 });
+    board.send(RestartBoardEvent);
+    board.settle();
+    assert_eq!(tiles(&mut board), before);
+    assert_eq!(board.covered_count(), 25);
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn texture_atlas_falls_back_to_plain_sprites_without_images() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        texture_atlas: true,
+        ..board_options((3, 3), &[(0, 0)])
+    });
+    board.trigger(Coordinates { x: 2, y: 2 });
+    let mut texts = board.app.world.query::<&Text>();
+    assert!(texts.iter(&board.app.world).any(|t| t.sections[0].value == "1"));
+    let mut atlas_sprites = board.app.world.query::<&TextureAtlasSprite>();
+    assert_eq!(atlas_sprites.iter(&board.app.world).count(), 0);
+}
+
+#[test]
+fn night_mode_lights_around_uncovered_tiles() {
+    let mut board = board((6, 1), &[(2, 0)]);
+    board.send(NightModeEvent(Some(NightMode::default())));
+    board.trigger(Coordinates { x: 0, y: 0 });
+    board.update();
+    let shade = |board: &mut HeadlessBoard, x: u16| {
+        let mut shades = board.app.world.query::<(&Name, &Parent, &Sprite)>();
+        let shades: Vec<_> = shades
+            .iter(&board.app.world)
+            .filter(|(name, _, _)| name.as_str() == "Night Shade")
+            .map(|(_, parent, sprite)| (parent.0, sprite.color.a()))
+            .collect();
+        shades
+            .into_iter()
+            .find(|(tile, _)| board.app.world.get::<Coordinates>(*tile).unwrap().x == x)
+            .expect("night shade")
+            .1
+    };
+    assert_eq!(shade(&mut board, 0), 0.);
+    assert!(shade(&mut board, 2) > 0.);
+    assert!(shade(&mut board, 2) < shade(&mut board, 5));
+    board.send(NightModeEvent(None));
+    board.update();
+    board.update();
+    let mut names = board.app.world.query::<&Name>();
+    assert!(names.iter(&board.app.world).all(|name| name.as_str() != "Night Shade"));
+}
+
+#[test]
+fn uncovered_numbers_pop_in() {
+    let mut board = board((3, 3), &[(0, 0)]);
+    board.trigger(Coordinates { x: 1, y: 1 });
+    let mut numbers = board.app.world.query::<(&Text, &Tween)>();
+    let (text, tween) = numbers.iter(&board.app.world).next().expect("number tween");
+    assert_eq!(text.sections[0].value, "1");
+    assert_eq!(tween.easing, Easing::BackOut);
+    assert_eq!(tween.target, TweenTarget::Scale { from: Vec3::ZERO, to: Vec3::ONE });
+}
+
+#[test]
+fn progress_bar_fills_with_uncovered_tiles() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        progress_bar: Some(ProgressBar::default()),
+        ..board_options((4, 1), &[(3, 0)])
+    });
+    let fill = |board: &mut HeadlessBoard| {
+        let mut fills = board.app.world.query::<(&Name, &Transform)>();
+        fills
+            .iter(&board.app.world)
+            .find(|(name, _)| name.as_str() == "Progress Bar Fill")
+            .expect("progress bar fill")
+            .1
+            .scale
+            .x
+    };
+    board.update();
+    assert_eq!(fill(&mut board), 0.);
+    board.trigger(Coordinates { x: 2, y: 0 });
+    board.update();
+    assert!((fill(&mut board) - 1. / 3.).abs() < 1e-6);
+}
+
+#[test]
+fn board_geometry_maps_tiles_to_world_positions() {
+    let board = board((6, 4), &[(0, 0)]);
+    let geometry = *board.resource::<BoardGeometry>();
+    assert_eq!(geometry.bounds.size.x, 6. * geometry.tile_size);
+    assert_eq!(geometry.bounds.size.y, 4. * geometry.tile_size);
+    for coords in [Coordinates { x: 0, y: 0 }, Coordinates { x: 5, y: 3 }] {
+        assert_eq!(geometry.world_position(geometry.tile_center(coords)), Some(coords));
+    }
+    assert_eq!(geometry.world_position(geometry.bounds.position - geometry.tile_size), None);
+}
+
+#[test]
+fn systems_are_inert_without_board() {
+    let mut board = board((5, 5), &[(0, 0)]);
+    board.remove_board();
+    board.send(UncoverTileEvent(Coordinates { x: 4, y: 4 }));
+    board.send(RestartBoardEvent);
+    for _ in 0..10 {
+        board.update();
+    }
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn tile_map_text_round_trips() {
+    let tile_map = TileMap::from_text("*..\n...\n..*\n").unwrap();
+    assert_eq!(tile_map.bomb_count(), 2);
+    assert!(tile_map.is_bomb_at(Coordinates { x: 0, y: 2 }));
+    assert_eq!(tile_map.to_text(), "*1.\n121\n.1*\n");
+    assert_eq!(TileMap::from_text(&tile_map.to_text()).unwrap().to_text(), tile_map.to_text());
+    assert_eq!(
+        TileMap::from_text("..\n.x").unwrap_err(),
+        TileMapTextError::InvalidCharacter { character: 'x', line: 2 }
+    );
+}
+
+#[test]
+fn modifier_tiles_resist_uncovering() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        tile_modifiers: TileModifiers {
+            ice: vec![(0, 0)],
+            locked: vec![(1, 1)],
+            keys: vec![(3, 0)],
+            teleports: vec![((0, 1), (3, 1))],
+        },
+        ..board_options((4, 2), &[(1, 0), (2, 1)])
+    });
+    let ice = Coordinates { x: 0, y: 0 };
+    board.trigger(ice);
+    assert!(board.is_covered(ice));
+    board.trigger(ice);
+    assert!(!board.is_covered(ice));
+    let locked = Coordinates { x: 1, y: 1 };
+    board.trigger(locked);
+    assert!(board.is_covered(locked));
+    board.trigger(Coordinates { x: 0, y: 1 });
+    assert!(!board.is_covered(Coordinates { x: 3, y: 1 }));
+    board.trigger(Coordinates { x: 3, y: 0 });
+    board.trigger(locked);
+    assert!(!board.is_covered(locked));
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn moving_mines_keep_the_board_consistent() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        moving_mines: Some(MovingMines { interval: None, uncovers: Some(1) }),
+        ..board_options((6, 6), &[(0, 0), (5, 5)])
+    });
+    let number = Coordinates { x: 1, y: 1 };
+    board.trigger(number);
+    let tile_map = &board.board().tile_map;
+    let text = tile_map.to_text();
+    assert_eq!(text.matches('*').count(), 2);
+    assert_eq!(TileMap::from_text(&text).unwrap().to_text(), text);
+    // The uncovered number may change but never becomes an unrevealed opening
+    assert!(matches!(tile_map[1][1], Tile::BombNeighbor(_)), "\n{}", text);
+    assert!(!board.is_covered(number));
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn mega_mines_blast_their_neighbors_open() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        lives: 2,
+        mine_kinds: MineKinds { mega: 1., ..Default::default() },
+        ..board_options((5, 5), &[(2, 2), (0, 0)])
+    });
+    let mega = Coordinates { x: 2, y: 2 };
+    assert_eq!(board.board().mine_kind(&mega), MineKind::Mega);
+    board.trigger(mega);
+    assert_eq!(board.state(), BoardState::Playing);
+    assert!(board.board().tile_map.neighbors(mega).all(|c| !board.is_covered(c)));
+    assert!(board.is_covered(Coordinates { x: 0, y: 0 }));
+}
+
+#[test]
+fn color_region_numbers_count_each_region() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        variant: BoardVariant::ColorRegions { regions: 3 },
+        ..board_options((3, 1), &[(0, 0), (2, 0)])
+    });
+    let number = Coordinates { x: 1, y: 0 };
+    board.trigger(number);
+    let mut tiles = board.app.world.query::<(&Coordinates, &Children)>();
+    let (_, children) =
+        tiles.iter(&board.app.world).find(|(c, _)| **c == number).expect("numbered tile");
+    let text = children.iter().find_map(|c| board.app.world.get::<Text>(*c)).expect("text");
+    // Every tile is its own region
+    let values: Vec<_> = text.sections.iter().map(|s| s.value.trim()).collect();
+    assert_eq!(values, ["1", "1"]);
+    assert_ne!(text.sections[0].style.color, text.sections[1].style.color);
+    assert_eq!(board.board().proven_mines(number).len(), 2);
+}
+
+#[test]
+fn split_counts_show_orthogonal_and_diagonal_mines() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        split_counts: true,
+        ..board_options((3, 2), &[(0, 0), (1, 0), (2, 0)])
+    });
+    let number = Coordinates { x: 1, y: 1 };
+    assert_eq!(board.board().tile_map.split_bomb_count(number), (1, 2));
+    board.trigger(number);
+    let mut tiles = board.app.world.query::<(&Coordinates, &Children)>();
+    let (_, children) =
+        tiles.iter(&board.app.world).find(|(c, _)| **c == number).expect("numbered tile");
+    let text = children.iter().find_map(|c| board.app.world.get::<Text>(*c)).expect("text");
+    let values: Vec<_> = text.sections.iter().map(|s| s.value.as_str()).collect();
+    assert_eq!(values, ["1\n", "2"]);
+}
+
+#[test]
+fn rotated_view_maps_input_through_the_rotation() {
+    let mut board = board((4, 2), &[(0, 0)]);
+    let corner = Coordinates { x: 3, y: 1 };
+    let before = board.resource::<BoardGeometry>().tile_center(corner);
+    board.app.world.resource_mut::<BoardGeometry>().rotate_view(180.);
+    board.update();
+    let geometry = *board.resource::<BoardGeometry>();
+    let after = geometry.tile_center(corner);
+    // Half a turn swaps the opposite corners
+    let origin = geometry.bounds.tile_center(Coordinates { x: 0, y: 0 }, geometry.tile_size);
+    assert!(after.distance(origin) < 0.01);
+    assert_eq!(geometry.world_position(after), Some(corner));
+    assert_eq!(geometry.world_position(before), Some(Coordinates { x: 0, y: 0 }));
+    let transform = board.app.world.get::<Transform>(board.board().entity).unwrap();
+    assert_eq!(*transform, geometry.view_transform(transform.translation.z));
+}
+
+#[test]
+fn transform_tweens_move_rotate_and_scale() {
+    let from = Transform::default();
+    let to = Transform::from_xyz(10., 20., 0.).with_scale(Vec3::new(2., 2., 1.));
+    let mut tween = Tween::new(1., TweenTarget::Transform { from, to }, Easing::Linear);
+    tween.timer.tick(std::time::Duration::from_secs_f32(0.5));
+    let mut transform = from;
+    tween.apply(&mut transform);
+    assert_eq!(transform.translation, Vec3::new(5., 10., 0.));
+    assert_eq!(transform.scale, Vec3::new(1.5, 1.5, 1.));
+}
+
+#[test]
+fn budgeted_boards_spawn_over_several_frames() {
+    let mut board = HeadlessBoard::new(BoardOptions {
+        spawn_budget: Some(30),
+        ..board_options((10, 10), &[(0, 0)])
+    });
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(board.covered_count(), 100);
+    board.send(RestartBoardEvent);
+    board.update();
+    assert_eq!(board.state(), BoardState::Loading);
+    assert_eq!(board.resource::<BoardLoading>().progress(), 0.3);
+    board.settle();
+    assert_eq!(board.state(), BoardState::Playing);
+    assert_eq!(board.covered_count(), 100);
+}
+
+#[test]
+fn invalid_options_fail_or_get_clamped() {
+    let options =
+        BoardOptions { layout: BoardLayout::Random { count: 25 }, ..board_options((5, 5), &[]) };
+    let error = BoardOptionsError::TooManyBombs { count: 25, tiles: 25 };
+    assert_eq!(options.validate(), Err(error));
+    let strict = HeadlessBoard::new(options.clone());
+    assert!(!strict.app.world.contains_resource::<BoardState>());
+    let lenient = HeadlessBoard::new(BoardOptions { lenient: true, ..options });
+    assert_eq!(lenient.board().tile_map.bomb_count(), 24);
+    assert_eq!(lenient.state(), BoardState::Playing);
+}
+
+#[test]
+fn options_builder_reports_invalid_options() {
+    let options = BoardOptionsBuilder::default().map_size(9, 9).bomb_count(10).build().unwrap();
+    assert_eq!(options.map_size, (9, 9));
+    let error = BoardOptionsBuilder::default().map_size(3, 3).bomb_count(9).build().unwrap_err();
+    assert_eq!(error, BoardOptionsError::TooManyBombs { count: 9, tiles: 9 });
+    let padded = BoardOptionsBuilder::default().tile_size(TileSize::Fixed(20.)).tile_padding(20.);
+    assert_eq!(
+        padded.build().unwrap_err(),
+        BoardOptionsError::InvalidPadding { padding: 20., tile_size: 20. }
+    );
+    let error = BoardOptionsBuilder::default()
+        .tile_size(TileSize::Adaptive { min: 40., max: 10. })
+        .build()
+        .unwrap_err();
+    assert_eq!(error, BoardOptionsError::InvalidTileSize { min: 40., max: 10. });
+}
+
+#[test]
+fn tile_map_iterates_over_its_tiles() {
+    let tile_map = TileMap::from_bomb_positions(3, 2, &[Coordinates { x: 1, y: 0 }]);
+    let tiles: Vec<(Coordinates, Tile)> = tile_map.tiles().collect();
+    assert_eq!(tiles.len(), 6);
+    assert_eq!(tiles[1], (Coordinates { x: 1, y: 0 }, Tile::Bomb));
+    assert_eq!(tiles[5], (Coordinates { x: 2, y: 1 }, Tile::BombNeighbor(1)));
+    let bombs = tiles.iter().filter(|(coords, _)| tile_map.is_bomb_at(*coords)).count();
+    assert_eq!(bombs, 1);
+    assert_eq!(tile_map.bomb_count_at(Coordinates { x: 0, y: 1 }), 1);
+}
+
+#[test]
+fn board_hierarchy_has_marker_components() {
+    let mut board = board((3, 3), &[(0, 0), (2, 2)]);
+    let world = &mut board.app.world;
+    assert_eq!(world.query_filtered::<(), With<BoardRoot>>().iter(world).count(), 1);
+    assert_eq!(world.query_filtered::<(), With<TileRoot>>().iter(world).count(), 9);
+    assert_eq!(world.query_filtered::<(), With<CoverSprite>>().iter(world).count(), 9);
+    assert_eq!(world.query_filtered::<(), With<BombSprite>>().iter(world).count(), 2);
+    assert_eq!(world.query_filtered::<(), With<NumberText>>().iter(world).count(), 5);
+}
+
+#[test]
+fn armor_is_capped_to_fit_neighbor_counts() {
+    let options = BoardOptions {
+        variant: BoardVariant::Armored { armored_count: 8, max_armor: 100 },
+        ..board_options((3, 3), &[])
+    };
+    assert_eq!(options.validate(), Err(BoardOptionsError::InvalidArmor { max_armor: 100 }));
+    let clamped = options.clamped();
+    assert_eq!(clamped.variant, BoardVariant::Armored { armored_count: 8, max_armor: 15 });
+    assert!(clamped.validate().is_ok());
+}
+
+#[test]
+fn low_max_armor_still_allows_flagging_armored_bombs() {
+    let variant = BoardVariant::Armored { armored_count: 1, max_armor: 1 };
+    assert_eq!(variant.max_flags(), 2);
+}
+
+#[test]
+fn partial_option_presets_deserialize() {
+    let options: BoardOptions =
+        ron::from_str("(map_size: (20, 10), layout: Random(count: 30), safe_start: true)").unwrap();
+    assert_eq!(options.map_size, (20, 10));
+    assert_eq!(options.layout, BoardLayout::Random { count: 30 });
+    assert!(options.safe_start);
+    assert_eq!(options.lives, BoardOptions::default().lives);
+}
+
+#[test]
+fn explicit_bomb_count_ignores_duplicates() {
+    let layout = BoardLayout::Explicit(vec![
+        Coordinates { x: 1, y: 1 },
+        Coordinates { x: 1, y: 1 },
+        Coordinates { x: 2, y: 0 },
+    ]);
+    assert_eq!(layout.bomb_count(), 2);
+}
+
+#[test]
+fn moving_mines_need_plain_counts() {
+    let options = BoardOptions {
+        moving_mines: Some(MovingMines { interval: Some(1.), uncovers: None }),
+        split_counts: true,
+        ..board_options((3, 3), &[])
+    };
+    assert_eq!(options.validate(), Err(BoardOptionsError::UnsupportedMovingMines));
+    let clamped = options.clamped();
+    assert!(clamped.moving_mines.is_none());
+    assert!(clamped.validate().is_ok());
+}
+
+/// Presses then releases `key`
+fn press(board: &mut HeadlessBoard, key: KeyCode) {
+    for state in [ElementState::Pressed, ElementState::Released] {
+        board.send(KeyboardInput { scan_code: 0, key_code: Some(key), state });
+        board.settle();
+    }
+}
+
+/// Versus race board, the rival cursor starting at the center
+fn race(map_size: (u16, u16), bombs: &[(u16, u16)]) -> HeadlessBoard {
+    HeadlessBoard::new(BoardOptions { versus: true, ..board_options(map_size, bombs) })
+}
+
+#[test]
+fn keyboard_plays_the_rival_board() {
+    let mut board = race((5, 5), &[(0, 0)]);
+    press(&mut board, KeyCode::Space);
+    let race = board.resource::<VersusRace>();
+    assert_eq!(race.progress(Player::Two).ratio(), 1.);
+    assert_eq!(race.progress(Player::One).uncovered, 0);
+    assert_eq!(race.winner(), Some(Player::Two));
+    assert_eq!(board.covered_count(), 25, "the main board is left to the mouse");
+    assert_eq!(board.state(), BoardState::Lost);
+}
+
+#[test]
+fn rival_explosion_wins_the_race() {
+    let mut board = race((5, 5), &[(2, 2)]);
+    press(&mut board, KeyCode::Space);
+    let race = board.resource::<VersusRace>();
+    assert!(race.progress(Player::Two).exploded);
+    assert_eq!(race.winner(), Some(Player::One));
+    assert_eq!(board.state(), BoardState::Won);
+}
+
+#[test]
+fn race_boards_share_the_safe_start() {
+    let wall = [(2, 0), (2, 1), (2, 2), (2, 3), (2, 4)];
+    let mut board = HeadlessBoard::new(BoardOptions {
+        versus: true,
+        safe_start: true,
+        ..board_options((5, 5), &wall)
+    });
+    let race = board.resource::<VersusRace>();
+    assert_eq!(race.progress(Player::One).uncovered, 10);
+    assert_eq!(race.progress(Player::One), race.progress(Player::Two));
+    board.trigger(Coordinates { x: 4, y: 4 });
+    assert_eq!(board.state(), BoardState::Won);
+    assert_eq!(board.resource::<VersusRace>().winner(), Some(Player::One));
+}
+
+/// Moves the rival cursor from `from` to `to` with the cursor keys
+fn move_rival(board: &mut HeadlessBoard, from: Coordinates, to: Coordinates) {
+    let x = if to.x < from.x { KeyCode::Left } else { KeyCode::Right };
+    for _ in 0..(to.x as i32 - from.x as i32).abs() {
+        press(board, x);
+    }
+    let y = if to.y < from.y { KeyCode::Down } else { KeyCode::Up };
+    for _ in 0..(to.y as i32 - from.y as i32).abs() {
+        press(board, y);
+    }
+}
+
+#[test]
+fn race_boards_resolve_clicks_alike() {
+    let mut board = race((7, 7), &[(1, 1), (3, 3), (1, 5), (5, 5)]);
+    let mut cursor = Coordinates { x: 3, y: 3 };
+    let clicks = [
+        ((6, 0), KeyCode::Space),
+        ((3, 3), KeyCode::F),
+        ((4, 3), KeyCode::D),
+        ((0, 0), KeyCode::F),
+        ((0, 0), KeyCode::F),
+    ];
+    for ((x, y), key) in clicks {
+        let coords = Coordinates { x, y };
+        match key {
+            KeyCode::Space => board.trigger(coords),
+            KeyCode::F => board.mark(coords),
+            _ => board.chord(coords),
+        }
+        move_rival(&mut board, cursor, coords);
+        cursor = coords;
+        press(&mut board, key);
+        let rival = board.resource::<VersusRace>().rival_board().render_ascii();
+        assert_eq!(rival, board.render_ascii(), "after {:?} on {}", key, coords);
+    }
+    assert!(board.is_covered(Coordinates { x: 0, y: 0 }));
+    assert_eq!(board.flags_at(Coordinates { x: 0, y: 0 }), 0);
+    assert_eq!(board.state(), BoardState::Playing);
+}
+
+#[test]
+fn versus_needs_plain_rules() {
+    let options = BoardOptions { versus: true, lives: 3, ..board_options((3, 3), &[]) };
+    assert_eq!(options.validate(), Err(BoardOptionsError::UnsupportedVersus));
+    assert!(!options.clamped().versus);
+}
@@ -0,0 +1,32 @@
+use bevy::prelude::Component;
+
+/// Root node of the menu, despawned with its children when leaving the menu
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct MenuRoot;
+
+/// Camera rendering the menu
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct MenuCamera;
+
+/// Label displaying the custom board parameters
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub struct CustomLabel;
+
+/// Custom board parameter adjusted by a menu button
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum CustomParam {
+    Width,
+    Height,
+    Bombs,
+}
+
+/// Action of a menu button
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Component)]
+pub enum MenuAction {
+    /// Starts a game with the given difficulty
+    Play(board_plugin::resources::Difficulty),
+    /// Starts a game with the custom board parameters
+    PlayCustom,
+    /// Adds `delta` to a custom board parameter
+    Adjust { param: CustomParam, delta: i16 },
+}
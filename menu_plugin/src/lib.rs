@@ -0,0 +1,35 @@
+mod components;
+pub mod resources;
+mod systems;
+
+use crate::resources::{MenuAssets, MenuOptions, MenuTarget};
+use crate::systems::{button_colors, cleanup_menu, menu_actions, spawn_menu, update_custom_label};
+use bevy::ecs::schedule::StateData;
+use bevy::prelude::*;
+
+/// Difficulty selection menu, starting a game with the chosen `BoardOptions`
+pub struct MenuPlugin<T> {
+    /// State displaying the menu
+    pub menu_state: T,
+    /// State entered once a difficulty is chosen, running the `BoardPlugin`
+    pub running_state: T,
+}
+
+impl<T: StateData> Plugin for MenuPlugin<T> {
+    fn build(&self, app: &mut App) {
+        app.add_system_set(SystemSet::on_enter(self.menu_state.clone()).with_system(spawn_menu))
+            .add_system_set(
+                SystemSet::on_update(self.menu_state.clone())
+                    .with_system(button_colors)
+                    .with_system(update_custom_label)
+                    .with_system(menu_actions::<T>),
+            )
+            .add_system_set(SystemSet::on_exit(self.menu_state.clone()).with_system(cleanup_menu))
+            .insert_resource(MenuTarget(self.running_state.clone()))
+            // Options may be customized by the host app beforehand
+            .init_resource::<MenuOptions>()
+            // Assets may be customized by the host app beforehand
+            .init_resource::<MenuAssets>();
+        info!("Loaded Menu Plugin");
+    }
+}
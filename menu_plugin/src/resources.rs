@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use board_plugin::resources::{BoardLayout, BoardOptions, Difficulty};
+
+/// Smallest custom board side
+const MIN_SIDE: u16 = 5;
+/// Largest custom board side
+const MAX_SIDE: u16 = 50;
+
+/// Menu options. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct MenuOptions {
+    /// Options every started game is based on, the chosen difficulty replacing the map size and
+    /// layout
+    pub base: BoardOptions,
+    /// Custom board width
+    pub custom_width: u16,
+    /// Custom board height
+    pub custom_height: u16,
+    /// Custom board bomb count
+    pub custom_bombs: u16,
+}
+
+impl Default for MenuOptions {
+    fn default() -> Self {
+        Self {
+            base: BoardOptions { safe_start: true, ..Default::default() },
+            custom_width: 20,
+            custom_height: 20,
+            custom_bombs: 50,
+        }
+    }
+}
+
+impl MenuOptions {
+    /// Custom board difficulty
+    pub fn custom(&self) -> Difficulty {
+        Difficulty::from_params((self.custom_width, self.custom_height), self.custom_bombs)
+    }
+
+    /// Sets the custom board, within the menu bounds
+    pub fn set_custom(&mut self, width: u16, height: u16, bomb_count: u16) {
+        self.custom_width = width.clamp(MIN_SIDE, MAX_SIDE);
+        self.custom_height = height.clamp(MIN_SIDE, MAX_SIDE);
+        self.custom_bombs = bomb_count.max(1);
+        self.clamp_bombs();
+    }
+
+    /// Adds `delta` to the custom width, keeping the bomb count below the tile count
+    pub fn adjust_width(&mut self, delta: i16) {
+        self.custom_width = Self::adjust_side(self.custom_width, delta);
+        self.clamp_bombs();
+    }
+
+    /// Adds `delta` to the custom height, keeping the bomb count below the tile count
+    pub fn adjust_height(&mut self, delta: i16) {
+        self.custom_height = Self::adjust_side(self.custom_height, delta);
+        self.clamp_bombs();
+    }
+
+    /// Adds `delta` to the custom bomb count, keeping at least one bomb and one safe tile
+    pub fn adjust_bombs(&mut self, delta: i16) {
+        self.custom_bombs = (self.custom_bombs as i16).saturating_add(delta).max(1) as u16;
+        self.clamp_bombs();
+    }
+
+    /// Board options of a game with `difficulty`
+    pub fn board_options(&self, difficulty: Difficulty) -> BoardOptions {
+        BoardOptions {
+            map_size: difficulty.map_size(),
+            layout: BoardLayout::Random { count: difficulty.bomb_count() },
+            ..self.base.clone()
+        }
+    }
+
+    fn adjust_side(side: u16, delta: i16) -> u16 {
+        (side as i16).saturating_add(delta).clamp(MIN_SIDE as i16, MAX_SIDE as i16) as u16
+    }
+
+    fn clamp_bombs(&mut self) {
+        let max = self.custom_width * self.custom_height - 1;
+        self.custom_bombs = self.custom_bombs.min(max);
+    }
+}
+
+/// Menu assets. Must be used as a resource
+#[derive(Debug, Clone)]
+pub struct MenuAssets {
+    pub font: Handle<Font>,
+    pub background_color: Color,
+    pub button_color: Color,
+    pub hovered_button_color: Color,
+    pub pressed_button_color: Color,
+    pub text_color: Color,
+}
+
+impl FromWorld for MenuAssets {
+    fn from_world(world: &mut World) -> Self {
+        let asset_server = world.get_resource::<AssetServer>().expect("Missing AssetServer");
+        Self {
+            font: asset_server.load("fonts/pixeled.ttf"),
+            background_color: Color::rgb(0.1, 0.1, 0.1),
+            button_color: Color::DARK_GRAY,
+            hovered_button_color: Color::rgb(0.4, 0.4, 0.4),
+            pressed_button_color: Color::GRAY,
+            text_color: Color::WHITE,
+        }
+    }
+}
+
+/// State entered once a difficulty is chosen
+#[derive(Debug, Clone)]
+pub struct MenuTarget<T>(pub T);
@@ -0,0 +1,195 @@
+use crate::components::{CustomLabel, CustomParam, MenuAction, MenuCamera, MenuRoot};
+use crate::resources::{MenuAssets, MenuOptions, MenuTarget};
+use bevy::ecs::schedule::StateData;
+use bevy::prelude::*;
+use board_plugin::resources::{Difficulty, Settings};
+
+/// Menu text font size
+const FONT_SIZE: f32 = 24.;
+
+/// Spawns the menu and its camera, starting from the difficulty chosen last time
+pub fn spawn_menu(
+    mut commands: Commands,
+    assets: Res<MenuAssets>,
+    mut options: ResMut<MenuOptions>,
+    settings: Option<Res<Settings>>,
+) {
+    let last = settings.map(|s| s.difficulty);
+    if let Some(Difficulty::Custom { width, height, bomb_count }) = last {
+        options.set_custom(width, height, bomb_count);
+    }
+    commands.spawn_bundle(UiCameraBundle::default()).insert(MenuCamera);
+    commands
+        .spawn_bundle(NodeBundle {
+            style: Style {
+                size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                // Bevy UI goes bottom to top, the menu is read top to bottom
+                flex_direction: FlexDirection::ColumnReverse,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: assets.background_color.into(),
+            ..Default::default()
+        })
+        .insert(MenuRoot)
+        .insert(Name::new("Menu"))
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle("Mine Sweeper!", FONT_SIZE * 2., &assets));
+            if let Some(last) = last {
+                let label = format!("Play again: {}", difficulty_label(last));
+                spawn_button(parent, &label, MenuAction::Play(last), &assets);
+            }
+            for difficulty in [Difficulty::Beginner, Difficulty::Intermediate, Difficulty::Expert] {
+                let label = difficulty_label(difficulty);
+                spawn_button(parent, &label, MenuAction::Play(difficulty), &assets);
+            }
+            parent
+                .spawn_bundle(text_bundle(&custom_label(&options), FONT_SIZE, &assets))
+                .insert(CustomLabel);
+            for (name, param) in [
+                ("Width", CustomParam::Width),
+                ("Height", CustomParam::Height),
+                ("Bombs", CustomParam::Bombs),
+            ] {
+                parent.spawn_bundle(row()).with_children(|parent| {
+                    let minus = MenuAction::Adjust { param, delta: -1 };
+                    spawn_button(parent, &format!("{} -", name), minus, &assets);
+                    let plus = MenuAction::Adjust { param, delta: 1 };
+                    spawn_button(parent, &format!("{} +", name), plus, &assets);
+                });
+            }
+            spawn_button(parent, "Custom", MenuAction::PlayCustom, &assets);
+        });
+}
+
+fn row() -> NodeBundle {
+    NodeBundle {
+        style: Style { flex_direction: FlexDirection::Row, ..Default::default() },
+        color: Color::NONE.into(),
+        ..Default::default()
+    }
+}
+
+fn text_bundle(value: &str, font_size: f32, assets: &MenuAssets) -> TextBundle {
+    TextBundle {
+        style: Style { margin: Rect::all(Val::Px(8.)), ..Default::default() },
+        text: Text::with_section(
+            value,
+            TextStyle { font: assets.font.clone(), font_size, color: assets.text_color },
+            Default::default(),
+        ),
+        ..Default::default()
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, label: &str, action: MenuAction, assets: &MenuAssets) {
+    parent
+        .spawn_bundle(ButtonBundle {
+            style: Style {
+                margin: Rect::all(Val::Px(4.)),
+                padding: Rect::all(Val::Px(8.)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..Default::default()
+            },
+            color: assets.button_color.into(),
+            ..Default::default()
+        })
+        .insert(action)
+        .with_children(|parent| {
+            parent.spawn_bundle(text_bundle(label, FONT_SIZE, assets));
+        });
+}
+
+fn difficulty_label(difficulty: Difficulty) -> String {
+    let (width, height) = difficulty.map_size();
+    let name = match difficulty {
+        Difficulty::Custom { .. } => "Custom".to_string(),
+        _ => format!("{:?}", difficulty),
+    };
+    format!("{} ({}x{}, {} bombs)", name, width, height, difficulty.bomb_count())
+}
+
+fn custom_label(options: &MenuOptions) -> String {
+    format!(
+        "Custom: {}x{}, {} bombs",
+        options.custom_width, options.custom_height, options.custom_bombs
+    )
+}
+
+/// Tints the buttons on hover and press
+pub fn button_colors(
+    assets: Res<MenuAssets>,
+    mut buttons: Query<(&Interaction, &mut UiColor), (Changed<Interaction>, With<Button>)>,
+) {
+    for (interaction, mut color) in buttons.iter_mut() {
+        *color = match interaction {
+            Interaction::Clicked => assets.pressed_button_color,
+            Interaction::Hovered => assets.hovered_button_color,
+            Interaction::None => assets.button_color,
+        }
+        .into();
+    }
+}
+
+/// Keeps the custom board label in sync with the menu options
+pub fn update_custom_label(
+    options: Res<MenuOptions>,
+    mut labels: Query<&mut Text, With<CustomLabel>>,
+) {
+    if !options.is_changed() {
+        return;
+    }
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = custom_label(&options);
+    }
+}
+
+/// Adjusts the custom board on button clicks, or starts a game with the chosen difficulty
+pub fn menu_actions<T: StateData>(
+    mut commands: Commands,
+    mut options: ResMut<MenuOptions>,
+    target: Res<MenuTarget<T>>,
+    mut settings: Option<ResMut<Settings>>,
+    mut state: ResMut<State<T>>,
+    interactions: Query<(&Interaction, &MenuAction), Changed<Interaction>>,
+) {
+    for (interaction, action) in interactions.iter() {
+        if *interaction != Interaction::Clicked {
+            continue;
+        }
+        let difficulty = match *action {
+            MenuAction::Play(difficulty) => difficulty,
+            MenuAction::PlayCustom => options.custom(),
+            MenuAction::Adjust { param, delta } => {
+                match param {
+                    CustomParam::Width => options.adjust_width(delta),
+                    CustomParam::Height => options.adjust_height(delta),
+                    CustomParam::Bombs => options.adjust_bombs(delta),
+                }
+                continue;
+            }
+        };
+        info!("Starting {:?} game", difficulty);
+        // The chosen difficulty is remembered across runs
+        if let Some(settings) = settings.as_mut() {
+            settings.difficulty = difficulty;
+        }
+        commands.insert_resource(options.board_options(difficulty));
+        if let Err(e) = state.set(target.0.clone()) {
+            error!("Failed to start the game: {}", e);
+        }
+        return;
+    }
+}
+
+/// Despawns the menu and its camera
+pub fn cleanup_menu(
+    mut commands: Commands,
+    menus: Query<Entity, Or<(With<MenuRoot>, With<MenuCamera>)>>,
+) {
+    for entity in menus.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
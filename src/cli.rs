@@ -0,0 +1,121 @@
+use crate::window::WindowPreset;
+use board_plugin::resources::NumberPalette;
+
+pub const USAGE: &str = "Usage: minesweeper-tutorial [OPTIONS]
+
+Options:
+    --width <TILES>       Board width, skipping the menu
+    --height <TILES>      Board height, skipping the menu
+    --bombs <COUNT>       Bomb count, skipping the menu
+    --seed <SEED>         Bomb placement seed, skipping the menu
+    --versus              Local race on two boards of the same seed, skipping the menu
+    --theme <THEME>       Number palette: classic, deuteranopia, tritanopia or high-contrast
+    --window <PRESET>     Window preset: small, medium, large, borderless or fullscreen
+    --fullscreen          Same as --window borderless, F11 toggling it at runtime
+    -h, --help            Prints this message";
+
+/// Command line options of the example app
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CliArgs {
+    pub width: Option<u16>,
+    pub height: Option<u16>,
+    pub bombs: Option<u16>,
+    pub seed: Option<u64>,
+    pub versus: bool,
+    pub theme: Option<NumberPalette>,
+    pub window: WindowPreset,
+    pub help: bool,
+}
+
+impl CliArgs {
+    /// Parses the arguments following the program name
+    pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Self, String> {
+        let mut parsed = Self::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("Missing value for {}", arg));
+            match arg.as_str() {
+                "--width" => parsed.width = Some(parse_number(&arg, value()?)?),
+                "--height" => parsed.height = Some(parse_number(&arg, value()?)?),
+                "--bombs" => parsed.bombs = Some(parse_number(&arg, value()?)?),
+                "--seed" => parsed.seed = Some(parse_number(&arg, value()?)?),
+                "--versus" => parsed.versus = true,
+                "--theme" => parsed.theme = Some(parse_theme(&value()?)?),
+                "--window" => {
+                    let name = value()?;
+                    parsed.window = WindowPreset::from_name(&name)
+                        .ok_or_else(|| format!("Unknown window preset {}", name))?;
+                }
+                "--fullscreen" => parsed.window = WindowPreset::Borderless,
+                "-h" | "--help" => parsed.help = true,
+                _ => return Err(format!("Unknown argument {}", arg)),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Is a board configured, the menu being skipped
+    pub fn has_board(&self) -> bool {
+        self.width.is_some()
+            || self.height.is_some()
+            || self.bombs.is_some()
+            || self.seed.is_some()
+            || self.versus
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(arg: &str, value: String) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid value {} for {}", value, arg))
+}
+
+fn parse_theme(value: &str) -> Result<NumberPalette, String> {
+    match value {
+        "classic" => Ok(NumberPalette::Classic),
+        "deuteranopia" => Ok(NumberPalette::Deuteranopia),
+        "tritanopia" => Ok(NumberPalette::Tritanopia),
+        "high-contrast" => Ok(NumberPalette::HighContrast),
+        _ => Err(format!("Unknown theme {}", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliArgs, String> {
+        CliArgs::parse(args.iter().map(|a| a.to_string()))
+    }
+
+    #[test]
+    fn no_arguments_open_the_menu() {
+        let args = parse(&[]).unwrap();
+        assert_eq!(args, CliArgs::default());
+        assert_eq!(args.window, WindowPreset::Medium);
+        assert!(!args.has_board());
+    }
+
+    #[test]
+    fn board_options_skip_the_menu() {
+        let args =
+            parse(&["--width", "30", "--bombs", "99", "--seed", "7", "--theme", "tritanopia"])
+                .unwrap();
+        assert_eq!(args.width, Some(30));
+        assert_eq!(args.height, None);
+        assert_eq!(args.bombs, Some(99));
+        assert_eq!(args.seed, Some(7));
+        assert_eq!(args.theme, Some(NumberPalette::Tritanopia));
+        assert!(args.has_board());
+        assert!(parse(&["--versus"]).unwrap().has_board());
+        assert_eq!(parse(&["--fullscreen"]).unwrap().window, WindowPreset::Borderless);
+    }
+
+    #[test]
+    fn invalid_arguments_are_rejected() {
+        assert_eq!(parse(&["--size"]), Err("Unknown argument --size".to_string()));
+        assert_eq!(parse(&["--width"]), Err("Missing value for --width".to_string()));
+        assert_eq!(parse(&["--width", "-3"]), Err("Invalid value -3 for --width".to_string()));
+        assert_eq!(parse(&["--bombs", "ten"]), Err("Invalid value ten for --bombs".to_string()));
+        assert_eq!(parse(&["--theme", "sepia"]), Err("Unknown theme sepia".to_string()));
+        assert_eq!(parse(&["--window", "huge"]), Err("Unknown window preset huge".to_string()));
+    }
+}
@@ -2,42 +2,98 @@ use bevy::prelude::*;
 #[cfg(feature = "debug")]
 use bevy_inspector_egui::WorldInspectorPlugin;
 
-use board_plugin::resources::BoardOptions;
+use board_plugin::events::{BoardCompletedEvent, PauseEvent, SubmitScoreEvent};
+use board_plugin::resources::{
+    BoardOptions, BoardOptionsBuilder, CameraShake, Celebration, LeaderboardKey, Leaderboards,
+    ScoreEntry, Settings,
+};
 use board_plugin::BoardPlugin;
+use cli::{CliArgs, USAGE};
+use menu_plugin::resources::MenuOptions;
+use menu_plugin::MenuPlugin;
+
+mod cli;
+mod window;
 
 #[derive(Debug, Clone, Eq, PartialEq, Hash)]
 pub enum AppState {
+    Menu,
     InGame,
-    Out,
 }
 
 fn main() {
+    let args = match CliArgs::parse(std::env::args().skip(1)) {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("{}\n\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    if args.help {
+        println!("{}", USAGE);
+        return;
+    }
     let mut app = App::new();
 
-    app.insert_resource(WindowDescriptor {
-        title: "Mine Sweeper!".to_string(),
-        width: 700.,
-        height: 800.,
-        ..Default::default()
-    })
-    .add_plugins(DefaultPlugins);
+    app.insert_resource(args.window.descriptor()).add_plugins(DefaultPlugins);
 
     #[cfg(feature = "debug")]
     app.add_plugin(WorldInspectorPlugin::new());
 
-    // Board plugin options
-    app.insert_resource(BoardOptions {
-        map_size: (20, 20),
-        bomb_count: 40,
-        tile_padding: 3.0,
-        safe_start: true,
+    // Board plugin options, the map size and layout are chosen in the menu
+    let menu_options = MenuOptions {
+        base: BoardOptionsBuilder::default()
+            .tile_padding(3.0)
+            .safe_start(true)
+            .hide_on_pause(true)
+            .builtin_end_screens(true)
+            .input_debounce(0.15)
+            .camera_shake(CameraShake { amplitude: 8., duration: 0.4 })
+            .celebration(Celebration { confetti_count: 150, duration: 4., color_wave: true })
+            .build()
+            .expect("Invalid base board options"),
         ..Default::default()
-    })
-    .add_state(AppState::InGame)
-    .add_plugin(BoardPlugin { running_state: AppState::InGame })
-    .add_startup_system(camera_setup)
-    .add_system(state_handler)
-    .run();
+    };
+    // Boards configured from the command line skip the menu
+    let initial_state = if args.has_board() {
+        app.insert_resource(cli_board_options(&args, &menu_options));
+        AppState::InGame
+    } else {
+        AppState::Menu
+    };
+    if let Some(theme) = args.theme {
+        let mut settings = match Settings::default_path().map(Settings::load) {
+            Some(Ok(s)) => s,
+            _ => Settings::default(),
+        };
+        settings.palette = theme;
+        app.insert_resource(settings);
+    }
+    app.insert_resource(menu_options)
+        .add_state(initial_state)
+        .add_plugin(MenuPlugin { menu_state: AppState::Menu, running_state: AppState::InGame })
+        .add_plugin(BoardPlugin { running_state: AppState::InGame })
+        .add_startup_system(camera_setup)
+        .add_system(state_handler)
+        .add_system(pause_handler)
+        .add_system(submit_score)
+        .run();
+}
+
+/// Custom menu board with the command line overrides
+fn cli_board_options(args: &CliArgs, menu_options: &MenuOptions) -> BoardOptions {
+    let width = args.width.unwrap_or(menu_options.custom_width).max(1);
+    let height = args.height.unwrap_or(menu_options.custom_height).max(1);
+    let max_bombs = (width as u32 * height as u32 - 1).min(u16::MAX as u32) as u16;
+    let bombs = args.bombs.unwrap_or(menu_options.custom_bombs).min(max_bombs);
+    let mut builder = BoardOptionsBuilder::from(menu_options.base.clone())
+        .map_size(width, height)
+        .bomb_count(bombs)
+        .versus(args.versus);
+    if let Some(seed) = args.seed {
+        builder = builder.seed(seed);
+    }
+    builder.build().expect("Invalid command line board options")
 }
 
 fn camera_setup(mut commands: Commands) {
@@ -49,14 +105,43 @@ fn state_handler(mut state: ResMut<State<AppState>>, keys: Res<Input<KeyCode>>)
         debug!("clearing detected");
         if state.current() == &AppState::InGame {
             info!("clearing game");
-            state.set(AppState::Out).unwrap();
+            state.set(AppState::Menu).unwrap();
         }
     }
-    if keys.just_pressed(KeyCode::G) {
-        debug!("loading detected");
-        if state.current() == &AppState::Out {
-            info!("loading game");
-            state.set(AppState::InGame).unwrap();
+}
+
+fn pause_handler(keys: Res<Input<KeyCode>>, mut pause_ewr: EventWriter<PauseEvent>) {
+    if keys.just_pressed(KeyCode::P) {
+        pause_ewr.send(PauseEvent::Toggle);
+    }
+}
+
+fn submit_score(
+    board_options: Option<Res<BoardOptions>>,
+    leaderboards: Res<Leaderboards>,
+    mut board_completed_evr: EventReader<BoardCompletedEvent>,
+    mut submit_score_ewr: EventWriter<SubmitScoreEvent>,
+) {
+    // Options are only missing before the first game
+    let board_options = match board_options {
+        None => return,
+        Some(o) => o,
+    };
+    for event in board_completed_evr.iter() {
+        let key = LeaderboardKey::from_options(&board_options);
+        if let Some(leaderboard) = leaderboards.get(&key) {
+            for (i, entry) in leaderboard.top(5).iter().enumerate() {
+                info!("#{} {} - {:.2}s", i + 1, entry.name, entry.time);
+            }
         }
+        submit_score_ewr.send(SubmitScoreEvent {
+            key,
+            entry: ScoreEntry {
+                name: std::env::var("USER").unwrap_or_else(|_| "Player".to_string()),
+                time: event.time,
+                bbbv: event.bbbv,
+                clicks: event.clicks,
+            },
+        });
     }
 }
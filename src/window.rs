@@ -0,0 +1,54 @@
+use bevy::prelude::WindowDescriptor;
+use bevy::window::WindowMode;
+
+/// Window presets of the example app, fullscreen being toggled at runtime with `F11`
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum WindowPreset {
+    /// Fits small laptop screens
+    Small,
+    /// Default window size
+    Medium,
+    /// Fits full HD screens
+    Large,
+    /// Borderless window covering the monitor, at its resolution
+    Borderless,
+    /// Exclusive fullscreen, at the monitor best video mode
+    Fullscreen,
+}
+
+impl Default for WindowPreset {
+    fn default() -> Self {
+        Self::Medium
+    }
+}
+
+impl WindowPreset {
+    /// Parses a preset from its lowercase name
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "small" => Some(Self::Small),
+            "medium" => Some(Self::Medium),
+            "large" => Some(Self::Large),
+            "borderless" => Some(Self::Borderless),
+            "fullscreen" => Some(Self::Fullscreen),
+            _ => None,
+        }
+    }
+
+    pub fn descriptor(&self) -> WindowDescriptor {
+        let (width, height, mode) = match self {
+            Self::Small => (500., 580., WindowMode::Windowed),
+            Self::Medium => (700., 800., WindowMode::Windowed),
+            Self::Large => (900., 1000., WindowMode::Windowed),
+            Self::Borderless => (700., 800., WindowMode::BorderlessFullscreen),
+            Self::Fullscreen => (700., 800., WindowMode::Fullscreen),
+        };
+        WindowDescriptor {
+            title: "Mine Sweeper!".to_string(),
+            width,
+            height,
+            mode,
+            ..Default::default()
+        }
+    }
+}